@@ -53,6 +53,7 @@ export const getIdentity = (seed: string): Ed25519KeyIdentity => {
  * WASM_FILE_PATH  - Path to the WASM file (artifact)
  * VERSION         - Version of the artifact
  * DESCRIPTION     - Description to the version of the artifact
+ * SCHEMA_VERSION  - Stable memory schema version of the artifact
  * CANISTER_ID     - (id of vault_repo canister)
  * IDENTITY_SEED   -
  */
@@ -62,12 +63,13 @@ async function saveVaultWasm() {
     const canisterId = process.env.CANISTER_ID;
     const identitySeed = process.env.IDENTITY_SEED;
     const description = process.env.DESCRIPTION;
+    const schemaVersion = process.env.SCHEMA_VERSION;
 
     if (!wasmFilePath || !version || !canisterId || !identitySeed) {
         throw new Error("Please provide WASM_FILE_PATH, VERSION, CANISTER_ID, IDENTITY_SEED as environment variables.");
     }
 
-    console.log({wasmFilePath,version,canisterId,description});
+    console.log({wasmFilePath,version,canisterId,description,schemaVersion});
     
     try {
         const wasmBytes = readWasmFile(wasmFilePath);
@@ -80,6 +82,7 @@ async function saveVaultWasm() {
             hash: hash,
             version: version,
             description: description ? [description] : [],
+            schema_version: schemaVersion ? [Number(schemaVersion)] : [],
         };
 
         console.log({principal});
@@ -0,0 +1,55 @@
+use std::cell::RefCell;
+
+use ic_cdk::api::{canister_balance, time};
+
+use crate::enums::TransactionState::{Approved, Failed};
+use crate::state::get_current_state;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::transaction::TransactionCandid;
+use crate::transaction::transaction_service::{execute_approved_transactions, get_all_transactions, get_unfinished_transactions, store_transaction};
+use crate::transaction::transfer::auto_top_up_transaction::AutoTopUpTransaction;
+
+const CHECK_INTERVAL_NANOS: u64 = 60 * 60 * 1_000_000_000;
+const DAY_NANOS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+thread_local! {
+    static LAST_CHECK: RefCell<u64> = RefCell::new(0);
+}
+
+//called from the global timer, the check itself runs at most once per interval
+pub async fn check_auto_top_up() {
+    let now = time();
+    if LAST_CHECK.with(|l| *l.borrow() + CHECK_INTERVAL_NANOS > now) {
+        return;
+    }
+    LAST_CHECK.with(|l| l.replace(now));
+    let rule = match get_current_state().auto_top_up {
+        None => { return; }
+        Some(x) => { x }
+    };
+    let cycles_balance = canister_balance();
+    if cycles_balance >= rule.min_cycles {
+        return;
+    }
+    let in_progress = get_unfinished_transactions().iter()
+        .any(|tr| matches!(tr.to_candid(), TransactionCandid::AutoTopUpTransactionV(_)));
+    if in_progress {
+        return;
+    }
+    //failed top-ups count too when the ledger transfer went through
+    let spent: u64 = get_all_transactions().into_iter()
+        .filter(|tr| tr.get_common_ref().created_date + DAY_NANOS > now)
+        .filter_map(|tr| match tr.to_candid() {
+            TransactionCandid::AutoTopUpTransactionV(t) => Some(t),
+            _ => None
+        })
+        .filter(|t| !t.get_state().eq(&Failed) || t.block_index.is_some())
+        .map(|t| t.amount)
+        .sum();
+    if spent + rule.amount > rule.daily_cap {
+        return;
+    }
+    let trs = AutoTopUpTransaction::new(Approved, rule.wallet, rule.amount, cycles_balance);
+    store_transaction(trs.clone_self());
+    execute_approved_transactions().await
+}
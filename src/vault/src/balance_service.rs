@@ -0,0 +1,85 @@
+use std::cell::RefCell;
+
+use candid::{CandidType, Nat, Principal};
+use ic_cdk::api::time;
+use ic_ledger_types::MAINNET_LEDGER_CANISTER_ID;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::Network;
+use crate::state::get_current_state;
+use crate::transfer_service::{get_icp_balance, get_icrc1_balance};
+
+thread_local! {
+    pub static BALANCES: RefCell<Vec<WalletBalance>> = RefCell::new(Default::default());
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct LedgerBalance {
+    pub ledger: Principal,
+    pub balance: Option<Nat>,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct WalletBalance {
+    pub wallet: String,
+    pub balances: Vec<LedgerBalance>,
+    pub refreshed_date: u64,
+}
+
+//an empty list stands for all wallets of the vault
+fn get_wallet_uids(wallet_uids: Vec<String>) -> Vec<String> {
+    get_current_state().wallets.into_iter()
+        .filter(|w| w.network == Network::IC)
+        .map(|w| w.uid)
+        .filter(|uid| wallet_uids.is_empty() || wallet_uids.contains(uid))
+        .collect()
+}
+
+//queries the ICP ledger and every registered ICRC-1 ledger and refreshes the cache
+pub async fn refresh_balances(wallet_uids: Vec<String>) -> Vec<WalletBalance> {
+    let ledgers: Vec<Principal> = get_current_state().icrc1_canisters.into_iter()
+        .map(|c| c.ledger)
+        .collect();
+    let mut balances = Vec::new();
+    for uid in get_wallet_uids(wallet_uids) {
+        let mut wallet_balances = vec![to_ledger_balance(MAINNET_LEDGER_CANISTER_ID, get_icp_balance(uid.clone()).await)];
+        for ledger in ledgers.iter() {
+            wallet_balances.push(to_ledger_balance(ledger.clone(), get_icrc1_balance(ledger.clone(), uid.clone()).await));
+        }
+        balances.push(WalletBalance {
+            wallet: uid,
+            balances: wallet_balances,
+            refreshed_date: time(),
+        });
+    }
+    BALANCES.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        for balance in balances.iter() {
+            cache.retain(|existing| existing.wallet != balance.wallet);
+            cache.push(balance.clone());
+        }
+    });
+    balances
+}
+
+pub fn get_cached_balances(wallet_uids: Vec<String>) -> Vec<WalletBalance> {
+    let uids = get_wallet_uids(wallet_uids);
+    BALANCES.with(|cache| {
+        cache.borrow().iter()
+            .filter(|b| uids.contains(&b.wallet))
+            .cloned()
+            .collect()
+    })
+}
+
+fn to_ledger_balance(ledger: Principal, result: Result<Nat, String>) -> LedgerBalance {
+    match result {
+        Ok(balance) => {
+            LedgerBalance { ledger, balance: Some(balance), error: None }
+        }
+        Err(error) => {
+            LedgerBalance { ledger, balance: None, error: Some(error) }
+        }
+    }
+}
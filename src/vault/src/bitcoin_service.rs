@@ -0,0 +1,326 @@
+use std::cell::RefCell;
+
+use bech32::{FromBase32, Variant};
+use candid::CandidType;
+use ic_cdk::api::management_canister::bitcoin::{bitcoin_get_balance, bitcoin_get_current_fee_percentiles, bitcoin_get_utxos, bitcoin_send_transaction, BitcoinNetwork, GetBalanceRequest, GetCurrentFeePercentilesRequest, GetUtxosRequest, Outpoint, Satoshi, SendTransactionRequest, Utxo, UtxoFilter};
+use ic_cdk::api::time;
+use ripemd::Ripemd160;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::get_btc_network;
+use crate::ecdsa_service::{get_derivation_path, get_public_key, sign};
+
+const SIGHASH_ALL: u32 = 1;
+const DUST_THRESHOLD: Satoshi = 546;
+//used when the network has no fee percentiles yet, e.g. on regtest
+const DEFAULT_FEE_PER_VBYTE: u64 = 2000;
+//a spend which is not mined by then is considered dropped from the mempool and its inputs are released
+const PENDING_SPEND_TIMEOUT_NANOS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+thread_local! {
+    //the utxos api only reflects mined blocks, spends broadcast since then are tracked here
+    static PENDING_SPENDS: RefCell<Vec<PendingSpend>> = RefCell::new(Vec::new());
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct PendingSpend {
+    pub wallet: String,
+    pub spent: Vec<Outpoint>,
+    //the change output returns to the wallet and becomes spendable once mined
+    pub change: Option<Outpoint>,
+    pub created_date: u64,
+}
+
+pub fn get_pending_spends() -> Vec<PendingSpend> {
+    PENDING_SPENDS.with(|p| p.borrow().clone())
+}
+
+pub fn restore_pending_spends(spends: Vec<PendingSpend>) {
+    PENDING_SPENDS.with(|p| p.replace(spends));
+}
+
+fn wallet_path(wallet_uid: &String) -> Vec<Vec<u8>> {
+    get_derivation_path("btc", wallet_uid)
+}
+
+pub async fn get_btc_address(wallet_uid: &String) -> Result<String, String> {
+    let public_key = get_public_key(wallet_path(wallet_uid)).await?;
+    Ok(to_p2pkh_address(&public_key, get_btc_network()))
+}
+
+pub async fn get_btc_balance(wallet_uid: &String) -> Result<Satoshi, String> {
+    let address = get_btc_address(wallet_uid).await?;
+    bitcoin_get_balance(GetBalanceRequest { address, network: get_btc_network(), min_confirmations: None }).await
+        .map(|(balance, )| balance)
+        .map_err(|e| format!("failed to get balance: {:?}", e))
+}
+
+pub async fn get_btc_utxos(wallet_uid: &String) -> Result<Vec<Utxo>, String> {
+    let address = get_btc_address(wallet_uid).await?;
+    get_utxos(address).await
+}
+
+async fn get_utxos(address: String) -> Result<Vec<Utxo>, String> {
+    let mut utxos = Vec::new();
+    let mut filter = None;
+    loop {
+        let (response, ) = bitcoin_get_utxos(GetUtxosRequest { address: address.clone(), network: get_btc_network(), filter }).await
+            .map_err(|e| format!("failed to get utxos: {:?}", e))?;
+        utxos.extend(response.utxos);
+        match response.next_page {
+            None => { return Ok(utxos); }
+            Some(page) => {
+                filter = Some(UtxoFilter::Page(page));
+            }
+        }
+    }
+}
+
+//builds a P2PKH transaction from the wallet utxos, signs every input with the wallet key and broadcasts it
+pub async fn send_btc(wallet_uid: &String, destination: &String, amount: Satoshi) -> Result<String, String> {
+    let network = get_btc_network();
+    let destination_script = address_to_script(destination, network)?;
+    let public_key = get_public_key(wallet_path(wallet_uid)).await?;
+    let own_address = to_p2pkh_address(&public_key, network);
+    let own_script = p2pkh_script(&hash160(&public_key));
+    let fee_per_vbyte = get_fee_per_vbyte(network).await?;
+    //no await between the selection and the registration of the spend, concurrent sends pick other utxos
+    let mut utxos = get_spendable_utxos(wallet_uid, get_utxos(own_address).await?);
+    utxos.sort_by(|a, b| b.value.cmp(&a.value));
+
+    let mut inputs = Vec::new();
+    let mut total: Satoshi = 0;
+    let mut fee = estimate_fee(1, 2, fee_per_vbyte);
+    for utxo in utxos {
+        if total >= amount + fee {
+            break;
+        }
+        total += utxo.value;
+        inputs.push(utxo);
+        fee = estimate_fee(inputs.len(), 2, fee_per_vbyte);
+    }
+    if total < amount + fee {
+        return Err(format!("Insufficient funds: {} available, {} required", total, amount + fee));
+    }
+    let mut outputs = vec![(amount, destination_script)];
+    let change = total - amount - fee;
+    if change > DUST_THRESHOLD {
+        outputs.push((change, own_script.clone()));
+    }
+    let spent: Vec<Outpoint> = inputs.iter().map(|u| u.outpoint.clone()).collect();
+    PENDING_SPENDS.with(|p| p.borrow_mut().push(PendingSpend {
+        wallet: wallet_uid.clone(),
+        spent: spent.clone(),
+        change: None,
+        created_date: time(),
+    }));
+    match sign_and_send(wallet_uid, &public_key, &own_script, &inputs, &outputs, network).await {
+        Ok(txid) => {
+            let change = if outputs.len() > 1 {
+                Some(Outpoint { txid: txid.iter().rev().cloned().collect(), vout: 1 })
+            } else {
+                None
+            };
+            PENDING_SPENDS.with(|p| {
+                if let Some(spend) = p.borrow_mut().iter_mut().find(|s| s.spent == spent) {
+                    spend.change = change;
+                }
+            });
+            Ok(hex::encode(txid))
+        }
+        Err(message) => {
+            PENDING_SPENDS.with(|p| p.borrow_mut().retain(|s| s.spent != spent));
+            Err(message)
+        }
+    }
+}
+
+//spends whose inputs left the utxo set are mined, the others keep their inputs and change out of the selection
+fn get_spendable_utxos(wallet_uid: &String, utxos: Vec<Utxo>) -> Vec<Utxo> {
+    let now = time();
+    PENDING_SPENDS.with(|p| {
+        let mut pending = p.borrow_mut();
+        pending.retain(|s| !s.wallet.eq(wallet_uid)
+            || (s.created_date + PENDING_SPEND_TIMEOUT_NANOS > now
+            && s.spent.iter().any(|o| utxos.iter().any(|u| u.outpoint.eq(o)))));
+        let excluded: Vec<&Outpoint> = pending.iter()
+            .filter(|s| s.wallet.eq(wallet_uid))
+            .flat_map(|s| s.spent.iter().chain(s.change.iter()))
+            .collect();
+        utxos.into_iter()
+            .filter(|u| !excluded.contains(&&u.outpoint))
+            .collect()
+    })
+}
+
+//returns the txid in the displayed byte order
+async fn sign_and_send(wallet_uid: &String, public_key: &Vec<u8>, own_script: &Vec<u8>, inputs: &Vec<Utxo>,
+                       outputs: &Vec<(Satoshi, Vec<u8>)>, network: BitcoinNetwork) -> Result<Vec<u8>, String> {
+    let mut script_sigs = Vec::new();
+    for i in 0..inputs.len() {
+        let mut unsigned_scripts = vec![Vec::new(); inputs.len()];
+        unsigned_scripts[i] = own_script.clone();
+        let mut preimage = serialize_transaction(inputs, &unsigned_scripts, outputs);
+        preimage.extend(SIGHASH_ALL.to_le_bytes());
+        let signature = sign(wallet_path(wallet_uid), sha256d(&preimage)).await?;
+        let mut der = to_der(&signature);
+        der.push(SIGHASH_ALL as u8);
+        let mut script_sig = push_data(&der);
+        script_sig.extend(push_data(public_key));
+        script_sigs.push(script_sig);
+    }
+    let transaction = serialize_transaction(inputs, &script_sigs, outputs);
+    let mut txid = sha256d(&transaction);
+    txid.reverse();
+    bitcoin_send_transaction(SendTransactionRequest { transaction, network }).await
+        .map_err(|e| format!("failed to send transaction: {:?}", e))?;
+    Ok(txid)
+}
+
+async fn get_fee_per_vbyte(network: BitcoinNetwork) -> Result<u64, String> {
+    let (percentiles, ) = bitcoin_get_current_fee_percentiles(GetCurrentFeePercentilesRequest { network }).await
+        .map_err(|e| format!("failed to get fee percentiles: {:?}", e))?;
+    //percentiles are in millisatoshi per byte, the median is taken
+    Ok(percentiles.get(percentiles.len() / 2).cloned().unwrap_or(DEFAULT_FEE_PER_VBYTE))
+}
+
+fn estimate_fee(inputs: usize, outputs: usize, fee_per_vbyte: u64) -> Satoshi {
+    let size = 10 + 148 * inputs as u64 + 34 * outputs as u64;
+    size * fee_per_vbyte / 1000
+}
+
+fn serialize_transaction(inputs: &Vec<Utxo>, script_sigs: &Vec<Vec<u8>>, outputs: &Vec<(Satoshi, Vec<u8>)>) -> Vec<u8> {
+    let mut tx = Vec::new();
+    tx.extend(2u32.to_le_bytes());
+    tx.extend(var_int(inputs.len() as u64));
+    for (utxo, script_sig) in inputs.iter().zip(script_sigs.iter()) {
+        tx.extend(utxo.outpoint.txid.iter());
+        tx.extend(utxo.outpoint.vout.to_le_bytes());
+        tx.extend(var_int(script_sig.len() as u64));
+        tx.extend(script_sig.iter());
+        tx.extend(0xffffffffu32.to_le_bytes());
+    }
+    tx.extend(var_int(outputs.len() as u64));
+    for (value, script) in outputs {
+        tx.extend(value.to_le_bytes());
+        tx.extend(var_int(script.len() as u64));
+        tx.extend(script.iter());
+    }
+    tx.extend(0u32.to_le_bytes());
+    tx
+}
+
+fn var_int(n: u64) -> Vec<u8> {
+    match n {
+        0..=0xfc => vec![n as u8],
+        0xfd..=0xffff => [vec![0xfd], (n as u16).to_le_bytes().to_vec()].concat(),
+        0x10000..=0xffffffff => [vec![0xfe], (n as u32).to_le_bytes().to_vec()].concat(),
+        _ => [vec![0xff], n.to_le_bytes().to_vec()].concat(),
+    }
+}
+
+fn push_data(data: &[u8]) -> Vec<u8> {
+    [vec![data.len() as u8], data.to_vec()].concat()
+}
+
+//the management canister returns r || s, bitcoin expects a DER sequence
+fn to_der(signature: &[u8]) -> Vec<u8> {
+    let to_der_int = |x: &[u8]| {
+        let mut x: Vec<u8> = x.iter().cloned().skip_while(|b| *b == 0).collect();
+        if x.is_empty() || x[0] & 0x80 != 0 {
+            x.insert(0, 0);
+        }
+        [vec![0x02, x.len() as u8], x].concat()
+    };
+    let r = to_der_int(&signature[..32]);
+    let s = to_der_int(&signature[32..]);
+    [vec![0x30, (r.len() + s.len()) as u8], r, s].concat()
+}
+
+fn sha256d(data: &[u8]) -> Vec<u8> {
+    Sha256::digest(Sha256::digest(data)).to_vec()
+}
+
+fn hash160(data: &[u8]) -> Vec<u8> {
+    Ripemd160::digest(Sha256::digest(data)).to_vec()
+}
+
+fn p2pkh_script(hash: &[u8]) -> Vec<u8> {
+    [vec![0x76, 0xa9, 0x14], hash.to_vec(), vec![0x88, 0xac]].concat()
+}
+
+fn p2pkh_prefix(network: BitcoinNetwork) -> u8 {
+    match network {
+        BitcoinNetwork::Mainnet => 0x00,
+        _ => 0x6f,
+    }
+}
+
+fn p2sh_prefix(network: BitcoinNetwork) -> u8 {
+    match network {
+        BitcoinNetwork::Mainnet => 0x05,
+        _ => 0xc4,
+    }
+}
+
+fn bech32_hrp(network: BitcoinNetwork) -> &'static str {
+    match network {
+        BitcoinNetwork::Mainnet => "bc",
+        BitcoinNetwork::Testnet => "tb",
+        BitcoinNetwork::Regtest => "bcrt",
+    }
+}
+
+//BIP-173 and BIP-350 rules, a malformed program would make the output unspendable
+fn validate_witness_program(version: u8, program: &Vec<u8>, variant: Variant) -> Result<(), String> {
+    if version > 16 {
+        return Err(format!("Invalid witness version {}", version));
+    }
+    let expected_variant = if version == 0 { Variant::Bech32 } else { Variant::Bech32m };
+    if variant != expected_variant {
+        return Err(format!("Invalid checksum variant for witness version {}", version));
+    }
+    if program.len() < 2 || program.len() > 40 {
+        return Err(format!("Invalid witness program length {}", program.len()));
+    }
+    if version == 0 && program.len() != 20 && program.len() != 32 {
+        return Err(format!("Invalid witness v0 program length {}", program.len()));
+    }
+    Ok(())
+}
+
+fn to_p2pkh_address(public_key: &[u8], network: BitcoinNetwork) -> String {
+    let payload = [vec![p2pkh_prefix(network)], hash160(public_key)].concat();
+    bs58::encode(payload).with_check().into_string()
+}
+
+//supports P2PKH and P2SH base58 addresses and segwit bech32/bech32m addresses
+pub fn address_to_script(address: &String, network: BitcoinNetwork) -> Result<Vec<u8>, String> {
+    if let Ok((hrp, data, variant)) = bech32::decode(address) {
+        if hrp != bech32_hrp(network) {
+            return Err("Address is for another network".to_string());
+        }
+        if data.is_empty() {
+            return Err("Empty witness program".to_string());
+        }
+        let version = data[0].to_u8();
+        let program = Vec::<u8>::from_base32(&data[1..])
+            .map_err(|e| format!("Invalid witness program: {:?}", e))?;
+        validate_witness_program(version, &program, variant)?;
+        let version_op = if version == 0 { 0x00 } else { 0x50 + version };
+        return Ok([vec![version_op], push_data(&program)].concat());
+    }
+    let decoded = bs58::decode(address).with_check(None).into_vec()
+        .map_err(|e| format!("Invalid address: {:?}", e))?;
+    if decoded.len() != 21 {
+        return Err("Invalid address length".to_string());
+    }
+    if decoded[0] == p2pkh_prefix(network) {
+        Ok(p2pkh_script(&decoded[1..]))
+    } else if decoded[0] == p2sh_prefix(network) {
+        Ok([vec![0xa9, 0x14], decoded[1..].to_vec(), vec![0x87]].concat())
+    } else {
+        Err("Address is for another network".to_string())
+    }
+}
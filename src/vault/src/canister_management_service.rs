@@ -0,0 +1,271 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use candid::{CandidType, Principal};
+use ic_cdk::call;
+use ic_cdk::api::call::CallResult;
+use ic_cdk::api::management_canister::main::{CanisterIdRecord, CanisterInstallMode, CanisterSettings, delete_canister, start_canister, stop_canister, update_settings, UpdateSettingsArgument};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::transaction::upgrade::upgrade_transaction::{get_repo_wasm, get_verified_hash};
+use crate::util::caller_to_address;
+
+//the management canister accepts chunks of up to 1 MiB
+const MAX_CHUNK_SIZE: usize = 1024 * 1024;
+const MAX_WASM_SIZE: usize = 20 * MAX_CHUNK_SIZE;
+const MAX_UPLOADED_WASMS: usize = 5;
+
+//uploads live on the heap only, a vault upgrade drops them together with the chunks in progress
+thread_local! {
+    static WASM_CHUNKS: RefCell<HashMap<String, Vec<Vec<u8>>>> = RefCell::new(HashMap::new());
+    static WASM_MODULES: RefCell<HashMap<String, Vec<Vec<u8>>>> = RefCell::new(HashMap::new());
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub enum WasmSource {
+    Repo { version: String },
+    //sha256 hex of a module committed with commit_wasm_upload
+    Upload { hash: String },
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct UploadedWasm {
+    pub hash: String,
+    pub size: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct CanisterSnapshot {
+    pub id: Vec<u8>,
+    pub taken_at_timestamp: u64,
+    pub total_size: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub enum CanisterOperation {
+    InstallCode { mode: CanisterInstallMode, wasm: WasmSource, arg: Vec<u8> },
+    UpdateSettings { settings: CanisterSettings },
+    Start,
+    Stop,
+    Delete,
+    TakeSnapshot { replace_snapshot: Option<Vec<u8>> },
+    LoadSnapshot { snapshot_id: Vec<u8> },
+    DeleteSnapshot { snapshot_id: Vec<u8> },
+}
+
+#[derive(CandidType)]
+struct TakeCanisterSnapshotArgs {
+    canister_id: Principal,
+    replace_snapshot: Option<Vec<u8>>,
+}
+
+#[derive(CandidType)]
+struct LoadCanisterSnapshotArgs {
+    canister_id: Principal,
+    snapshot_id: Vec<u8>,
+    sender_canister_version: Option<u64>,
+}
+
+#[derive(CandidType)]
+struct DeleteCanisterSnapshotArgs {
+    canister_id: Principal,
+    snapshot_id: Vec<u8>,
+}
+
+#[derive(CandidType)]
+struct UploadChunkArgs {
+    canister_id: Principal,
+    chunk: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize)]
+struct ChunkHash {
+    hash: Vec<u8>,
+}
+
+#[derive(CandidType)]
+struct InstallChunkedCodeArgs {
+    mode: CanisterInstallMode,
+    target_canister: Principal,
+    store_canister: Option<Principal>,
+    chunk_hashes_list: Vec<ChunkHash>,
+    wasm_module_hash: Vec<u8>,
+    arg: Vec<u8>,
+    sender_canister_version: Option<u64>,
+}
+
+fn get_size(chunks: &[Vec<u8>]) -> usize {
+    chunks.iter().map(|c| c.len()).sum()
+}
+
+//chunks are collected per caller, so admins can upload in parallel
+pub fn append_wasm_chunk(chunk: Vec<u8>) -> Result<u64, String> {
+    if chunk.len() > MAX_CHUNK_SIZE {
+        return Err(format!("Chunk is larger than {} bytes", MAX_CHUNK_SIZE));
+    }
+    WASM_CHUNKS.with(|c| {
+        let mut chunks = c.borrow_mut();
+        let buffer = chunks.entry(caller_to_address()).or_default();
+        let size = get_size(buffer) + chunk.len();
+        if size > MAX_WASM_SIZE {
+            return Err(format!("Wasm is larger than {} bytes", MAX_WASM_SIZE));
+        }
+        buffer.push(chunk);
+        Ok(size as u64)
+    })
+}
+
+pub fn commit_wasm_upload() -> Result<UploadedWasm, String> {
+    let chunks = WASM_CHUNKS.with(|c| c.borrow_mut().remove(&caller_to_address()))
+        .ok_or("Nothing uploaded".to_string())?;
+    let mut hasher = Sha256::new();
+    chunks.iter().for_each(|c| hasher.update(c));
+    let uploaded = UploadedWasm {
+        hash: hex::encode(hasher.finalize()),
+        size: get_size(&chunks) as u64,
+    };
+    WASM_MODULES.with(|m| {
+        let mut modules = m.borrow_mut();
+        if !modules.contains_key(&uploaded.hash) && modules.len() >= MAX_UPLOADED_WASMS {
+            return Err(format!("At most {} wasms can be uploaded, delete one first", MAX_UPLOADED_WASMS));
+        }
+        modules.insert(uploaded.hash.clone(), chunks);
+        Ok(uploaded)
+    })
+}
+
+pub fn delete_uploaded_wasm(hash: &String) -> Result<(), String> {
+    WASM_MODULES.with(|m| m.borrow_mut().remove(hash))
+        .map(|_| ())
+        .ok_or(format!("Wasm {} is not uploaded", hash))
+}
+
+pub fn get_uploaded_wasms() -> Vec<UploadedWasm> {
+    WASM_MODULES.with(|m| m.borrow().iter()
+        .map(|(hash, chunks)| UploadedWasm { hash: hash.clone(), size: get_size(chunks) as u64 })
+        .collect())
+}
+
+//the sha256 hex the module has to match on execution, repo modules are checked against the published hash
+pub async fn pin_wasm_hash(source: &WasmSource) -> Result<String, String> {
+    match source {
+        WasmSource::Repo { version } => {
+            let version = Version::parse(version)
+                .map_err(|e| format!("Failed to parse semver!: {}", e))?;
+            let hash = get_verified_hash(&version).await?;
+            Ok(hash.trim_start_matches("0x").to_string())
+        }
+        WasmSource::Upload { hash } => {
+            if !WASM_MODULES.with(|m| m.borrow().contains_key(hash)) {
+                return Err(format!("Wasm {} is not uploaded", hash));
+            }
+            Ok(hash.clone())
+        }
+    }
+}
+
+//returns the module chunks with the sha256 hex of the whole module
+pub async fn get_wasm(source: &WasmSource) -> Result<(Vec<Vec<u8>>, String), String> {
+    match source {
+        WasmSource::Repo { version } => {
+            let version = Version::parse(version)
+                .map_err(|e| format!("Failed to parse semver!: {}", e))?;
+            let wasm = get_repo_wasm(&version).await?;
+            let hash = hex::encode(Sha256::digest(&wasm.wasm_module));
+            let chunks = wasm.wasm_module.chunks(MAX_CHUNK_SIZE).map(|c| c.to_vec()).collect();
+            Ok((chunks, hash))
+        }
+        WasmSource::Upload { hash } => {
+            let chunks = WASM_MODULES.with(|m| m.borrow().get(hash).cloned())
+                .ok_or(format!("Wasm {} is not uploaded", hash))?;
+            Ok((chunks, hash.clone()))
+        }
+    }
+}
+
+//the chunks go to the store of the target canister, so the module is never assembled in a single message
+async fn install_chunked(canister_id: Principal, mode: CanisterInstallMode, chunks: Vec<Vec<u8>>, hash: &String, arg: Vec<u8>) -> CallResult<()> {
+    call::<_, ()>(Principal::management_canister(), "clear_chunk_store", (CanisterIdRecord { canister_id }, )).await?;
+    let mut chunk_hashes_list = Vec::new();
+    for chunk in chunks {
+        let (chunk_hash, ): (ChunkHash, ) = call(Principal::management_canister(), "upload_chunk", (UploadChunkArgs { canister_id, chunk }, )).await?;
+        chunk_hashes_list.push(chunk_hash);
+    }
+    let args = InstallChunkedCodeArgs {
+        mode,
+        target_canister: canister_id,
+        store_canister: None,
+        chunk_hashes_list,
+        wasm_module_hash: hex::decode(hash).unwrap_or_default(),
+        arg,
+        sender_canister_version: None,
+    };
+    let result = call::<_, ()>(Principal::management_canister(), "install_chunked_code", (args, )).await;
+    let _ = call::<_, ()>(Principal::management_canister(), "clear_chunk_store", (CanisterIdRecord { canister_id }, )).await;
+    result
+}
+
+//returns the snapshot for TakeSnapshot, the wasm hash for InstallCode,
+//which has to match the hash pinned when the operation was requested
+pub async fn run_operation(canister_id: Principal, operation: &CanisterOperation, pinned_hash: Option<String>) -> Result<(Option<CanisterSnapshot>, Option<String>), String> {
+    let format_error = |(code, msg)| format!("{:?}: {}", code, msg);
+    match operation {
+        CanisterOperation::InstallCode { mode, wasm, arg } => {
+            let (chunks, hash) = get_wasm(wasm).await?;
+            if let Some(pinned_hash) = pinned_hash {
+                if pinned_hash != hash {
+                    return Err(format!("Wasm hash mismatch: expected {}, got {}", pinned_hash, hash));
+                }
+            }
+            install_chunked(canister_id, mode.clone(), chunks, &hash, arg.clone()).await
+                .map_err(format_error)?;
+            Ok((None, Some(hash)))
+        }
+        CanisterOperation::UpdateSettings { settings } => {
+            update_settings(UpdateSettingsArgument { canister_id, settings: settings.clone() }).await
+                .map_err(format_error)?;
+            Ok((None, None))
+        }
+        CanisterOperation::Start => {
+            start_canister(CanisterIdRecord { canister_id }).await
+                .map_err(format_error)?;
+            Ok((None, None))
+        }
+        CanisterOperation::Stop => {
+            stop_canister(CanisterIdRecord { canister_id }).await
+                .map_err(format_error)?;
+            Ok((None, None))
+        }
+        CanisterOperation::Delete => {
+            delete_canister(CanisterIdRecord { canister_id }).await
+                .map_err(format_error)?;
+            Ok((None, None))
+        }
+        CanisterOperation::TakeSnapshot { replace_snapshot } => {
+            let args = TakeCanisterSnapshotArgs { canister_id, replace_snapshot: replace_snapshot.clone() };
+            let (snapshot, ): (CanisterSnapshot, ) = call(Principal::management_canister(), "take_canister_snapshot", (args, )).await
+                .map_err(format_error)?;
+            Ok((Some(snapshot), None))
+        }
+        CanisterOperation::LoadSnapshot { snapshot_id } => {
+            let args = LoadCanisterSnapshotArgs { canister_id, snapshot_id: snapshot_id.clone(), sender_canister_version: None };
+            call::<_, ()>(Principal::management_canister(), "load_canister_snapshot", (args, )).await
+                .map_err(format_error)?;
+            Ok((None, None))
+        }
+        CanisterOperation::DeleteSnapshot { snapshot_id } => {
+            let args = DeleteCanisterSnapshotArgs { canister_id, snapshot_id: snapshot_id.clone() };
+            call::<_, ()>(Principal::management_canister(), "delete_canister_snapshot", (args, )).await
+                .map_err(format_error)?;
+            Ok((None, None))
+        }
+    }
+}
+
+pub async fn list_canister_snapshots(canister_id: Principal) -> Result<Vec<CanisterSnapshot>, String> {
+    let (snapshots, ): (Vec<CanisterSnapshot>, ) = call(Principal::management_canister(), "list_canister_snapshots", (CanisterIdRecord { canister_id }, )).await
+        .map_err(|(code, msg)| format!("{:?}: {}", code, msg))?;
+    Ok(snapshots)
+}
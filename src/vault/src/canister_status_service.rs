@@ -0,0 +1,45 @@
+use std::cell::RefCell;
+
+use candid::{CandidType, Nat};
+use ic_cdk::api::management_canister::main::{canister_status, CanisterIdRecord, CanisterStatusType, DefiniteCanisterSettings};
+use ic_cdk::api::time;
+use ic_cdk::id;
+use serde::{Deserialize, Serialize};
+
+thread_local! {
+    static STATUS: RefCell<Option<VaultCanisterStatus>> = RefCell::new(None);
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct VaultCanisterStatus {
+    pub status: CanisterStatusType,
+    pub settings: DefiniteCanisterSettings,
+    pub module_hash: Option<Vec<u8>>,
+    pub memory_size: Nat,
+    pub cycles: Nat,
+    pub reserved_cycles: Nat,
+    pub idle_cycles_burned_per_day: Nat,
+    pub refreshed_date: u64,
+}
+
+//canister_status is an update call on the management canister, the query serves the last result
+pub async fn refresh_canister_status() -> Result<VaultCanisterStatus, String> {
+    let (response, ) = canister_status(CanisterIdRecord { canister_id: id() }).await
+        .map_err(|(code, msg)| format!("{:?}: {}", code, msg))?;
+    let status = VaultCanisterStatus {
+        status: response.status,
+        settings: response.settings,
+        module_hash: response.module_hash,
+        memory_size: response.memory_size,
+        cycles: response.cycles,
+        reserved_cycles: response.reserved_cycles,
+        idle_cycles_burned_per_day: response.idle_cycles_burned_per_day,
+        refreshed_date: time(),
+    };
+    STATUS.with(|s| s.replace(Some(status.clone())));
+    Ok(status)
+}
+
+pub fn get_cached_canister_status() -> Option<VaultCanisterStatus> {
+    STATUS.with(|s| s.borrow().clone())
+}
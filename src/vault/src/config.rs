@@ -1,12 +1,29 @@
 use std::cell::RefCell;
 
 use candid::{CandidType, Principal};
+use ic_cdk::api::management_canister::bitcoin::BitcoinNetwork;
 use serde::{Deserialize, Serialize};
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct Conf {
     pub origins: Vec<String>,
     pub repo_canister: String,
+    pub btc_network: Option<BitcoinNetwork>,
+    pub ecdsa_key_name: Option<String>,
+    //first pre-release identifier of the versions to propose, "stable" for plain releases, any when empty
+    pub release_channel: Option<String>,
+    //EVM RPC canister and the JSON-RPC endpoint it forwards ETH balance requests to,
+    //ETH wallets are only archived without the zero balance check when no endpoint is set
+    pub evm_rpc_canister: Option<String>,
+    pub eth_rpc_url: Option<String>,
+    //withdrawals only go to the configured minters, mainnet ones when empty
+    pub ckbtc_minter: Option<String>,
+    pub cketh_minter: Option<String>,
+    //ledgers the withdrawn tokens are burned from, mainnet ones when empty
+    pub ckbtc_ledger: Option<String>,
+    pub cketh_ledger: Option<String>,
+    //ICP index canister the ICP deposits are read from, the mainnet one when empty
+    pub icp_index: Option<String>,
 }
 
 
@@ -15,6 +32,16 @@ impl Default for Conf {
         Conf {
             origins: Default::default(),
             repo_canister: "7jlkn-paaaa-aaaap-abvpa-cai".to_string(),
+            btc_network: None,
+            ecdsa_key_name: None,
+            release_channel: None,
+            evm_rpc_canister: None,
+            eth_rpc_url: None,
+            ckbtc_minter: None,
+            cketh_minter: None,
+            ckbtc_ledger: None,
+            cketh_ledger: None,
+            icp_index: None,
         }
     }
 }
@@ -25,4 +52,50 @@ thread_local! {
 
 pub fn get_repo_canister_id() -> Principal {
     CONF.with(|c| Principal::from_text(c.borrow().repo_canister.clone()).unwrap())
-}
\ No newline at end of file
+}
+
+pub fn get_btc_network() -> BitcoinNetwork {
+    CONF.with(|c| c.borrow().btc_network.unwrap_or(BitcoinNetwork::Mainnet))
+}
+
+pub fn get_ecdsa_key_name() -> String {
+    CONF.with(|c| c.borrow().ecdsa_key_name.clone().unwrap_or("key_1".to_string()))
+}
+
+pub fn get_release_channel() -> Option<String> {
+    CONF.with(|c| c.borrow().release_channel.clone())
+}
+
+pub fn get_evm_rpc_canister_id() -> Principal {
+    CONF.with(|c| Principal::from_text(c.borrow().evm_rpc_canister.clone()
+        .unwrap_or("7hfb6-caaaa-aaaar-qadga-cai".to_string())).unwrap())
+}
+
+pub fn get_eth_rpc_url() -> Option<String> {
+    CONF.with(|c| c.borrow().eth_rpc_url.clone())
+}
+
+pub fn get_ckbtc_minter_id() -> Principal {
+    CONF.with(|c| Principal::from_text(c.borrow().ckbtc_minter.clone()
+        .unwrap_or("mqygn-kiaaa-aaaar-qaadq-cai".to_string())).unwrap())
+}
+
+pub fn get_cketh_minter_id() -> Principal {
+    CONF.with(|c| Principal::from_text(c.borrow().cketh_minter.clone()
+        .unwrap_or("sv3dd-oaaaa-aaaar-qacoa-cai".to_string())).unwrap())
+}
+
+pub fn get_ckbtc_ledger_id() -> Principal {
+    CONF.with(|c| Principal::from_text(c.borrow().ckbtc_ledger.clone()
+        .unwrap_or("mxzaz-hqaaa-aaaar-qaada-cai".to_string())).unwrap())
+}
+
+pub fn get_cketh_ledger_id() -> Principal {
+    CONF.with(|c| Principal::from_text(c.borrow().cketh_ledger.clone()
+        .unwrap_or("ss2fx-dyaaa-aaaar-qacoq-cai".to_string())).unwrap())
+}
+
+pub fn get_icp_index_id() -> Principal {
+    CONF.with(|c| Principal::from_text(c.borrow().icp_index.clone()
+        .unwrap_or("qhbym-qaaaa-aaaaa-aaafq-cai".to_string())).unwrap())
+}
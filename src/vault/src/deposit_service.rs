@@ -0,0 +1,262 @@
+use std::cell::RefCell;
+
+use candid::{CandidType, Nat, Principal};
+use ic_cdk::id;
+use ic_ledger_types::{AccountIdentifier, MAINNET_LEDGER_CANISTER_ID, Subaccount as SubLegacy};
+use icrc_ledger_types::icrc1::account::Account;
+use serde::{Deserialize, Serialize};
+
+use crate::config::get_icp_index_id;
+use crate::enums::Network;
+use crate::state::get_current_state;
+use crate::transaction::address_book::address_book::Address;
+use crate::transaction::member::members::Account as MemberAccount;
+use crate::transaction::wallet::wallet::to_subaccount;
+use crate::transfer_service::{get_icp_index_account_transactions, get_index_account_transactions, IcpIndexOperation, IcpTransactionWithId, nat_to_u64, TransactionWithId};
+
+//limits the index calls of a single sync per account, the next sync continues from the cursor
+const SYNC_MAX_PAGES: u64 = 10;
+const INDEX_PAGE: u64 = 500;
+
+thread_local! {
+    pub static DEPOSITS: RefCell<DepositStorage> = RefCell::new(DepositStorage::default());
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize, Default)]
+pub struct DepositStorage {
+    pub deposits: Vec<Deposit>,
+    //one cursor per ledger and wallet, ICP included
+    pub icrc1_cursors: Vec<IndexCursor>,
+}
+
+//the index pages from the newest transaction backwards, so a sync cut by the page limit
+//resumes below the last page and only moves synced_to once it got down to the previous one
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct IndexCursor {
+    pub ledger: Principal,
+    pub wallet: String,
+    //every transaction up to this id is synced
+    pub synced_to: Option<u64>,
+    pub resume: Option<IndexResume>,
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct IndexResume {
+    //the newest transaction of the interrupted sync
+    pub newest: u64,
+    //the oldest transaction read so far, the next page starts below it
+    pub next_start: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct Deposit {
+    pub wallet: String,
+    pub ledger: Principal,
+    pub block_index: u64,
+    pub from: Address,
+    pub amount: Nat,
+    pub memo: Option<u64>,
+    pub icrc1_memo: Option<Vec<u8>>,
+    pub timestamp: u64,
+    pub label: Option<String>,
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct DepositPage {
+    pub deposits: Vec<Deposit>,
+    pub total: u64,
+}
+
+pub fn get_deposit_storage() -> DepositStorage {
+    DEPOSITS.with(|d| d.borrow().clone())
+}
+
+pub fn restore_deposit_storage(storage: DepositStorage) {
+    DEPOSITS.with(|d| d.replace(storage));
+}
+
+//newest deposits first
+pub fn get_deposit_page(wallet: Option<String>, offset: u64, limit: u64) -> DepositPage {
+    DEPOSITS.with(|d| {
+        let storage = d.borrow();
+        let mut deposits: Vec<Deposit> = storage.deposits.iter()
+            .filter(|dp| wallet.as_ref().map_or(true, |w| dp.wallet.eq(w)))
+            .cloned()
+            .collect();
+        deposits.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        let total = deposits.len() as u64;
+        let deposits = deposits.into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect();
+        DepositPage { deposits, total }
+    })
+}
+
+pub fn label_deposit(ledger: Principal, block_index: u64, label: Option<String>) -> Result<Deposit, String> {
+    DEPOSITS.with(|d| {
+        let mut storage = d.borrow_mut();
+        match storage.deposits.iter_mut().find(|dp| dp.ledger == ledger && dp.block_index == block_index) {
+            None => { Err("Deposit not found".to_string()) }
+            Some(deposit) => {
+                deposit.label = label;
+                Ok(deposit.clone())
+            }
+        }
+    })
+}
+
+//ICP deposits come from the ICP index, ICRC-1 deposits from the index canister of each ledger,
+//both page the history of every wallet account from its own cursor
+pub async fn sync_wallet_deposits() -> Result<u64, String> {
+    let state = get_current_state();
+    //wallets created before the uid validation may have no subaccount, they can not receive deposits
+    let wallets: Vec<(String, [u8; 32])> = state.wallets.iter()
+        .filter(|w| w.network == Network::IC)
+        .filter_map(|w| to_subaccount(&w.uid).map(|subaccount| (w.uid.clone(), subaccount)))
+        .collect();
+    let mut sources = vec![DepositSource::ICP { index: get_icp_index_id() }];
+    for icrc1 in state.icrc1_canisters {
+        if let Some(index) = icrc1.index {
+            sources.push(DepositSource::ICRC1 { ledger: icrc1.ledger, index });
+        }
+    }
+    //cursors are committed together with the deposits, a failed call leaves both untouched
+    let mut found = Vec::new();
+    let mut cursors = Vec::new();
+    for source in sources.iter() {
+        for (wallet, subaccount) in wallets.iter() {
+            let (deposits, cursor) = get_index_deposits(source, wallet.clone(), *subaccount).await?;
+            found.extend(deposits);
+            cursors.push(cursor);
+        }
+    }
+    let added = DEPOSITS.with(|d| {
+        let mut storage = d.borrow_mut();
+        for cursor in cursors {
+            storage.icrc1_cursors.retain(|c| c.ledger != cursor.ledger || c.wallet != cursor.wallet);
+            storage.icrc1_cursors.push(cursor);
+        }
+        let mut added = 0;
+        for deposit in found {
+            if !storage.deposits.iter().any(|dp| dp.ledger == deposit.ledger && dp.block_index == deposit.block_index) {
+                storage.deposits.push(deposit);
+                added += 1;
+            }
+        }
+        added
+    });
+    Ok(added)
+}
+
+enum DepositSource {
+    ICP { index: Principal },
+    ICRC1 { ledger: Principal, index: Principal },
+}
+
+impl DepositSource {
+    fn get_ledger(&self) -> Principal {
+        match self {
+            DepositSource::ICP { .. } => { MAINNET_LEDGER_CANISTER_ID }
+            DepositSource::ICRC1 { ledger, .. } => { *ledger }
+        }
+    }
+
+    //one page of the wallet history below start, newest first, paired with the deposit each transaction is
+    async fn get_page(&self, wallet: &String, subaccount: [u8; 32], start: Option<u64>) -> Result<Vec<(u64, Option<Deposit>)>, String> {
+        match self {
+            DepositSource::ICP { index } => {
+                let account = AccountIdentifier::new(&id(), &SubLegacy(subaccount));
+                let page = get_icp_index_account_transactions(*index, account, start, INDEX_PAGE).await?;
+                Ok(page.transactions.into_iter()
+                    .map(|t| (t.id, to_icp_deposit(t, wallet, &account)))
+                    .collect())
+            }
+            DepositSource::ICRC1 { ledger, index } => {
+                let account = Account { owner: id(), subaccount: Some(subaccount) };
+                let page = get_index_account_transactions(*index, account, start, INDEX_PAGE).await?;
+                Ok(page.transactions.into_iter()
+                    .map(|t| (nat_to_u64(&t.id), to_icrc1_deposit(t, *ledger, wallet, &account)))
+                    .collect())
+            }
+        }
+    }
+}
+
+async fn get_index_deposits(source: &DepositSource, wallet: String, subaccount: [u8; 32]) -> Result<(Vec<Deposit>, IndexCursor), String> {
+    let ledger = source.get_ledger();
+    let mut cursor = DEPOSITS.with(|d| d.borrow().icrc1_cursors.iter()
+        .find(|c| c.ledger == ledger && c.wallet == wallet)
+        .cloned())
+        .unwrap_or(IndexCursor { ledger, wallet: wallet.clone(), synced_to: None, resume: None });
+    let mut newest = cursor.resume.as_ref().map(|r| r.newest);
+    let mut start = cursor.resume.as_ref().map(|r| r.next_start);
+    let mut deposits = Vec::new();
+    let mut pages = 0;
+    let mut complete = false;
+    while !complete && pages < SYNC_MAX_PAGES {
+        //the index returns the transactions below start, newest first
+        let page = source.get_page(&wallet, subaccount, start).await?;
+        complete = (page.len() as u64) < INDEX_PAGE;
+        for (id, deposit) in page {
+            if cursor.synced_to.map_or(false, |synced_to| id <= synced_to) {
+                complete = true;
+                break;
+            }
+            newest = newest.or(Some(id));
+            start = Some(id);
+            if let Some(deposit) = deposit {
+                deposits.push(deposit);
+            }
+        }
+        pages += 1;
+    }
+    if complete {
+        cursor.synced_to = newest.or(cursor.synced_to);
+        cursor.resume = None;
+    } else if let (Some(newest), Some(next_start)) = (newest, start) {
+        cursor.resume = Some(IndexResume { newest, next_start });
+    }
+    Ok((deposits, cursor))
+}
+
+fn to_icp_deposit(t: IcpTransactionWithId, wallet: &String, account: &AccountIdentifier) -> Option<Deposit> {
+    let account = account.to_hex();
+    match t.transaction.operation {
+        IcpIndexOperation::Transfer { to, from, amount, .. } if to == account && from != account => {
+            Some(Deposit {
+                wallet: wallet.clone(),
+                ledger: MAINNET_LEDGER_CANISTER_ID,
+                block_index: t.id,
+                from: Address::AccountIdentifier(from),
+                amount: Nat::from(amount.e8s()),
+                memo: Some(t.transaction.memo),
+                icrc1_memo: t.transaction.icrc1_memo.map(|m| m.into_vec()),
+                timestamp: t.transaction.timestamp.map_or(0, |x| x.timestamp_nanos),
+                label: None,
+            })
+        }
+        _ => { None }
+    }
+}
+
+fn to_icrc1_deposit(t: TransactionWithId, ledger: Principal, wallet: &String, account: &Account) -> Option<Deposit> {
+    let timestamp = t.transaction.timestamp;
+    let block_index = nat_to_u64(&t.id);
+    t.transaction.transfer
+        .filter(|transfer| transfer.to == *account && transfer.from != *account)
+        .map(|transfer| Deposit {
+            wallet: wallet.clone(),
+            ledger,
+            block_index,
+            from: Address::ICRC1(MemberAccount {
+                owner: transfer.from.owner,
+                subaccount: transfer.from.subaccount,
+            }),
+            amount: transfer.amount,
+            memo: None,
+            icrc1_memo: transfer.memo.map(|m| m.0.into_vec()),
+            timestamp,
+            label: None,
+        })
+}
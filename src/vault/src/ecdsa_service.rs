@@ -0,0 +1,39 @@
+use ic_cdk::api::management_canister::ecdsa::{ecdsa_public_key, EcdsaCurve, EcdsaKeyId, EcdsaPublicKeyArgument, sign_with_ecdsa, SignWithEcdsaArgument};
+
+use crate::config::get_ecdsa_key_name;
+
+fn get_key_id() -> EcdsaKeyId {
+    EcdsaKeyId {
+        curve: EcdsaCurve::Secp256k1,
+        name: get_ecdsa_key_name(),
+    }
+}
+
+//every wallet has its own key, derived from the chain and the wallet uid
+pub fn get_derivation_path(chain: &str, wallet_uid: &String) -> Vec<Vec<u8>> {
+    vec![chain.as_bytes().to_vec(), wallet_uid.as_bytes().to_vec()]
+}
+
+//SEC1 compressed public key
+pub async fn get_public_key(derivation_path: Vec<Vec<u8>>) -> Result<Vec<u8>, String> {
+    let args = EcdsaPublicKeyArgument {
+        canister_id: None,
+        derivation_path,
+        key_id: get_key_id(),
+    };
+    ecdsa_public_key(args).await
+        .map(|(response, )| response.public_key)
+        .map_err(|e| format!("failed to get public key: {:?}", e))
+}
+
+//64 bytes r || s signature
+pub async fn sign(derivation_path: Vec<Vec<u8>>, message_hash: Vec<u8>) -> Result<Vec<u8>, String> {
+    let args = SignWithEcdsaArgument {
+        message_hash,
+        derivation_path,
+        key_id: get_key_id(),
+    };
+    sign_with_ecdsa(args).await
+        .map(|(response, )| response.signature)
+        .map_err(|e| format!("failed to sign: {:?}", e))
+}
@@ -1,4 +1,4 @@
-use candid::CandidType;
+use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, CandidType, Serialize, Deserialize, Hash, PartialEq, Eq)]
@@ -37,6 +37,10 @@ pub enum Network {
 #[derive(Clone, Debug, CandidType, Serialize, Deserialize, Hash, PartialEq, Eq)]
 pub enum Currency {
     ICP,
+    ICRC1(Principal),
+    BTC,
+    Cycles,
+    ETH,
 }
 
 #[derive(Clone, Debug, CandidType, Deserialize, Copy, Eq, PartialEq, Serialize)]
@@ -14,4 +14,20 @@ pub enum VaultError {
     QuorumNotReachable,
     ThresholdDefineError { message: String },
     ControllersUpdateError { message: String },
+    AddressNotExists,
+    AddressAlreadyExists,
+    InvalidAddress,
+    DestinationNotAllowed,
+    ICRC1CanisterNotExists,
+    TransactionNotExists,
+    TransactionNotRetryable,
+    RetryWindowExpired,
+    InvalidUID,
+    WalletArchived,
+    WalletNotEmpty,
+    InvalidNetwork,
+    CallTargetNotAllowed,
+    CanisterNotManaged,
+    CanisterAlreadyManaged,
+    IncompatibleSchemaVersion,
 }
\ No newline at end of file
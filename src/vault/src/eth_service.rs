@@ -0,0 +1,314 @@
+use candid::{CandidType, Deserialize, Nat, Reserved};
+use ic_cdk::api::call::call_with_payment128;
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use sha3::{Digest, Keccak256};
+
+use crate::config::{get_eth_rpc_url, get_evm_rpc_canister_id};
+use crate::ecdsa_service::{get_derivation_path, get_public_key, sign};
+
+const EIP1559_TX_TYPE: u8 = 0x02;
+//unused cycles are refunded by the EVM RPC canister
+const EVM_RPC_CYCLES: u128 = 2_000_000_000;
+const EVM_RPC_MAX_RESPONSE_BYTES: u64 = 1000;
+
+#[derive(CandidType)]
+struct HttpHeader {
+    name: String,
+    value: String,
+}
+
+#[derive(CandidType)]
+struct RpcApi {
+    url: String,
+    headers: Option<Vec<HttpHeader>>,
+}
+
+#[derive(CandidType)]
+enum RpcService {
+    Custom(RpcApi),
+}
+
+//the error details are not needed, only that the request failed
+#[derive(CandidType, Deserialize)]
+enum RequestResult {
+    Ok(String),
+    Err(Reserved),
+}
+
+pub struct Eip1559Transaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: Nat,
+    pub max_fee_per_gas: Nat,
+    pub gas_limit: u64,
+    pub to: String,
+    pub value: Nat,
+    pub data: Vec<u8>,
+}
+
+pub struct SignedTransaction {
+    pub raw: String,
+    pub hash: String,
+}
+
+fn wallet_path(wallet_uid: &String) -> Vec<Vec<u8>> {
+    get_derivation_path("eth", wallet_uid)
+}
+
+pub async fn get_eth_address(wallet_uid: &String) -> Result<String, String> {
+    let public_key = get_public_key(wallet_path(wallet_uid)).await?;
+    to_address(&public_key)
+}
+
+//latest balance in wei, read through the EVM RPC canister
+pub async fn get_eth_balance(wallet_uid: &String) -> Result<Nat, String> {
+    let url = get_eth_rpc_url().ok_or("No ETH RPC endpoint is configured".to_string())?;
+    let address = get_eth_address(wallet_uid).await?;
+    let json = format!(r#"{{"jsonrpc":"2.0","id":1,"method":"eth_getBalance","params":["{}","latest"]}}"#, address);
+    let service = RpcService::Custom(RpcApi { url, headers: None });
+    let (result, ): (RequestResult, ) = call_with_payment128(get_evm_rpc_canister_id(), "request",
+                                                             (service, json, EVM_RPC_MAX_RESPONSE_BYTES), EVM_RPC_CYCLES).await
+        .map_err(|e| format!("failed to call EVM RPC: {:?}", e))?;
+    let body = match result {
+        RequestResult::Ok(body) => { body }
+        RequestResult::Err(_) => { return Err("EVM RPC request failed".to_string()); }
+    };
+    let response: serde_json::Value = serde_json::from_str(&body)
+        .map_err(|e| format!("Invalid EVM RPC response: {:?}", e))?;
+    let balance = response["result"].as_str()
+        .ok_or(format!("No balance in EVM RPC response: {}", body))?;
+    let digits = balance.trim_start_matches("0x");
+    let padded = if digits.len() % 2 == 1 { format!("0{}", digits) } else { digits.to_string() };
+    let bytes = hex::decode(padded)
+        .map_err(|e| format!("Invalid balance {}: {:?}", balance, e))?;
+    Ok(bytes.iter().fold(Nat::from(0u64), |n, b| n * Nat::from(256u64) + Nat::from(*b as u64)))
+}
+
+//the address is the last 20 bytes of the keccak hash of the uncompressed key, in the EIP-55 checksum case
+fn to_address(public_key: &[u8]) -> Result<String, String> {
+    let key = VerifyingKey::from_sec1_bytes(public_key)
+        .map_err(|e| format!("Invalid public key: {:?}", e))?;
+    let uncompressed = key.to_encoded_point(false);
+    let hash = Keccak256::digest(&uncompressed.as_bytes()[1..]);
+    Ok(to_checksum_address(&hash[12..]))
+}
+
+fn to_checksum_address(address: &[u8]) -> String {
+    let address = hex::encode(address);
+    let checksum = hex::encode(Keccak256::digest(address.as_bytes()));
+    let checksummed: String = address.chars().zip(checksum.chars())
+        .map(|(c, h)| if h.to_digit(16).unwrap_or(0) >= 8 { c.to_ascii_uppercase() } else { c })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+pub fn decode_address(address: &String) -> Result<Vec<u8>, String> {
+    let bytes = hex::decode(address.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid address: {:?}", e))?;
+    if bytes.len() != 20 {
+        return Err("Invalid address length".to_string());
+    }
+    Ok(bytes)
+}
+
+//signs the typed transaction with the wallet key, the raw transaction is broadcast by the client
+pub async fn sign_eip1559(wallet_uid: &String, tx: &Eip1559Transaction) -> Result<SignedTransaction, String> {
+    let fields = get_fields(tx)?;
+    let hash = Keccak256::digest(&to_typed(&fields)).to_vec();
+
+    let public_key = get_public_key(wallet_path(wallet_uid)).await?;
+    let signature = sign(wallet_path(wallet_uid), hash.clone()).await?;
+    let y_parity = get_y_parity(&hash, &signature, &public_key)?;
+
+    let signed = to_signed(fields, y_parity, &signature);
+    Ok(SignedTransaction {
+        hash: format!("0x{}", hex::encode(Keccak256::digest(&signed))),
+        raw: format!("0x{}", hex::encode(signed)),
+    })
+}
+
+fn get_fields(tx: &Eip1559Transaction) -> Result<Vec<Vec<u8>>, String> {
+    let to = decode_address(&tx.to)?;
+    Ok(vec![
+        rlp_bytes(&u64_to_be(tx.chain_id)),
+        rlp_bytes(&u64_to_be(tx.nonce)),
+        rlp_bytes(&nat_to_be(&tx.max_priority_fee_per_gas)),
+        rlp_bytes(&nat_to_be(&tx.max_fee_per_gas)),
+        rlp_bytes(&u64_to_be(tx.gas_limit)),
+        rlp_bytes(&to),
+        rlp_bytes(&nat_to_be(&tx.value)),
+        rlp_bytes(&tx.data),
+        rlp_list(&vec![]),
+    ])
+}
+
+fn to_typed(fields: &Vec<Vec<u8>>) -> Vec<u8> {
+    [vec![EIP1559_TX_TYPE], rlp_list(fields)].concat()
+}
+
+//the signature is r||s, as returned by the management canister
+fn to_signed(mut fields: Vec<Vec<u8>>, y_parity: u8, signature: &[u8]) -> Vec<u8> {
+    fields.push(rlp_bytes(&u64_to_be(y_parity as u64)));
+    fields.push(rlp_bytes(&strip_zeros(&signature[..32])));
+    fields.push(rlp_bytes(&strip_zeros(&signature[32..])));
+    to_typed(&fields)
+}
+
+//the management canister does not return the recovery id, so it is found by recovering the key
+fn get_y_parity(hash: &[u8], signature: &[u8], public_key: &[u8]) -> Result<u8, String> {
+    let signature = Signature::from_slice(signature)
+        .map_err(|e| format!("Invalid signature: {:?}", e))?;
+    let expected = VerifyingKey::from_sec1_bytes(public_key)
+        .map_err(|e| format!("Invalid public key: {:?}", e))?;
+    for parity in 0..2u8 {
+        let recovery_id = RecoveryId::from_byte(parity)
+            .ok_or("Invalid recovery id".to_string())?;
+        if let Ok(recovered) = VerifyingKey::recover_from_prehash(hash, &signature, recovery_id) {
+            if recovered == expected {
+                return Ok(parity);
+            }
+        }
+    }
+    Err("Could not recover the signing key".to_string())
+}
+
+fn u64_to_be(n: u64) -> Vec<u8> {
+    strip_zeros(&n.to_be_bytes())
+}
+
+fn nat_to_be(n: &Nat) -> Vec<u8> {
+    strip_zeros(&n.0.to_bytes_be())
+}
+
+fn strip_zeros(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().cloned().skip_while(|b| *b == 0).collect()
+}
+
+fn rlp_length(offset: u8, length: usize) -> Vec<u8> {
+    if length < 56 {
+        vec![offset + length as u8]
+    } else {
+        let length_bytes = u64_to_be(length as u64);
+        [vec![offset + 55 + length_bytes.len() as u8], length_bytes].concat()
+    }
+}
+
+fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+    [rlp_length(0x80, bytes.len()), bytes.to_vec()].concat()
+}
+
+fn rlp_list(items: &Vec<Vec<u8>>) -> Vec<u8> {
+    let payload = items.concat();
+    [rlp_length(0xc0, payload.len()), payload].concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use k256::ecdsa::SigningKey;
+
+    use super::*;
+
+    fn from_hex(s: &str) -> Vec<u8> {
+        hex::decode(s.trim_start_matches("0x")).unwrap()
+    }
+
+    #[test]
+    fn rlp_encodes_the_spec_examples() {
+        assert_eq!(rlp_bytes(b"dog"), from_hex("83646f67"));
+        assert_eq!(rlp_list(&vec![rlp_bytes(b"cat"), rlp_bytes(b"dog")]), from_hex("c88363617483646f67"));
+        assert_eq!(rlp_bytes(b""), from_hex("80"));
+        assert_eq!(rlp_list(&vec![]), from_hex("c0"));
+        assert_eq!(rlp_bytes(&u64_to_be(0)), from_hex("80"));
+        assert_eq!(rlp_bytes(&u64_to_be(15)), from_hex("0f"));
+        assert_eq!(rlp_bytes(&u64_to_be(1024)), from_hex("820400"));
+        let lorem = b"Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+        assert_eq!(rlp_bytes(lorem), [from_hex("b838"), lorem.to_vec()].concat());
+    }
+
+    #[test]
+    fn addresses_are_eip55_checksummed() {
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            assert_eq!(to_checksum_address(&decode_address(&address.to_string()).unwrap()), address);
+        }
+    }
+
+    #[test]
+    fn address_is_derived_from_the_public_key() {
+        let key = SigningKey::from_slice(&from_hex("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318")).unwrap();
+        let compressed = key.verifying_key().to_encoded_point(true);
+        assert_eq!(to_address(compressed.as_bytes()).unwrap(), "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23");
+    }
+
+    //the signing data and signature of the EIP-155 example transaction
+    #[test]
+    fn y_parity_is_recovered_for_the_eip155_example() {
+        let signing_data = rlp_list(&vec![
+            rlp_bytes(&u64_to_be(9)),
+            rlp_bytes(&u64_to_be(20_000_000_000)),
+            rlp_bytes(&u64_to_be(21000)),
+            rlp_bytes(&from_hex("3535353535353535353535353535353535353535")),
+            rlp_bytes(&u64_to_be(1_000_000_000_000_000_000)),
+            rlp_bytes(&[]),
+            rlp_bytes(&u64_to_be(1)),
+            rlp_bytes(&u64_to_be(0)),
+            rlp_bytes(&u64_to_be(0)),
+        ]);
+        assert_eq!(signing_data, from_hex("ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080"));
+        let hash = Keccak256::digest(&signing_data).to_vec();
+        assert_eq!(hash, from_hex("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"));
+
+        let key = SigningKey::from_slice(&[0x46; 32]).unwrap();
+        let (signature, recovery_id) = key.sign_prehash_recoverable(&hash).unwrap();
+        assert_eq!(signature.to_bytes().to_vec(), from_hex("28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa63627667cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"));
+        let public_key = key.verifying_key().to_encoded_point(true);
+        //v = 37 = chain_id * 2 + 35 + y_parity
+        assert_eq!(get_y_parity(&hash, &signature.to_bytes(), public_key.as_bytes()).unwrap(), 0);
+        assert_eq!(recovery_id.to_byte(), 0);
+    }
+
+    #[test]
+    fn eip1559_transaction_is_typed_and_signed() {
+        let tx = Eip1559Transaction {
+            chain_id: 1,
+            nonce: 0,
+            max_priority_fee_per_gas: Nat::from(1u64),
+            max_fee_per_gas: Nat::from(2u64),
+            gas_limit: 21000,
+            to: "0x3535353535353535353535353535353535353535".to_string(),
+            value: Nat::from(1u64),
+            data: vec![],
+        };
+        let fields = get_fields(&tx).unwrap();
+        //0x02 || [chain_id, nonce, priority fee, max fee, gas, to, value, data, access list]
+        assert_eq!(to_typed(&fields), from_hex("02df018001028252089435353535353535353535353535353535353535350180c0"));
+
+        let key = SigningKey::from_slice(&[0x46; 32]).unwrap();
+        let hash = Keccak256::digest(&to_typed(&fields)).to_vec();
+        let (signature, recovery_id) = key.sign_prehash_recoverable(&hash).unwrap();
+        let public_key = key.verifying_key().to_encoded_point(true);
+        let y_parity = get_y_parity(&hash, &signature.to_bytes(), public_key.as_bytes()).unwrap();
+        assert_eq!(y_parity, recovery_id.to_byte());
+
+        let signed = to_signed(fields.clone(), y_parity, &signature.to_bytes());
+        let r = strip_zeros(&signature.to_bytes()[..32]);
+        let s = strip_zeros(&signature.to_bytes()[32..]);
+        let mut signed_fields = fields;
+        signed_fields.extend([rlp_bytes(&u64_to_be(y_parity as u64)), rlp_bytes(&r), rlp_bytes(&s)]);
+        assert_eq!(signed, [vec![0x02], rlp_list(&signed_fields)].concat());
+        assert_eq!(signed[1], 0xf8);
+    }
+
+    #[test]
+    fn invalid_addresses_are_rejected() {
+        assert!(decode_address(&"0x1234".to_string()).is_err());
+        assert!(decode_address(&"0xzz35353535353535353535353535353535353535".to_string()).is_err());
+    }
+}
@@ -0,0 +1,348 @@
+use std::cell::RefCell;
+
+use candid::{CandidType, Deserialize};
+use hmac::{Hmac, Mac};
+use ic_cdk::api::management_canister::main::raw_rand;
+use ic_cdk::api::time;
+use icrc_ledger_types::icrc1::account::Account as ICRC1Account;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Sha256;
+
+use crate::enums::Currency;
+use crate::report_service::get_outgoing;
+use crate::state::get_current_state;
+use crate::transaction::address_book::address_book::Address;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_service::get_all_transactions;
+use crate::transaction::transfer::transfer_common::TransferCommon;
+use crate::transaction::transfer::transfer_executor_common::TransferExecutor;
+use crate::transaction::transfer::transfer_icrc1_executor_common::TransferICRC1Executor;
+use crate::util::caller_to_address;
+
+const TOKEN_TTL_NANOS: u64 = 15 * 60 * 1_000_000_000;
+const DEFAULT_PAGE_SIZE: usize = 100;
+const MAX_PAGE_SIZE: usize = 1000;
+//the export is not certified, the gateway is told to skip the verification of these responses
+const SKIP_CERTIFICATION: &str = "default_certification(ValidationArgs{no_certification:Empty{}})";
+
+//the signing key lives on the heap, an upgrade invalidates the tokens issued before it
+thread_local! {
+    static EXPORT_KEY: RefCell<Option<Vec<u8>>> = RefCell::new(None);
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    #[serde(with = "serde_bytes")]
+    pub body: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct HttpResponse {
+    pub status_code: u16,
+    pub headers: Vec<(String, String)>,
+    #[serde(with = "serde_bytes")]
+    pub body: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize, Clone, Debug)]
+pub struct ExportToken {
+    //sent as "Authorization: Bearer <token>" or as the token query parameter,
+    //e.g. https://<vault id>.raw.icp0.io/transactions.csv?token=...
+    //the raw domain is used since the responses are not certified
+    pub token: String,
+    pub expires_at: u64,
+}
+
+#[derive(Serialize)]
+struct ExportApproval {
+    signer: String,
+    status: String,
+    reason: Option<String>,
+    created_date: u64,
+}
+
+#[derive(Serialize)]
+struct ExportRow {
+    id: u64,
+    #[serde(rename = "type")]
+    transaction_type: String,
+    state: String,
+    currency: Option<String>,
+    amount: Option<String>,
+    wallet: Option<String>,
+    destination: Option<String>,
+    block_index: Option<String>,
+    approvals: Vec<ExportApproval>,
+    memo: Option<String>,
+    created_date: u64,
+}
+
+#[derive(Serialize)]
+struct ExportPage {
+    total: usize,
+    offset: usize,
+    limit: usize,
+    transactions: Vec<ExportRow>,
+}
+
+//tokens are bound to the member, so a removed member loses access to the tokens issued earlier
+pub async fn issue_export_token() -> Result<ExportToken, String> {
+    let key = match EXPORT_KEY.with(|k| k.borrow().clone()) {
+        Some(key) => { key }
+        None => {
+            let (random, ): (Vec<u8>, ) = raw_rand().await
+                .map_err(|(code, msg)| format!("{:?}: {}", code, msg))?;
+            //another call could have set the key while this one was waiting for the randomness
+            EXPORT_KEY.with(|k| k.borrow_mut().get_or_insert(random).clone())
+        }
+    };
+    let expires_at = time() + TOKEN_TTL_NANOS;
+    let payload = format!("{}.{}", caller_to_address(), expires_at);
+    let signature = hex::encode(sign(&key, payload.as_bytes()).finalize().into_bytes());
+    Ok(ExportToken {
+        token: format!("{}.{}", payload, signature),
+        expires_at,
+    })
+}
+
+pub fn http_request(request: HttpRequest) -> HttpResponse {
+    if !request.method.eq_ignore_ascii_case("GET") {
+        return error_response(405, "Method not allowed");
+    }
+    let (path, query) = match request.url.split_once('?') {
+        None => { (request.url.as_str(), "") }
+        Some(x) => { x }
+    };
+    let param = |name: &str| query.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| key.eq(&name))
+        .map(|(_, value)| value.to_string());
+    //the header keeps the token out of the logged urls, the query parameter is there for plain links
+    let bearer = request.headers.iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("Authorization"))
+        .and_then(|(_, value)| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string());
+    if let Err(message) = verify_export_token(bearer.or(param("token"))) {
+        return error_response(403, &message);
+    }
+    let offset = param("offset").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let limit = param("limit").and_then(|v| v.parse().ok()).unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+    match path {
+        "/transactions.csv" => {
+            let page = get_page(offset, limit);
+            response(to_csv(&page.transactions).into_bytes(), "text/csv", page.total)
+        }
+        "/transactions.json" => {
+            let page = get_page(offset, limit);
+            let total = page.total;
+            match serde_json::to_vec(&page) {
+                Ok(body) => { response(body, "application/json", total) }
+                Err(e) => { error_response(500, &e.to_string()) }
+            }
+        }
+        "/state.json" => {
+            match serde_json::to_vec(&get_current_state()) {
+                Ok(body) => { response(body, "application/json", 1) }
+                Err(e) => { error_response(500, &e.to_string()) }
+            }
+        }
+        _ => { error_response(404, "Not found") }
+    }
+}
+
+fn verify_export_token(token: Option<String>) -> Result<(), String> {
+    let token = token.ok_or("Export token is required".to_string())?;
+    let (payload, signature) = token.rsplit_once('.')
+        .ok_or("Malformed export token".to_string())?;
+    let (member, expires_at) = payload.split_once('.')
+        .ok_or("Malformed export token".to_string())?;
+    let key = EXPORT_KEY.with(|k| k.borrow().clone())
+        .ok_or("Export token is not valid".to_string())?;
+    let signature = hex::decode(signature)
+        .map_err(|_| "Malformed export token".to_string())?;
+    sign(&key, payload.as_bytes()).verify_slice(&signature)
+        .map_err(|_| "Export token is not valid".to_string())?;
+    let expires_at: u64 = expires_at.parse()
+        .map_err(|_| "Malformed export token".to_string())?;
+    if expires_at < time() {
+        return Err("Export token is expired".to_string());
+    }
+    if !get_current_state().members.iter().any(|m| m.member_id.eq_ignore_ascii_case(member)) {
+        return Err("Not registered".to_string());
+    }
+    Ok(())
+}
+
+fn get_page(offset: usize, limit: usize) -> ExportPage {
+    let mut transactions = get_all_transactions();
+    transactions.sort_by_key(|tr| tr.get_id());
+    let total = transactions.len();
+    let rows = transactions.into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|tr| to_row(tr))
+        .collect();
+    ExportPage {
+        total,
+        offset,
+        limit,
+        transactions: rows,
+    }
+}
+
+fn to_row(tr: Box<dyn ITransaction>) -> ExportRow {
+    let candid = tr.to_candid();
+    let common = tr.get_common_ref();
+    //the variant name gives the type
+    let variant = match serde_json::to_value(&candid) {
+        Ok(Value::Object(o)) => { o.keys().next().cloned().unwrap_or_default() }
+        _ => { String::new() }
+    };
+    let block_index = get_block_index(&candid);
+    let outgoing = get_outgoing(candid.clone());
+    let mut approvals: Vec<ExportApproval> = common.approves.iter()
+        .map(|a| ExportApproval {
+            signer: a.signer.clone(),
+            status: format!("{:?}", a.status),
+            reason: a.reason.clone(),
+            created_date: a.created_date,
+        })
+        .collect();
+    approvals.sort_by_key(|a| a.created_date);
+    ExportRow {
+        id: common.id,
+        transaction_type: variant.trim_end_matches('V').to_string(),
+        state: format!("{:?}", common.state),
+        currency: outgoing.as_ref().map(|(_, currency, _)| currency_label(currency)),
+        amount: outgoing.as_ref().map(|(_, _, amount)| amount.0.to_string()),
+        wallet: outgoing.as_ref().map(|(wallet, _, _)| wallet.clone()).filter(|w| !w.is_empty()),
+        destination: get_destination(&candid),
+        block_index,
+        approvals,
+        memo: common.memo.clone(),
+        created_date: common.created_date,
+    }
+}
+
+//ledger block index, or the chain reference of the transactions settled outside of the IC ledgers
+fn get_block_index(candid: &TransactionCandid) -> Option<String> {
+    match candid {
+        TransactionCandid::TransferTransactionV(t) => { t.get_block_index().map(|b| b.to_string()) }
+        TransactionCandid::TransferQuorumTransactionV(t) => { t.get_block_index().map(|b| b.to_string()) }
+        TransactionCandid::TransferICRC1TransactionV(t) => { t.get_block_index().map(|b| b.0.to_string()) }
+        TransactionCandid::TransferICRC1QuorumTransactionV(t) => { t.get_block_index().map(|b| b.0.to_string()) }
+        TransactionCandid::TopUpTransactionV(t) => { t.block_index.map(|b| b.to_string()) }
+        TransactionCandid::TopUpQuorumTransactionV(t) => { t.block_index.map(|b| b.to_string()) }
+        TransactionCandid::AutoTopUpTransactionV(t) => { t.block_index.map(|b| b.to_string()) }
+        TransactionCandid::ICRC2ApproveTransactionV(t) => { t.block_index.as_ref().map(|b| b.0.to_string()) }
+        TransactionCandid::ICRC2RevokeTransactionV(t) => { t.block_index.as_ref().map(|b| b.0.to_string()) }
+        TransactionCandid::TransferBTCTransactionV(t) => { t.txid.clone() }
+        TransactionCandid::EthTransferTransactionV(t) => { t.transaction_hash.clone() }
+        TransactionCandid::WithdrawalTransactionV(t) => { t.withdrawal_id.map(|id| id.to_string()) }
+        _ => { None }
+    }
+}
+
+fn get_destination(candid: &TransactionCandid) -> Option<String> {
+    let address = match candid {
+        TransactionCandid::TransferTransactionV(t) => { t.get_destination() }
+        TransactionCandid::TransferQuorumTransactionV(t) => { t.get_destination() }
+        TransactionCandid::TransferICRC1TransactionV(t) => { t.get_destination() }
+        TransactionCandid::TransferICRC1QuorumTransactionV(t) => { t.get_destination() }
+        TransactionCandid::TransferBTCTransactionV(t) => { t.get_destination() }
+        TransactionCandid::TopUpTransactionV(t) => { t.get_destination() }
+        TransactionCandid::TopUpQuorumTransactionV(t) => { t.get_destination() }
+        TransactionCandid::DepositCyclesTransactionV(t) => { t.get_destination() }
+        TransactionCandid::EthTransferTransactionV(t) => { return Some(t.to.clone()); }
+        TransactionCandid::WithdrawalTransactionV(t) => { return Some(t.address.clone()); }
+        _ => { None }
+    };
+    address.map(|a| match a {
+        Address::AccountIdentifier(hex) => { hex }
+        Address::ICRC1(account) => { ICRC1Account { owner: account.owner, subaccount: account.subaccount }.to_string() }
+        Address::BTC(address) => { address }
+        Address::ETH(address) => { address }
+    })
+}
+
+fn currency_label(currency: &Currency) -> String {
+    match currency {
+        Currency::ICRC1(ledger) => { format!("ICRC1:{}", ledger) }
+        _ => { format!("{:?}", currency) }
+    }
+}
+
+fn to_csv(rows: &Vec<ExportRow>) -> String {
+    let mut csv = String::from("id,type,state,currency,amount,wallet,destination,block_index,approvals,memo,created_date\n");
+    for row in rows {
+        let approvals = row.approvals.iter()
+            .map(|a| format!("{}:{}", a.signer, a.status))
+            .collect::<Vec<String>>()
+            .join(";");
+        let fields = [
+            row.id.to_string(),
+            row.transaction_type.clone(),
+            row.state.clone(),
+            row.currency.clone().unwrap_or_default(),
+            row.amount.clone().unwrap_or_default(),
+            row.wallet.clone().unwrap_or_default(),
+            row.destination.clone().unwrap_or_default(),
+            row.block_index.clone().unwrap_or_default(),
+            approvals,
+            row.memo.clone().unwrap_or_default(),
+            row.created_date.to_string(),
+        ];
+        csv.push_str(&fields.iter().map(|f| csv_field(f)).collect::<Vec<String>>().join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+//spreadsheets run cells starting with a formula sign, member provided text is prefixed to stay plain text
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn response(body: Vec<u8>, content_type: &str, total: usize) -> HttpResponse {
+    HttpResponse {
+        status_code: 200,
+        headers: vec![
+            ("Content-Type".to_string(), content_type.to_string()),
+            ("X-Total-Count".to_string(), total.to_string()),
+            ("Cache-Control".to_string(), "no-store".to_string()),
+            ("IC-CertificateExpression".to_string(), SKIP_CERTIFICATION.to_string()),
+        ],
+        body,
+    }
+}
+
+fn error_response(status_code: u16, message: &str) -> HttpResponse {
+    HttpResponse {
+        status_code,
+        headers: vec![
+            ("Content-Type".to_string(), "text/plain".to_string()),
+            ("IC-CertificateExpression".to_string(), SKIP_CERTIFICATION.to_string()),
+        ],
+        body: message.as_bytes().to_vec(),
+    }
+}
+
+fn sign(key: &[u8], message: &[u8]) -> Hmac<Sha256> {
+    //any key length is accepted for hmac
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+    mac.update(message);
+    mac
+}
@@ -0,0 +1,116 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use candid::CandidType;
+use ic_cdk::api::time;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::TransactionState::{Approved, Blocked, Pending};
+use crate::errors::VaultError;
+use crate::state::get_current_state;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_service::{get_all_transactions, get_unfinished_transactions};
+use crate::util::caller_to_address;
+
+thread_local! {
+    //member address to the date of the last acknowledgement
+    static ACKNOWLEDGEMENTS: RefCell<HashMap<String, u64>> = RefCell::new(HashMap::new());
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct Acknowledgement {
+    pub member: String,
+    pub date: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct PendingAction {
+    pub transaction: TransactionCandid,
+    pub missing_approvals: u8,
+    //unfinished transactions which have to finish first
+    pub blocked_by: Vec<u64>,
+    //the error the transaction will fail with once unblocked, missing_approvals is 0 then
+    pub threshold_error: Option<VaultError>,
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct PendingActions {
+    pub actions: Vec<PendingAction>,
+    //transactions created or changed since the last acknowledgement
+    pub unseen_activity: u64,
+    pub last_acknowledged: Option<u64>,
+}
+
+pub fn get_my_pending_actions() -> PendingActions {
+    let caller = caller_to_address();
+    let role = get_current_state().members.into_iter()
+        .find(|m| m.member_id.eq_ignore_ascii_case(&caller))
+        .map(|m| m.role);
+    let unfinished = get_unfinished_transactions();
+    let actions = match role {
+        None => { vec![] }
+        Some(role) => {
+            unfinished.iter()
+                .filter(|tr| [Blocked, Pending].contains(tr.get_state()))
+                .filter(|tr| tr.get_accepted_roles().contains(&role))
+                .filter(|tr| !tr.get_common_ref().approves.iter().any(|a| a.signer.eq_ignore_ascii_case(&caller)))
+                .map(|tr| to_pending_action(tr, &unfinished))
+                .collect()
+        }
+    };
+    let last_acknowledged = ACKNOWLEDGEMENTS.with(|a| a.borrow().get(&caller).cloned());
+    let since = last_acknowledged.unwrap_or(0);
+    let unseen_activity = get_all_transactions().iter()
+        .filter(|tr| tr.get_common_ref().modified_date > since)
+        .count() as u64;
+    PendingActions {
+        actions,
+        unseen_activity,
+        last_acknowledged,
+    }
+}
+
+pub fn acknowledge_activity() -> u64 {
+    let now = time();
+    ACKNOWLEDGEMENTS.with(|a| a.borrow_mut().insert(caller_to_address(), now));
+    now
+}
+
+pub fn get_acknowledgements() -> Vec<Acknowledgement> {
+    ACKNOWLEDGEMENTS.with(|a| a.borrow().iter()
+        .map(|(member, date)| Acknowledgement { member: member.clone(), date: date.clone() })
+        .collect())
+}
+
+pub fn restore_acknowledgements(acknowledgements: Vec<Acknowledgement>) {
+    ACKNOWLEDGEMENTS.with(|a| a.replace(acknowledgements.into_iter()
+        .map(|ack| (ack.member, ack.date))
+        .collect()));
+}
+
+fn to_pending_action(tr: &Box<dyn ITransaction>, unfinished: &Vec<Box<dyn ITransaction>>) -> PendingAction {
+    //blocked transactions get their threshold only once they are unblocked
+    let mut probe = tr.clone();
+    let (threshold, threshold_error) = match tr.get_threshold() {
+        Some(t) => { (t, None) }
+        None => {
+            match probe.define_threshold() {
+                Ok(t) => { (t, None) }
+                Err(e) => { (0, Some(e)) }
+            }
+        }
+    };
+    let approved = tr.get_common_ref().approves.iter()
+        .filter(|a| a.status == Approved)
+        .count() as u8;
+    let blocked_by = unfinished.iter()
+        .filter(|other| probe.get_block_predicate(other))
+        .map(|other| other.get_id())
+        .collect();
+    PendingAction {
+        transaction: tr.to_candid(),
+        missing_approvals: threshold.saturating_sub(approved),
+        blocked_by,
+        threshold_error,
+    }
+}
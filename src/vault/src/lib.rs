@@ -6,6 +6,7 @@ use std::string::ToString;
 use candid::{CandidType, export_service, Principal};
 use ic_cdk::{call, id, trap};
 use ic_cdk::api::call::CallResult;
+use ic_cdk::api::management_canister::bitcoin::Utxo;
 use ic_cdk::api::management_canister::main::CanisterStatusResponse;
 use ic_cdk::api::time;
 use ic_cdk_macros::*;
@@ -15,34 +16,63 @@ use sha2::digest::generic_array::functional::FunctionalSequence;
 use nfid_certified::{CertifiedResponse, get_trusted_origins_cert, update_trusted_origins};
 
 use crate::config::{Conf, CONF};
+use crate::canister_management_service::{CanisterSnapshot, UploadedWasm};
+use crate::canister_status_service::VaultCanisterStatus;
+use crate::balance_service::{get_cached_balances, refresh_balances, WalletBalance};
+use crate::deposit_service::{Deposit, DepositPage, get_deposit_page, label_deposit, sync_wallet_deposits};
+use crate::inbox_service::PendingActions;
+use crate::export_service::{ExportToken, HttpRequest, HttpResponse};
 use crate::enums::{TransactionState, VaultRole};
+use crate::reconciliation_service::{get_discrepancies, reconcile_transfers, TransferDiscrepancy};
 use crate::state::{get_vault_state, VaultState};
+use crate::transaction::allowance::allowance::{get_wallet_allowances, WalletAllowance};
 use crate::transaction::basic_transaction::BasicTransaction;
 use crate::transaction::member::member_create_transaction_v2::MemberCreateTransactionV2;
 use crate::transaction::member::members::Account;
 use crate::transaction::transaction::{Candid, TransactionCandid};
 use crate::transaction::transaction_approve_handler::{Approve, handle_approve, TransactionApproveRequest};
+use crate::transaction::transaction_comment_handler::{handle_comment, TransactionCommentRequest};
+use crate::transaction::transaction_metadata_handler::{handle_metadata_update, TransactionMetadataRequest};
+use crate::report_service::{get_outgoing_report, OutgoingReportEntry, OutgoingReportRequest};
 use crate::transaction::transaction_request_handler::{handle_transaction_request, TransactionRequest};
 use crate::transaction::transaction_service::{execute_approved_transactions, get_all_transactions, stable_restore, stable_save, store_transaction};
 use crate::util::{to_address, to_array};
 use crate::version_const::VERSION;
-use crate::security_service::is_caller_registered;
+use crate::schema_const::SCHEMA_VERSION;
+use crate::security_service::{is_caller_registered, verify_caller};
 
 mod util;
 mod enums;
 mod security_service;
 mod transfer_service;
+mod reconciliation_service;
+mod balance_service;
+mod deposit_service;
+mod ecdsa_service;
+mod bitcoin_service;
+mod eth_service;
+mod minter_service;
+mod canister_management_service;
+mod auto_top_up_service;
+mod canister_status_service;
+mod upgrade_proposal_service;
+mod timer_service;
+mod inbox_service;
+mod report_service;
+mod export_service;
 mod config;
 mod transaction;
 mod state;
 mod errors;
 mod version_const;
+mod schema_const;
 
 
 #[init]
 async fn init(initiator: Principal, conf: Conf) {
     update_trusted_origins(conf.origins.clone());
     CONF.with(|c| c.replace(conf));
+    timer_service::schedule_next_tick();
     let member_id = to_address(initiator);
     let account = Account {
         owner: initiator,
@@ -55,6 +85,7 @@ async fn init(initiator: Principal, conf: Conf) {
         signer: member_id,
         created_date: time(),
         status: TransactionState::Approved,
+        reason: None,
     });
     store_transaction(mc.clone_self());
     execute_approved_transactions().await
@@ -65,6 +96,11 @@ async fn get_version() -> String {
     VERSION.to_string()
 }
 
+#[query]
+async fn get_schema_version() -> u32 {
+    SCHEMA_VERSION
+}
+
 #[update(guard = "is_caller_registered")]
 async fn request_transaction(transaction_request: Vec<TransactionRequest>) -> Vec<TransactionCandid> {
     let mut trs: Vec<TransactionCandid> = Default::default();
@@ -109,12 +145,167 @@ async fn get_state(tr_id: Option<u64>) -> VaultState {
 async fn approve(request: Vec<TransactionApproveRequest>) -> Vec<TransactionCandid> {
     let mut approved_trs = Vec::default();
     for approve in request {
-        let trs = handle_approve(approve.transaction_id, approve.state);
+        let trs = handle_approve(approve.transaction_id, approve.state, approve.reason);
         approved_trs.push(trs);
     }
     approved_trs
 }
 
+#[update(guard = "is_caller_registered")]
+async fn comment(request: TransactionCommentRequest) -> TransactionCandid {
+    handle_comment(request.transaction_id, request.text)
+}
+
+#[update(guard = "is_caller_registered")]
+async fn update_metadata(request: TransactionMetadataRequest) -> TransactionCandid {
+    handle_metadata_update(request.transaction_id, request.metadata)
+}
+
+#[update(guard = "is_caller_registered")]
+async fn issue_export_token() -> ExportToken {
+    export_service::issue_export_token().await
+        .unwrap_or_else(|message| trap(&message))
+}
+
+//members authenticate with an export token, see issue_export_token
+#[query]
+fn http_request(request: HttpRequest) -> HttpResponse {
+    export_service::http_request(request)
+}
+
+#[query(guard = "is_caller_registered")]
+async fn get_outgoing_amounts(request: OutgoingReportRequest) -> Vec<OutgoingReportEntry> {
+    get_outgoing_report(request)
+}
+
+#[update(guard = "is_caller_registered")]
+async fn get_allowances(wallet: String) -> Vec<WalletAllowance> {
+    get_wallet_allowances(wallet).await
+}
+
+#[update(guard = "is_caller_registered")]
+async fn get_balances(wallet_uids: Vec<String>) -> Vec<WalletBalance> {
+    refresh_balances(wallet_uids).await
+}
+
+#[query(guard = "is_caller_registered")]
+async fn get_balances_cached(wallet_uids: Vec<String>) -> Vec<WalletBalance> {
+    get_cached_balances(wallet_uids)
+}
+
+#[update(guard = "is_caller_registered")]
+async fn sync_deposits() -> u64 {
+    match sync_wallet_deposits().await {
+        Ok(added) => { added }
+        Err(message) => { trap(&message) }
+    }
+}
+
+#[query(guard = "is_caller_registered")]
+async fn get_deposits(wallet: Option<String>, offset: u64, limit: u64) -> DepositPage {
+    get_deposit_page(wallet, offset, limit)
+}
+
+#[query(guard = "is_caller_registered")]
+async fn get_my_pending_actions() -> PendingActions {
+    inbox_service::get_my_pending_actions()
+}
+
+#[update(guard = "is_caller_registered")]
+async fn acknowledge_activity() -> u64 {
+    inbox_service::acknowledge_activity()
+}
+
+#[update(guard = "is_caller_registered")]
+async fn tag_deposit(ledger: Principal, block_index: u64, label: Option<String>) -> Deposit {
+    match label_deposit(ledger, block_index, label) {
+        Ok(deposit) => { deposit }
+        Err(message) => { trap(&message) }
+    }
+}
+
+#[update(guard = "is_caller_registered")]
+async fn get_btc_address(wallet: String) -> String {
+    bitcoin_service::get_btc_address(&wallet).await
+        .unwrap_or_else(|message| trap(&message))
+}
+
+#[update(guard = "is_caller_registered")]
+async fn get_btc_balance(wallet: String) -> u64 {
+    bitcoin_service::get_btc_balance(&wallet).await
+        .unwrap_or_else(|message| trap(&message))
+}
+
+#[update(guard = "is_caller_registered")]
+async fn get_btc_utxos(wallet: String) -> Vec<Utxo> {
+    bitcoin_service::get_btc_utxos(&wallet).await
+        .unwrap_or_else(|message| trap(&message))
+}
+
+#[update(guard = "is_caller_registered")]
+async fn get_eth_address(wallet: String) -> String {
+    eth_service::get_eth_address(&wallet).await
+        .unwrap_or_else(|message| trap(&message))
+}
+
+#[update(guard = "is_caller_registered")]
+async fn poll_withdrawals() -> Vec<TransactionCandid> {
+    minter_service::poll_withdrawals().await
+}
+
+#[update(guard = "is_caller_registered")]
+fn append_wasm_chunk(chunk: Vec<u8>) -> u64 {
+    verify_caller(vec![VaultRole::Admin]);
+    canister_management_service::append_wasm_chunk(chunk)
+        .unwrap_or_else(|message| trap(&message))
+}
+
+#[update(guard = "is_caller_registered")]
+fn commit_wasm_upload() -> UploadedWasm {
+    verify_caller(vec![VaultRole::Admin]);
+    canister_management_service::commit_wasm_upload()
+        .unwrap_or_else(|message| trap(&message))
+}
+
+#[update(guard = "is_caller_registered")]
+fn delete_uploaded_wasm(hash: String) {
+    verify_caller(vec![VaultRole::Admin]);
+    canister_management_service::delete_uploaded_wasm(&hash)
+        .unwrap_or_else(|message| trap(&message))
+}
+
+#[query(guard = "is_caller_registered")]
+fn get_uploaded_wasms() -> Vec<UploadedWasm> {
+    canister_management_service::get_uploaded_wasms()
+}
+
+#[update(guard = "is_caller_registered")]
+async fn list_canister_snapshots(canister_id: Principal) -> Vec<CanisterSnapshot> {
+    canister_management_service::list_canister_snapshots(canister_id).await
+        .unwrap_or_else(|message| trap(&message))
+}
+
+#[update(guard = "is_caller_registered")]
+async fn get_canister_status() -> VaultCanisterStatus {
+    canister_status_service::refresh_canister_status().await
+        .unwrap_or_else(|message| trap(&message))
+}
+
+#[query(guard = "is_caller_registered")]
+fn get_canister_status_cached() -> Option<VaultCanisterStatus> {
+    canister_status_service::get_cached_canister_status()
+}
+
+#[update(guard = "is_caller_registered")]
+async fn reconcile() -> Vec<TransferDiscrepancy> {
+    reconcile_transfers().await
+}
+
+#[query(guard = "is_caller_registered")]
+async fn get_transfer_discrepancies() -> Vec<TransferDiscrepancy> {
+    get_discrepancies()
+}
+
 #[query]
 async fn canister_balance() -> u64 {
     ic_cdk::api::canister_balance()
@@ -131,6 +322,7 @@ fn pre_upgrade() {
 
 #[post_upgrade]
 pub async fn post_upgrade() {
+    timer_service::schedule_next_tick();
     stable_restore().await
 }
 
@@ -0,0 +1,287 @@
+use candid::{CandidType, Nat, Principal};
+use ic_cdk::call;
+use icrc_ledger_types::icrc1::account::{Account, Subaccount};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{get_ckbtc_ledger_id, get_ckbtc_minter_id, get_cketh_ledger_id, get_cketh_minter_id};
+use crate::enums::TransactionState::Executed;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_service::{get_all_transactions, restore_transaction};
+use crate::transaction::withdrawal::withdrawal_transaction::WithdrawalTransaction;
+use crate::transfer_service::{approve_icrc2, ApproveResult, get_icrc1_fee};
+use crate::util::to_array;
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WithdrawalKind {
+    CkBTC,
+    CkETH,
+}
+
+impl WithdrawalKind {
+    pub fn get_minter(&self) -> Principal {
+        match self {
+            WithdrawalKind::CkBTC => { get_ckbtc_minter_id() }
+            WithdrawalKind::CkETH => { get_cketh_minter_id() }
+        }
+    }
+
+    pub fn get_ledger(&self) -> Principal {
+        match self {
+            WithdrawalKind::CkBTC => { get_ckbtc_ledger_id() }
+            WithdrawalKind::CkETH => { get_cketh_ledger_id() }
+        }
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WithdrawalStatus {
+    Unknown,
+    Pending,
+    Submitted { txid: String },
+    Finalized { txid: String },
+    Reimbursed { txid: Option<String> },
+    AmountTooLow,
+}
+
+impl WithdrawalStatus {
+    pub fn is_final(&self) -> bool {
+        match self {
+            WithdrawalStatus::Finalized { .. } => { true }
+            WithdrawalStatus::Reimbursed { txid } => { txid.is_some() }
+            WithdrawalStatus::AmountTooLow => { true }
+            _ => { false }
+        }
+    }
+}
+
+//ckBTC minter interface
+
+#[derive(CandidType, Deserialize)]
+struct RetrieveBtcWithApprovalArgs {
+    address: String,
+    amount: u64,
+    from_subaccount: Option<Subaccount>,
+}
+
+#[derive(CandidType, Deserialize)]
+struct RetrieveBtcOk {
+    block_index: u64,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+enum RetrieveBtcWithApprovalError {
+    MalformedAddress(String),
+    AlreadyProcessing,
+    AmountTooLow(u64),
+    InsufficientFunds { balance: u64 },
+    InsufficientAllowance { allowance: u64 },
+    TemporarilyUnavailable(String),
+    GenericError { error_code: u64, error_message: String },
+}
+
+#[derive(CandidType, Deserialize)]
+struct RetrieveBtcStatusRequest {
+    block_index: u64,
+}
+
+#[derive(CandidType, Deserialize)]
+struct BtcTxid {
+    txid: Vec<u8>,
+}
+
+#[derive(CandidType, Deserialize)]
+enum RetrieveBtcStatus {
+    Unknown,
+    Pending,
+    Signing,
+    Sending(BtcTxid),
+    Submitted(BtcTxid),
+    AmountTooLow,
+    Confirmed(BtcTxid),
+}
+
+//ckETH minter interface
+
+#[derive(CandidType, Deserialize)]
+struct WithdrawalArg {
+    amount: Nat,
+    recipient: String,
+    from_subaccount: Option<Subaccount>,
+}
+
+#[derive(CandidType, Deserialize)]
+struct RetrieveEthRequest {
+    block_index: Nat,
+}
+
+#[derive(CandidType, Deserialize, Debug)]
+enum WithdrawalError {
+    AmountTooLow { min_withdrawal_amount: Nat },
+    InsufficientFunds { balance: Nat },
+    InsufficientAllowance { allowance: Nat },
+    RecipientAddressBlocked { address: String },
+    TemporarilyUnavailable(String),
+}
+
+#[derive(CandidType, Deserialize)]
+struct EthTransaction {
+    transaction_hash: String,
+}
+
+#[derive(CandidType, Deserialize)]
+struct TxFinalizedSuccess {
+    transaction_hash: String,
+    effective_transaction_fee: Option<Nat>,
+}
+
+#[derive(CandidType, Deserialize)]
+struct TxFinalizedReimbursed {
+    transaction_hash: String,
+    reimbursed_amount: Nat,
+    reimbursed_in_block: Nat,
+}
+
+#[derive(CandidType, Deserialize)]
+enum TxFinalizedStatus {
+    Success(TxFinalizedSuccess),
+    PendingReimbursement(EthTransaction),
+    Reimbursed(TxFinalizedReimbursed),
+}
+
+#[derive(CandidType, Deserialize)]
+enum RetrieveEthStatus {
+    NotFound,
+    Pending,
+    TxCreated,
+    TxSigned(EthTransaction),
+    TxSent(EthTransaction),
+    TxFinalized(TxFinalizedStatus),
+}
+
+//the minter burns the tokens from the wallet, so it is approved for the amount plus the ledger fee first
+pub async fn approve_minter(ledger: Principal, minter: Principal, wallet: String, amount: Nat) -> Result<Nat, String> {
+    let fee = get_icrc1_fee(ledger).await?;
+    let spender = Account { owner: minter, subaccount: None };
+    match approve_icrc2(ledger, amount + fee, spender, None, None, wallet).await {
+        Ok((ApproveResult::Ok(block_index), )) => { Ok(block_index) }
+        Ok((ApproveResult::Err(err), )) => { Err(format!("{:?}", err)) }
+        Err((_, message)) => { Err(message) }
+    }
+}
+
+//drops what is left of the minter allowance after a failed withdrawal
+pub async fn revoke_minter(ledger: Principal, minter: Principal, wallet: String) -> Result<Nat, String> {
+    let spender = Account { owner: minter, subaccount: None };
+    match approve_icrc2(ledger, Nat::from(0u64), spender, None, None, wallet).await {
+        Ok((ApproveResult::Ok(block_index), )) => { Ok(block_index) }
+        Ok((ApproveResult::Err(err), )) => { Err(format!("{:?}", err)) }
+        Err((_, message)) => { Err(message) }
+    }
+}
+
+//returns the withdrawal id, which is the burn block index on the minter ledger
+pub async fn withdraw(kind: &WithdrawalKind, minter: Principal, wallet: String, amount: Nat, address: String) -> Result<u64, String> {
+    let from_subaccount = Some(wallet_subaccount(wallet)?);
+    match kind {
+        WithdrawalKind::CkBTC => {
+            let args = RetrieveBtcWithApprovalArgs {
+                address,
+                amount: nat_to_u64_checked(&amount)?,
+                from_subaccount,
+            };
+            let (result, ): (Result<RetrieveBtcOk, RetrieveBtcWithApprovalError>, ) = call(minter, "retrieve_btc_with_approval", (args, )).await
+                .map_err(|e| format!("failed to call minter: {:?}", e))?;
+            result.map(|ok| ok.block_index)
+                .map_err(|e| format!("{:?}", e))
+        }
+        WithdrawalKind::CkETH => {
+            let args = WithdrawalArg {
+                amount,
+                recipient: address,
+                from_subaccount,
+            };
+            let (result, ): (Result<RetrieveEthRequest, WithdrawalError>, ) = call(minter, "withdraw_eth", (args, )).await
+                .map_err(|e| format!("failed to call minter: {:?}", e))?;
+            let request = result.map_err(|e| format!("{:?}", e))?;
+            nat_to_u64_checked(&request.block_index)
+        }
+    }
+}
+
+pub async fn get_withdrawal_status(kind: &WithdrawalKind, minter: Principal, withdrawal_id: u64) -> Result<WithdrawalStatus, String> {
+    match kind {
+        WithdrawalKind::CkBTC => {
+            let (status, ): (RetrieveBtcStatus, ) = call(minter, "retrieve_btc_status", (RetrieveBtcStatusRequest { block_index: withdrawal_id }, )).await
+                .map_err(|e| format!("failed to call minter: {:?}", e))?;
+            Ok(match status {
+                RetrieveBtcStatus::Unknown => { WithdrawalStatus::Unknown }
+                RetrieveBtcStatus::Pending | RetrieveBtcStatus::Signing => { WithdrawalStatus::Pending }
+                RetrieveBtcStatus::Sending(tx) | RetrieveBtcStatus::Submitted(tx) => {
+                    WithdrawalStatus::Submitted { txid: to_btc_txid(tx.txid) }
+                }
+                RetrieveBtcStatus::AmountTooLow => { WithdrawalStatus::AmountTooLow }
+                RetrieveBtcStatus::Confirmed(tx) => { WithdrawalStatus::Finalized { txid: to_btc_txid(tx.txid) } }
+            })
+        }
+        WithdrawalKind::CkETH => {
+            let (status, ): (RetrieveEthStatus, ) = call(minter, "retrieve_eth_status", (withdrawal_id, )).await
+                .map_err(|e| format!("failed to call minter: {:?}", e))?;
+            Ok(match status {
+                RetrieveEthStatus::NotFound => { WithdrawalStatus::Unknown }
+                RetrieveEthStatus::Pending | RetrieveEthStatus::TxCreated => { WithdrawalStatus::Pending }
+                RetrieveEthStatus::TxSigned(tx) | RetrieveEthStatus::TxSent(tx) => {
+                    WithdrawalStatus::Submitted { txid: tx.transaction_hash }
+                }
+                RetrieveEthStatus::TxFinalized(TxFinalizedStatus::Success(tx)) => {
+                    WithdrawalStatus::Finalized { txid: tx.transaction_hash }
+                }
+                RetrieveEthStatus::TxFinalized(TxFinalizedStatus::PendingReimbursement(_)) => {
+                    WithdrawalStatus::Reimbursed { txid: None }
+                }
+                RetrieveEthStatus::TxFinalized(TxFinalizedStatus::Reimbursed(tx)) => {
+                    WithdrawalStatus::Reimbursed { txid: Some(tx.transaction_hash) }
+                }
+            })
+        }
+    }
+}
+
+//polls the minters for every executed withdrawal that has not reached a final status yet
+pub async fn poll_withdrawals() -> Vec<TransactionCandid> {
+    let withdrawals: Vec<WithdrawalTransaction> = get_all_transactions().into_iter()
+        .filter(|tr| tr.get_state().eq(&Executed))
+        .filter_map(|tr| match tr.to_candid() {
+            TransactionCandid::WithdrawalTransactionV(w) => Some(w),
+            _ => None
+        })
+        .filter(|w| !w.is_finalized())
+        .collect();
+    let mut updated = Vec::new();
+    for mut withdrawal in withdrawals {
+        if withdrawal.refresh_status().await {
+            restore_transaction(Box::new(withdrawal.clone()));
+            updated.push(withdrawal.to_candid());
+        }
+    }
+    updated
+}
+
+fn wallet_subaccount(wallet: String) -> Result<Subaccount, String> {
+    hex::decode(wallet)
+        .map(to_array)
+        .map_err(|e| format!("Failed to decode hex: {:?}", e))
+}
+
+fn nat_to_u64_checked(n: &Nat) -> Result<u64, String> {
+    let digits = n.0.to_u64_digits();
+    if digits.len() > 1 {
+        return Err(format!("{} does not fit into nat64", n));
+    }
+    Ok(digits.first().cloned().unwrap_or(0))
+}
+
+//bitcoin explorers show the txid in reversed byte order
+fn to_btc_txid(mut txid: Vec<u8>) -> String {
+    txid.reverse();
+    hex::encode(txid)
+}
@@ -0,0 +1,329 @@
+use std::cell::RefCell;
+use std::future::Future;
+
+use candid::{CandidType, Nat, Principal};
+use ic_cdk::api::time;
+use ic_cdk::id;
+use ic_ledger_types::{AccountIdentifier, Block, Memo as MemoLegacy, Operation, Subaccount as SubLegacy, Tokens};
+use icrc_ledger_types::icrc1::account::Account;
+use icrc_ledger_types::icrc1::transfer::Memo;
+use icrc_ledger_types::icrc3::transactions::Transaction;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::TransactionState;
+use crate::enums::TransactionState::{Approved, Executed, Failed};
+use crate::state::get_current_state;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_service::{get_all_transactions, restore_transaction};
+use crate::transaction::transfer::transfer_executor_common::TransferExecutor;
+use crate::transaction::transfer::transfer_icrc1_executor_common::TransferICRC1Executor;
+use crate::transfer_service::{get_icp_blocks, get_icrc1_transactions, get_index_account_transactions, nat_to_u64};
+use crate::util::to_array;
+
+//the ledgers deduplicate transfers within 24 hours, older transfers are not reconciled
+const RECONCILE_WINDOW_NANOS: u64 = 24 * 60 * 60 * 1_000_000_000;
+//how long after created_at_time a missing transfer is searched for
+const SEARCH_WINDOW_NANOS: u64 = 5 * 60 * 1_000_000_000;
+//an Approved transfer younger than this may still be awaiting the ledger reply and is left to the executor
+const IN_FLIGHT_TIMEOUT_NANOS: u64 = 15 * 60 * 1_000_000_000;
+const SEARCH_PAGE: u64 = 1000;
+const INDEX_PAGE: u64 = 500;
+
+thread_local! {
+    pub static DISCREPANCIES: RefCell<Vec<TransferDiscrepancy>> = RefCell::new(Default::default());
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize, PartialEq)]
+pub enum DiscrepancyKind {
+    //the stored block does not exist on the ledger
+    BlockNotFound,
+    //the stored block moves another amount or between other accounts
+    BlockMismatch,
+    //the transfer was found on the ledger and the transaction was moved to Executed
+    StateCorrected,
+    //the ledger could not be queried
+    Unverifiable,
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct TransferDiscrepancy {
+    pub transaction_id: u64,
+    pub kind: DiscrepancyKind,
+    pub message: String,
+    pub detected_date: u64,
+}
+
+impl TransferDiscrepancy {
+    fn new(transaction_id: u64, kind: DiscrepancyKind, message: String) -> Self {
+        TransferDiscrepancy {
+            transaction_id,
+            kind,
+            message,
+            detected_date: time(),
+        }
+    }
+}
+
+pub fn get_discrepancies() -> Vec<TransferDiscrepancy> {
+    DISCREPANCIES.with(|d| d.borrow().clone())
+}
+
+pub fn restore_discrepancies(discrepancies: Vec<TransferDiscrepancy>) {
+    DISCREPANCIES.with(|d| d.replace(discrepancies));
+}
+
+//checks recent transfers against the ledgers, the result replaces the discrepancies of the previous run
+pub async fn reconcile_transfers() -> Vec<TransferDiscrepancy> {
+    let transfers: Vec<TransactionCandid> = get_all_transactions().into_iter()
+        .filter(|t| [Approved, Executed, Failed].contains(t.get_state()))
+        .map(|t| t.to_candid())
+        .collect();
+    let mut discrepancies = Vec::new();
+    for transfer in transfers {
+        let discrepancy = match transfer {
+            TransactionCandid::TransferTransactionV(t) => reconcile_icp(t).await,
+            TransactionCandid::TransferQuorumTransactionV(t) => reconcile_icp(t).await,
+            TransactionCandid::TransferICRC1TransactionV(t) => reconcile_icrc1(t).await,
+            TransactionCandid::TransferICRC1QuorumTransactionV(t) => reconcile_icrc1(t).await,
+            //top ups are not corrected here, the cycles minting canister has to be notified after the transfer
+            _ => None
+        };
+        if let Some(d) = discrepancy {
+            discrepancies.push(d);
+        }
+    }
+    restore_discrepancies(discrepancies.clone());
+    discrepancies
+}
+
+async fn reconcile_icp<T: TransferExecutor + Clone + 'static>(trs: T) -> Option<TransferDiscrepancy> {
+    let created_at_time = get_recent(trs.get_created_at_time())?;
+    let from = match hex::decode(trs.get_wallet()) {
+        Ok(x) => AccountIdentifier::new(&id(), &SubLegacy(to_array(x))),
+        Err(e) => {
+            return Some(TransferDiscrepancy::new(trs.get_id(), DiscrepancyKind::Unverifiable, format!("Failed to decode wallet: {:?}", e)));
+        }
+    };
+    match trs.get_block_index() {
+        Some(block_index) => {
+            let to = AccountIdentifier::from_hex(&trs.get_address()).ok();
+            let amount = Tokens::from_e8s(trs.get_amount());
+            match get_icp_blocks(block_index, 1).await {
+                Err(message) => {
+                    Some(TransferDiscrepancy::new(trs.get_id(), DiscrepancyKind::Unverifiable, message))
+                }
+                Ok((_, blocks)) => {
+                    match blocks.into_iter().find(|(i, _)| *i == block_index) {
+                        None => {
+                            Some(TransferDiscrepancy::new(trs.get_id(), DiscrepancyKind::BlockNotFound, format!("Block {} not found", block_index)))
+                        }
+                        Some((_, block)) => {
+                            let matches = match block.transaction.operation {
+                                Some(Operation::Transfer { from: f, to: t, amount: a, .. }) => {
+                                    f == from && Some(t) == to && a == amount
+                                }
+                                _ => false
+                            };
+                            if matches {
+                                None
+                            } else {
+                                Some(TransferDiscrepancy::new(trs.get_id(), DiscrepancyKind::BlockMismatch, format!("Block {} does not match the transfer", block_index)))
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        None => {
+            let found = find_icp_block(from, MemoLegacy(trs.get_id()), created_at_time).await;
+            resolve_found(trs, created_at_time, found, |t, b| t.set_block_index(Some(b)))
+        }
+    }
+}
+
+async fn reconcile_icrc1<T: TransferICRC1Executor + Clone + 'static>(trs: T) -> Option<TransferDiscrepancy> {
+    let created_at_time = get_recent(trs.get_created_at_time())?;
+    let ledger = trs.get_ledger_id();
+    let from = match hex::decode(trs.get_wallet()) {
+        Ok(x) => Account { owner: id(), subaccount: Some(to_array(x)) },
+        Err(e) => {
+            return Some(TransferDiscrepancy::new(trs.get_id(), DiscrepancyKind::Unverifiable, format!("Failed to decode wallet: {:?}", e)));
+        }
+    };
+    match trs.get_block_index() {
+        Some(block_index) => {
+            let index = nat_to_u64(&block_index);
+            let to = Account { owner: trs.get_to_principal(), subaccount: trs.get_to_subaccount() };
+            let amount = Nat::from(trs.get_amount());
+            match get_icrc1_transactions(ledger, index, 1).await {
+                Err(message) => {
+                    Some(TransferDiscrepancy::new(trs.get_id(), DiscrepancyKind::Unverifiable, message))
+                }
+                Ok((_, transactions)) => {
+                    match transactions.into_iter().find(|(i, _)| *i == index) {
+                        None => {
+                            Some(TransferDiscrepancy::new(trs.get_id(), DiscrepancyKind::BlockNotFound, format!("Block {} not found", index)))
+                        }
+                        Some((_, transaction)) => {
+                            let matches = match transaction.transfer {
+                                Some(t) => t.from == from && t.to == to && t.amount == amount,
+                                None => false
+                            };
+                            if matches {
+                                None
+                            } else {
+                                Some(TransferDiscrepancy::new(trs.get_id(), DiscrepancyKind::BlockMismatch, format!("Block {} does not match the transfer", index)))
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        None => {
+            let memo = trs.get_icrc1_memo()
+                .unwrap_or_else(|| Memo::from(trs.get_id()));
+            let index_canister = get_current_state().icrc1_canisters.into_iter()
+                .find(|c| c.ledger == ledger)
+                .and_then(|c| c.index);
+            let found = match index_canister {
+                Some(index_canister) => find_icrc1_in_index(index_canister, from, memo, created_at_time).await,
+                None => find_icrc1_in_ledger(ledger, from, memo, created_at_time).await,
+            };
+            resolve_found(trs, created_at_time, found, |t, b| t.set_block_index(Some(Nat::from(b))))
+        }
+    }
+}
+
+fn get_recent(created_at_time: Option<u64>) -> Option<u64> {
+    created_at_time.filter(|t| t + RECONCILE_WINDOW_NANOS > time())
+}
+
+//a transfer which reached the ledger although the transaction is Failed or stuck in Approved gets its state corrected
+fn resolve_found<T, F>(mut trs: T, created_at_time: u64, found: Result<Option<u64>, String>, set_block_index: F) -> Option<TransferDiscrepancy>
+    where T: ITransaction + Clone + 'static, F: FnOnce(&mut T, u64) {
+    //the ledger calls above yield, the executor may have finished the transfer in the meantime
+    let current = get_all_transactions().into_iter().find(|t| t.get_id() == trs.get_id())?;
+    if !is_reconcilable(current.get_state(), created_at_time) {
+        return None;
+    }
+    match found {
+        Err(message) => {
+            Some(TransferDiscrepancy::new(trs.get_id(), DiscrepancyKind::Unverifiable, message))
+        }
+        Ok(None) => None,
+        Ok(Some(block_index)) => {
+            let message = format!("{} transfer found in block {}", trs.get_state().to_string(), block_index);
+            set_block_index(&mut trs, block_index);
+            trs.set_state(Executed);
+            trs.get_common_mut().error = None;
+            restore_transaction(Box::new(trs.clone()));
+            Some(TransferDiscrepancy::new(trs.get_id(), DiscrepancyKind::StateCorrected, message))
+        }
+    }
+}
+
+fn is_reconcilable(state: &TransactionState, created_at_time: u64) -> bool {
+    match state {
+        Failed => true,
+        Approved => created_at_time + IN_FLIGHT_TIMEOUT_NANOS <= time(),
+        _ => false
+    }
+}
+
+//blocks are appended in time order, so the first block not older than created_at_time is found by bisection
+pub async fn first_block_after<F, Fut>(chain_length: u64, created_at_time: u64, timestamp_at: F) -> Result<u64, String>
+    where F: Fn(u64) -> Fut, Fut: Future<Output=Result<u64, String>> {
+    let mut low = 0;
+    let mut high = chain_length;
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if timestamp_at(middle).await? < created_at_time {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+    Ok(low)
+}
+
+pub async fn icp_timestamp_at(index: u64) -> Result<u64, String> {
+    let (_, blocks) = get_icp_blocks(index, 1).await?;
+    blocks.into_iter()
+        .find(|(i, _)| *i == index)
+        .map(|(_, block)| block.timestamp.timestamp_nanos)
+        .ok_or(format!("Block {} not available", index))
+}
+
+async fn icrc1_timestamp_at(ledger: Principal, index: u64) -> Result<u64, String> {
+    let (_, transactions) = get_icrc1_transactions(ledger, index, 1).await?;
+    transactions.into_iter()
+        .find(|(i, _)| *i == index)
+        .map(|(_, transaction)| transaction.timestamp)
+        .ok_or(format!("Block {} not available", index))
+}
+
+fn is_icp_transfer_of(block: &Block, from: &AccountIdentifier, memo: &MemoLegacy, created_at_time: u64) -> bool {
+    block.transaction.memo == *memo
+        && block.transaction.created_at_time.timestamp_nanos == created_at_time
+        && match &block.transaction.operation {
+        Some(Operation::Transfer { from: f, .. }) => f == from,
+        _ => false
+    }
+}
+
+fn is_icrc1_transfer_of(transaction: &Transaction, from: &Account, memo: &Memo, created_at_time: u64) -> bool {
+    match &transaction.transfer {
+        Some(t) => t.from == *from && t.memo.as_ref() == Some(memo) && t.created_at_time == Some(created_at_time),
+        None => false
+    }
+}
+
+async fn find_icp_block(from: AccountIdentifier, memo: MemoLegacy, created_at_time: u64) -> Result<Option<u64>, String> {
+    let (chain_length, _) = get_icp_blocks(0, 0).await?;
+    let mut start = first_block_after(chain_length, created_at_time, icp_timestamp_at).await?;
+    while start < chain_length {
+        let (_, blocks) = get_icp_blocks(start, SEARCH_PAGE).await?;
+        if blocks.is_empty() {
+            break;
+        }
+        for (i, block) in blocks.iter() {
+            if block.timestamp.timestamp_nanos > created_at_time + SEARCH_WINDOW_NANOS {
+                return Ok(None);
+            }
+            if is_icp_transfer_of(block, &from, &memo, created_at_time) {
+                return Ok(Some(*i));
+            }
+            start = i + 1;
+        }
+    }
+    Ok(None)
+}
+
+async fn find_icrc1_in_ledger(ledger: Principal, from: Account, memo: Memo, created_at_time: u64) -> Result<Option<u64>, String> {
+    let (log_length, _) = get_icrc1_transactions(ledger, 0, 0).await?;
+    let mut start = first_block_after(log_length, created_at_time, |i| icrc1_timestamp_at(ledger, i)).await?;
+    while start < log_length {
+        let (_, transactions) = get_icrc1_transactions(ledger, start, SEARCH_PAGE).await?;
+        if transactions.is_empty() {
+            break;
+        }
+        for (i, transaction) in transactions.iter() {
+            if transaction.timestamp > created_at_time + SEARCH_WINDOW_NANOS {
+                return Ok(None);
+            }
+            if is_icrc1_transfer_of(transaction, &from, &memo, created_at_time) {
+                return Ok(Some(*i));
+            }
+            start = i + 1;
+        }
+    }
+    Ok(None)
+}
+
+//the index returns the latest transactions of the wallet first, one page covers the reconcile window
+async fn find_icrc1_in_index(index_canister: Principal, from: Account, memo: Memo, created_at_time: u64) -> Result<Option<u64>, String> {
+    let page = get_index_account_transactions(index_canister, from, None, INDEX_PAGE).await?;
+    Ok(page.transactions.into_iter()
+        .find(|t| is_icrc1_transfer_of(&t.transaction, &from, &memo, created_at_time))
+        .map(|t| nat_to_u64(&t.id)))
+}
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use candid::{CandidType, Nat};
+use serde::{Deserialize, Serialize};
+
+use crate::enums::Currency;
+use crate::enums::TransactionState::Executed;
+use crate::transaction::transaction::TransactionCandid;
+use crate::transaction::transaction_service::get_all_transactions;
+use crate::transaction::transfer::transfer_common::TransferCommon;
+
+const DAY_NANOS: u64 = 24 * 60 * 60 * 1_000_000_000;
+//cycles deposits are paid from the vault cycle balance, which is not a wallet
+const VAULT_CYCLES_WALLET: &str = "vault_cycles";
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub enum ReportPeriod {
+    Day,
+    Month,
+    Year,
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct OutgoingReportRequest {
+    pub period: ReportPeriod,
+    //creation date range, nanoseconds
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct OutgoingReportEntry {
+    //e.g. 2024-05 for monthly reports
+    pub period: String,
+    //wallet uid or vault_cycles
+    pub wallet: String,
+    //untagged transactions are reported without a tag
+    pub tag: Option<String>,
+    pub currency: Currency,
+    pub amount: Nat,
+    pub count: u64,
+}
+
+//transactions with several tags are reported under each of them
+pub fn get_outgoing_report(request: OutgoingReportRequest) -> Vec<OutgoingReportEntry> {
+    let mut entries: HashMap<(String, String, Option<String>, Currency), (Nat, u64)> = HashMap::new();
+    for tr in get_all_transactions() {
+        let common = tr.get_common_ref();
+        if !common.state.eq(&Executed)
+            || request.from.map_or(false, |from| common.created_date < from)
+            || request.to.map_or(false, |to| common.created_date > to) {
+            continue;
+        }
+        let (wallet, currency, amount) = match get_outgoing(tr.to_candid()) {
+            None => { continue; }
+            Some(x) => { x }
+        };
+        let wallet = if wallet.is_empty() { VAULT_CYCLES_WALLET.to_string() } else { wallet };
+        let period = format_period(common.created_date, &request.period);
+        let tags: Vec<Option<String>> = match common.metadata.as_ref().filter(|m| !m.tags.is_empty()) {
+            None => { vec![None] }
+            Some(metadata) => { metadata.tags.iter().cloned().map(Some).collect() }
+        };
+        for tag in tags {
+            let entry = entries.entry((period.clone(), wallet.clone(), tag, currency.clone()))
+                .or_insert((Nat::from(0u64), 0));
+            entry.0 += amount.clone();
+            entry.1 += 1;
+        }
+    }
+    let mut report: Vec<OutgoingReportEntry> = entries.into_iter()
+        .map(|((period, wallet, tag, currency), (amount, count))| OutgoingReportEntry { period, wallet, tag, currency, amount, count })
+        .collect();
+    report.sort_by(|a, b| (&a.period, &a.wallet, &a.tag).cmp(&(&b.period, &b.wallet, &b.tag)));
+    report
+}
+
+//wallet, currency and amount of the transactions moving funds out of the vault
+pub fn get_outgoing(candid: TransactionCandid) -> Option<(String, Currency, Nat)> {
+    match candid {
+        TransactionCandid::TransferTransactionV(t) => { Some(from_transfer(&t)) }
+        TransactionCandid::TransferQuorumTransactionV(t) => { Some(from_transfer(&t)) }
+        TransactionCandid::TransferICRC1TransactionV(t) => { Some(from_transfer(&t)) }
+        TransactionCandid::TransferICRC1QuorumTransactionV(t) => { Some(from_transfer(&t)) }
+        TransactionCandid::TransferBTCTransactionV(t) => { Some(from_transfer(&t)) }
+        TransactionCandid::TopUpTransactionV(t) => { Some(from_transfer(&t)) }
+        TransactionCandid::TopUpQuorumTransactionV(t) => { Some(from_transfer(&t)) }
+        TransactionCandid::DepositCyclesTransactionV(t) => { Some(from_transfer(&t)) }
+        TransactionCandid::AutoTopUpTransactionV(t) => { Some((t.wallet, Currency::ICP, Nat::from(t.amount))) }
+        TransactionCandid::EthTransferTransactionV(t) => { Some((t.wallet, Currency::ETH, t.value)) }
+        TransactionCandid::WithdrawalTransactionV(t) => { Some((t.wallet, Currency::ICRC1(t.ledger), t.amount)) }
+        _ => { None }
+    }
+}
+
+fn from_transfer<T: TransferCommon>(t: &T) -> (String, Currency, Nat) {
+    (t.get_wallet(), t.get_currency(), Nat::from(t.get_amount()))
+}
+
+fn format_period(date: u64, period: &ReportPeriod) -> String {
+    let (year, month, day) = to_civil_date(date / DAY_NANOS);
+    match period {
+        ReportPeriod::Day => { format!("{:04}-{:02}-{:02}", year, month, day) }
+        ReportPeriod::Month => { format!("{:04}-{:02}", year, month) }
+        ReportPeriod::Year => { format!("{:04}", year) }
+    }
+}
+
+//days since the unix epoch to the gregorian year, month and day
+fn to_civil_date(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
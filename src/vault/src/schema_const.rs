@@ -0,0 +1,4 @@
+//stable memory schema of this build, published to the repo together with the wasm.
+//bump it whenever a build can no longer restore the memory written by the previous one,
+//e.g. a new transaction variant or a changed non optional field
+pub const SCHEMA_VERSION: u32 = 1;
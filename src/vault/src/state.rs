@@ -4,8 +4,11 @@ use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::hash::Hash;
 use crate::enums::TransactionState::Executed;
+use crate::transaction::address_book::address_book::AddressBookEntry;
+use crate::transaction::canister::canister::{CanisterPermission, ManagedCanister};
 use crate::transaction::member::members::Member;
 use crate::transaction::policy::policy::Policy;
+use crate::transaction::vault::auto_top_up_rule::AutoTopUpRule;
 use crate::transaction::vault::quorum::Quorum;
 use crate::transaction::transaction::ITransaction;
 use crate::transaction::transaction_service::get_all_transactions;
@@ -43,6 +46,11 @@ pub struct VaultState {
     pub name: Option<String>,
     pub description: Option<String>,
     pub icrc1_canisters: Vec<ICRC1>,
+    pub address_book: Vec<AddressBookEntry>,
+    pub archived_wallets: Vec<Wallet>,
+    pub canister_permissions: Vec<CanisterPermission>,
+    pub managed_canisters: Vec<ManagedCanister>,
+    pub auto_top_up: Option<AutoTopUpRule>,
 }
 
 impl VaultState {
@@ -54,7 +62,12 @@ impl VaultState {
             policies: vec![],
             name: None,
             description: None,
-            icrc1_canisters: vec![]
+            icrc1_canisters: vec![],
+            address_book: vec![],
+            archived_wallets: vec![],
+            canister_permissions: vec![],
+            managed_canisters: vec![],
+            auto_top_up: None,
         }
     }
 }
@@ -0,0 +1,21 @@
+use ic_cdk::api::time;
+
+use crate::{auto_top_up_service, upgrade_proposal_service};
+
+//ic-cdk-timers is not among the dependencies, the vault arms the canister global timer itself
+const TIMER_INTERVAL_NANOS: u64 = 60 * 1_000_000_000;
+
+//the global timer fires once, so it is armed on init and post_upgrade and re-armed on every tick
+pub fn schedule_next_tick() {
+    unsafe {
+        ic0::global_timer_set((time() + TIMER_INTERVAL_NANOS) as i64);
+    }
+}
+
+#[export_name = "canister_global_timer"]
+fn canister_global_timer() {
+    ic_cdk::setup();
+    schedule_next_tick();
+    ic_cdk::spawn(auto_top_up_service::check_auto_top_up());
+    ic_cdk::spawn(upgrade_proposal_service::check_new_versions());
+}
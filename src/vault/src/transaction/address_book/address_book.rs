@@ -0,0 +1,75 @@
+use candid::{CandidType, Principal};
+use ic_cdk::api::time;
+use ic_ledger_types::AccountIdentifier;
+use serde::{Deserialize, Serialize};
+
+use crate::bitcoin_service::address_to_script;
+use crate::config::get_btc_network;
+use crate::eth_service::decode_address;
+use crate::state::VaultState;
+use crate::transaction::member::members::{Account, calculate_id};
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub enum Address {
+    AccountIdentifier(String),
+    ICRC1(Account),
+    BTC(String),
+    ETH(String),
+}
+
+impl Address {
+    //cycles sent to a canister are addressed to its default account
+    pub fn canister(canister: Principal) -> Self {
+        Address::ICRC1(Account { owner: canister, subaccount: None })
+    }
+
+    //both ICP account identifiers and ICRC-1 accounts are compared by the ICP account identifier they resolve to,
+    //other chain addresses are validated and compared as given
+    pub fn to_comparable(&self) -> Result<String, String> {
+        match self {
+            Address::AccountIdentifier(hex) => {
+                AccountIdentifier::from_hex(hex).map(|a| a.to_hex())
+            }
+            Address::ICRC1(account) => {
+                Ok(calculate_id(account.clone()))
+            }
+            Address::BTC(address) => {
+                address_to_script(address, get_btc_network()).map(|_| address.clone())
+            }
+            Address::ETH(address) => {
+                decode_address(address).map(hex::encode)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct AddressBookEntry {
+    pub uid: String,
+    pub label: String,
+    pub address: Address,
+    pub created_date: u64,
+    pub modified_date: u64,
+}
+
+impl AddressBookEntry {
+    pub fn new(uid: String, label: String, address: Address) -> Self {
+        AddressBookEntry {
+            uid,
+            label,
+            address,
+            created_date: time(),
+            modified_date: time(),
+        }
+    }
+}
+
+pub fn is_whitelisted(address: &Address, state: &VaultState) -> bool {
+    let comparable = match address.to_comparable() {
+        Ok(x) => { x }
+        Err(_) => { return false; }
+    };
+    state.address_book.iter()
+        .filter_map(|e| e.address.to_comparable().ok())
+        .any(|a| a.eq_ignore_ascii_case(&comparable))
+}
@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::TransactionState;
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError::{AddressAlreadyExists, InvalidAddress, UIDAlreadyExists};
+use crate::impl_basic_for_transaction;
+use crate::state::VaultState;
+use crate::transaction::address_book::address_book::{Address, AddressBookEntry, is_whitelisted};
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+
+impl_basic_for_transaction!(AddressBookAddTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct AddressBookAddTransaction {
+    common: BasicTransactionFields,
+    uid: String,
+    label: String,
+    address: Address,
+}
+
+impl AddressBookAddTransaction {
+    fn new(state: TransactionState, batch_uid: Option<String>, uid: String, label: String, address: Address) -> Self {
+        AddressBookAddTransaction {
+            common: BasicTransactionFields::new(state, batch_uid, true),
+            uid,
+            label,
+            address,
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for AddressBookAddTransaction {
+    async fn execute(&mut self, mut state: VaultState) -> VaultState {
+        if state.address_book.iter().any(|e| e.uid.eq(&self.uid)) {
+            self.set_state(Failed);
+            self.common.error = Some(UIDAlreadyExists);
+            return state;
+        }
+        if self.address.to_comparable().is_err() {
+            self.set_state(Failed);
+            self.common.error = Some(InvalidAddress);
+            return state;
+        }
+        if is_whitelisted(&self.address, &state) {
+            self.set_state(Failed);
+            self.common.error = Some(AddressAlreadyExists);
+            return state;
+        }
+        let entry = AddressBookEntry::new(self.uid.clone(), self.label.clone(), self.address.clone());
+        state.address_book.push(entry);
+        self.set_state(Executed);
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::AddressBookAddTransactionV(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct AddressBookAddTransactionRequest {
+    uid: String,
+    label: String,
+    address: Address,
+    batch_uid: Option<String>,
+}
+
+pub struct AddressBookAddTransactionBuilder {
+    request: AddressBookAddTransactionRequest,
+}
+
+impl AddressBookAddTransactionBuilder {
+    pub fn init(request: AddressBookAddTransactionRequest) -> Self {
+        return AddressBookAddTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for AddressBookAddTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = AddressBookAddTransaction::new(
+            state,
+            self.request.batch_uid.clone(),
+            self.request.uid.clone(),
+            self.request.label.clone(),
+            self.request.address.clone(),
+        );
+        Box::new(trs)
+    }
+}
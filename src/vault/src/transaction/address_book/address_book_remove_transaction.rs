@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::TransactionState;
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError::AddressNotExists;
+use crate::impl_basic_for_transaction;
+use crate::state::VaultState;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+
+impl_basic_for_transaction!(AddressBookRemoveTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct AddressBookRemoveTransaction {
+    common: BasicTransactionFields,
+    uid: String,
+}
+
+impl AddressBookRemoveTransaction {
+    fn new(state: TransactionState, batch_uid: Option<String>, uid: String) -> Self {
+        AddressBookRemoveTransaction {
+            common: BasicTransactionFields::new(state, batch_uid, true),
+            uid,
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for AddressBookRemoveTransaction {
+    async fn execute(&mut self, mut state: VaultState) -> VaultState {
+        match state.address_book.iter().find(|e| e.uid.eq(&self.uid)) {
+            None => {
+                self.set_state(Failed);
+                self.common.error = Some(AddressNotExists);
+                state
+            }
+            Some(_) => {
+                state.address_book.retain(|e| !e.uid.eq(&self.uid));
+                self.set_state(Executed);
+                state
+            }
+        }
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::AddressBookRemoveTransactionV(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct AddressBookRemoveTransactionRequest {
+    uid: String,
+    batch_uid: Option<String>,
+}
+
+pub struct AddressBookRemoveTransactionBuilder {
+    request: AddressBookRemoveTransactionRequest,
+}
+
+impl AddressBookRemoveTransactionBuilder {
+    pub fn init(request: AddressBookRemoveTransactionRequest) -> Self {
+        return AddressBookRemoveTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for AddressBookRemoveTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = AddressBookRemoveTransaction::new(
+            state,
+            self.request.batch_uid.clone(),
+            self.request.uid.clone(),
+        );
+        Box::new(trs)
+    }
+}
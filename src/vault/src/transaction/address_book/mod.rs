@@ -0,0 +1,3 @@
+pub mod address_book;
+pub mod address_book_add_transaction;
+pub mod address_book_remove_transaction;
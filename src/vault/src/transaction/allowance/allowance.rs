@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+
+use candid::{CandidType, Nat, Principal};
+use icrc_ledger_types::icrc1::account::Account as ICRC1Account;
+use icrc_ledger_types::icrc1::transfer::BlockIndex;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::TransactionState::Executed;
+use crate::transaction::member::members::Account;
+use crate::transaction::transaction::TransactionCandid;
+use crate::transaction::transaction_service::get_all_transactions;
+use crate::transfer_service::{approve_icrc2, ApproveResult, get_icrc2_allowance};
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct WalletAllowance {
+    pub wallet: String,
+    pub ledger_id: Principal,
+    pub spender: Account,
+    pub allowance: Nat,
+    pub expires_at: Option<u64>,
+}
+
+pub async fn approve(ledger_id: Principal, wallet: String, spender: Account, amount: u64,
+                     expected_allowance: Option<u64>, expires_at: Option<u64>) -> Result<BlockIndex, String> {
+    let spender = ICRC1Account {
+        owner: spender.owner,
+        subaccount: spender.subaccount,
+    };
+    match approve_icrc2(ledger_id, Nat::from(amount), spender, expected_allowance, expires_at, wallet).await {
+        Ok((ApproveResult::Ok(block_index), )) => { Ok(block_index) }
+        Ok((ApproveResult::Err(err), )) => { Err(format!("{:?}", err)) }
+        Err((_, message)) => { Err(message) }
+    }
+}
+
+//spenders are taken from executed approvals and withdrawals, the allowance itself is always read from the ledger
+pub async fn get_wallet_allowances(wallet: String) -> Vec<WalletAllowance> {
+    let mut spenders: HashSet<(Principal, Principal, Option<[u8; 32]>)> = HashSet::new();
+    for tr in get_all_transactions() {
+        if tr.get_state() != &Executed {
+            continue;
+        }
+        if let TransactionCandid::ICRC2ApproveTransactionV(approve) = tr.to_candid() {
+            if approve.wallet == wallet {
+                spenders.insert((approve.ledger_id, approve.spender.owner, approve.spender.subaccount));
+            }
+        }
+    }
+    //withdrawals approve the minter before the call, a failed one may leave an allowance behind
+    for tr in get_all_transactions() {
+        if let TransactionCandid::WithdrawalTransactionV(withdrawal) = tr.to_candid() {
+            if withdrawal.wallet == wallet && withdrawal.approve_block_index.is_some() {
+                spenders.insert((withdrawal.ledger, withdrawal.minter, None));
+            }
+        }
+    }
+    let mut allowances = Vec::new();
+    for (ledger_id, owner, subaccount) in spenders {
+        let spender = ICRC1Account { owner, subaccount };
+        if let Ok((allowance, )) = get_icrc2_allowance(ledger_id, spender, wallet.clone()).await {
+            if allowance.allowance > Nat::from(0u64) {
+                allowances.push(WalletAllowance {
+                    wallet: wallet.clone(),
+                    ledger_id,
+                    spender: Account { owner, subaccount },
+                    allowance: allowance.allowance,
+                    expires_at: allowance.expires_at,
+                });
+            }
+        }
+    }
+    allowances
+}
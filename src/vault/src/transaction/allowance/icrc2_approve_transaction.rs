@@ -0,0 +1,122 @@
+use async_trait::async_trait;
+use candid::{CandidType, Principal};
+use icrc_ledger_types::icrc1::transfer::BlockIndex;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::{TransactionState, VaultRole};
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError;
+use crate::errors::VaultError::CanisterReject;
+use crate::impl_basic_for_transaction;
+use crate::state::{get_current_state, VaultState};
+use crate::transaction::allowance::allowance::approve;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::member::members::Account;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+use crate::transaction::wallet::wallet::check_wallet_not_archived;
+
+impl_basic_for_transaction!(ICRC2ApproveTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct ICRC2ApproveTransaction {
+    common: BasicTransactionFields,
+    pub wallet: String,
+    pub ledger_id: Principal,
+    pub spender: Account,
+    amount: u64,
+    expires_at: Option<u64>,
+    expected_allowance: Option<u64>,
+    pub block_index: Option<BlockIndex>,
+}
+
+impl ICRC2ApproveTransaction {
+    fn new(state: TransactionState, wallet: String, ledger_id: Principal, spender: Account, amount: u64,
+           expires_at: Option<u64>, expected_allowance: Option<u64>, memo: Option<String>) -> Self {
+        let mut common = BasicTransactionFields::new(state, None, false);
+        common.memo = memo;
+        ICRC2ApproveTransaction {
+            common,
+            wallet,
+            ledger_id,
+            spender,
+            amount,
+            expires_at,
+            expected_allowance,
+            block_index: None,
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for ICRC2ApproveTransaction {
+    fn get_accepted_roles(&self) -> Vec<VaultRole> {
+        vec![VaultRole::Admin]
+    }
+
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        check_wallet_not_archived(&self.wallet)?;
+        let state = get_current_state();
+        let t = state.quorum.quorum;
+        self.set_threshold(t.clone());
+        Ok(t)
+    }
+
+    async fn execute(&mut self, state: VaultState) -> VaultState {
+        match approve(self.ledger_id, self.wallet.clone(), self.spender.clone(), self.amount,
+                      self.expected_allowance, self.expires_at).await {
+            Ok(block_index) => {
+                self.block_index = Some(block_index);
+                self.set_state(Executed);
+            }
+            Err(message) => {
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message });
+            }
+        }
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::ICRC2ApproveTransactionV(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct ICRC2ApproveTransactionRequest {
+    wallet: String,
+    ledger_id: Principal,
+    spender: Account,
+    amount: u64,
+    expires_at: Option<u64>,
+    expected_allowance: Option<u64>,
+    memo: Option<String>,
+}
+
+pub struct ICRC2ApproveTransactionBuilder {
+    request: ICRC2ApproveTransactionRequest,
+}
+
+impl ICRC2ApproveTransactionBuilder {
+    pub fn init(request: ICRC2ApproveTransactionRequest) -> Self {
+        return ICRC2ApproveTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for ICRC2ApproveTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = ICRC2ApproveTransaction::new(
+            state,
+            self.request.wallet.clone(),
+            self.request.ledger_id,
+            self.request.spender.clone(),
+            self.request.amount,
+            self.request.expires_at,
+            self.request.expected_allowance,
+            self.request.memo.clone(),
+        );
+        Box::new(trs)
+    }
+}
@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use candid::{CandidType, Principal};
+use icrc_ledger_types::icrc1::transfer::BlockIndex;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::{TransactionState, VaultRole};
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError;
+use crate::errors::VaultError::CanisterReject;
+use crate::impl_basic_for_transaction;
+use crate::state::{get_current_state, VaultState};
+use crate::transaction::allowance::allowance::approve;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::member::members::Account;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+
+impl_basic_for_transaction!(ICRC2RevokeTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct ICRC2RevokeTransaction {
+    common: BasicTransactionFields,
+    wallet: String,
+    ledger_id: Principal,
+    spender: Account,
+    pub block_index: Option<BlockIndex>,
+}
+
+impl ICRC2RevokeTransaction {
+    fn new(state: TransactionState, wallet: String, ledger_id: Principal, spender: Account, memo: Option<String>) -> Self {
+        let mut common = BasicTransactionFields::new(state, None, false);
+        common.memo = memo;
+        ICRC2RevokeTransaction {
+            common,
+            wallet,
+            ledger_id,
+            spender,
+            block_index: None,
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for ICRC2RevokeTransaction {
+    fn get_accepted_roles(&self) -> Vec<VaultRole> {
+        vec![VaultRole::Admin]
+    }
+
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        let state = get_current_state();
+        let t = state.quorum.quorum;
+        self.set_threshold(t.clone());
+        Ok(t)
+    }
+
+    async fn execute(&mut self, state: VaultState) -> VaultState {
+        match approve(self.ledger_id, self.wallet.clone(), self.spender.clone(), 0, None, None).await {
+            Ok(block_index) => {
+                self.block_index = Some(block_index);
+                self.set_state(Executed);
+            }
+            Err(message) => {
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message });
+            }
+        }
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::ICRC2RevokeTransactionV(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct ICRC2RevokeTransactionRequest {
+    wallet: String,
+    ledger_id: Principal,
+    spender: Account,
+    memo: Option<String>,
+}
+
+pub struct ICRC2RevokeTransactionBuilder {
+    request: ICRC2RevokeTransactionRequest,
+}
+
+impl ICRC2RevokeTransactionBuilder {
+    pub fn init(request: ICRC2RevokeTransactionRequest) -> Self {
+        return ICRC2RevokeTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for ICRC2RevokeTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = ICRC2RevokeTransaction::new(
+            state,
+            self.request.wallet.clone(),
+            self.request.ledger_id,
+            self.request.spender.clone(),
+            self.request.memo.clone(),
+        );
+        Box::new(trs)
+    }
+}
@@ -0,0 +1,3 @@
+pub mod allowance;
+pub mod icrc2_approve_transaction;
+pub mod icrc2_revoke_transaction;
@@ -2,15 +2,17 @@ use std::collections::HashSet;
 
 use candid::CandidType;
 use ic_cdk::api::time;
-use ic_cdk::trap;
+use ic_cdk::{id, trap};
 use serde::{Deserialize, Serialize};
 
 use crate::enums::TransactionState;
 use crate::errors::VaultError;
 use crate::transaction::transaction::ITransaction;
 use crate::transaction::transaction_approve_handler::Approve;
+use crate::transaction::transaction_comment_handler::Comment;
+use crate::transaction::transaction_metadata_handler::TransactionMetadata;
 use crate::transaction::transaction_service::get_id;
-use crate::util::caller_to_address;
+use crate::util::{caller_to_address, to_address};
 
 #[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
 pub struct BasicTransactionFields {
@@ -25,6 +27,8 @@ pub struct BasicTransactionFields {
     pub batch_uid: Option<String>,
     pub threshold: Option<u8>,
     pub error: Option<VaultError>,
+    pub comments: Option<Vec<Comment>>,
+    pub metadata: Option<TransactionMetadata>,
 }
 
 impl BasicTransactionFields {
@@ -41,6 +45,27 @@ impl BasicTransactionFields {
             batch_uid: batch_uid,
             threshold: None,
             error: None,
+            comments: None,
+            metadata: None,
+        }
+    }
+
+    //for transactions created by the vault itself, e.g. from the heartbeat where there is no caller
+    pub fn new_system(state: TransactionState) -> Self {
+        BasicTransactionFields {
+            id: get_id(),
+            is_vault_state: false,
+            approves: Default::default(),
+            state,
+            initiator: to_address(id()),
+            created_date: time(),
+            modified_date: time(),
+            memo: None,
+            batch_uid: None,
+            threshold: None,
+            error: None,
+            comments: None,
+            metadata: None,
         }
     }
 }
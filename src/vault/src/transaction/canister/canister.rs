@@ -0,0 +1,51 @@
+use candid::{CandidType, Principal};
+use ic_cdk::id;
+use ic_ledger_types::MAINNET_LEDGER_CANISTER_ID;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{get_ckbtc_minter_id, get_cketh_minter_id, get_evm_rpc_canister_id};
+use crate::enums::VaultRole;
+use crate::state::VaultState;
+use crate::transaction::transfer::top_up_transaction::CYCLE_MINTER_CANISTER_ID;
+
+//who may request and approve calls to a target canister, targets without an entry are admin only
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct CanisterPermission {
+    pub canister: Principal,
+    pub roles: Vec<VaultRole>,
+    //the vault quorum is used when not set
+    pub threshold: Option<u8>,
+    pub modified_date: u64,
+}
+
+pub fn get_canister_permission(canister: &Principal, state: &VaultState) -> Option<CanisterPermission> {
+    state.canister_permissions.iter()
+        .find(|p| p.canister.eq(canister))
+        .cloned()
+}
+
+//the vault holds funds on these or controls itself through them, arbitrary calls would bypass the policies
+pub fn is_protected_canister(canister: &Principal, state: &VaultState) -> bool {
+    let cmc = Principal::from_text(CYCLE_MINTER_CANISTER_ID).unwrap();
+    canister.eq(&Principal::management_canister())
+        || canister.eq(&id())
+        || canister.eq(&MAINNET_LEDGER_CANISTER_ID)
+        || canister.eq(&cmc)
+        || canister.eq(&get_ckbtc_minter_id())
+        || canister.eq(&get_cketh_minter_id())
+        || canister.eq(&get_evm_rpc_canister_id())
+        || state.icrc1_canisters.iter().any(|c| c.ledger.eq(canister) || c.index.as_ref().map_or(false, |i| i.eq(canister)))
+}
+
+//canisters the vault controls, operations on them go through ManagedCanisterOperationTransaction
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct ManagedCanister {
+    pub canister_id: Principal,
+    pub name: String,
+    pub created_date: u64,
+    pub modified_date: u64,
+}
+
+pub fn is_managed(canister: &Principal, state: &VaultState) -> bool {
+    state.managed_canisters.iter().any(|c| c.canister_id.eq(canister))
+}
@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use candid::{CandidType, Principal};
+use ic_cdk::api::call::call_raw;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::{Currency, TransactionState, VaultRole};
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError;
+use crate::errors::VaultError::{CallTargetNotAllowed, CanisterReject};
+use crate::impl_basic_for_transaction;
+use crate::state::{get_current_state, VaultState};
+use crate::transaction::address_book::address_book::Address;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::canister::canister::{get_canister_permission, is_protected_canister};
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+use crate::transaction::transfer::transfer_common::TransferCommon;
+
+impl_basic_for_transaction!(CanisterCallTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct CanisterCallTransaction {
+    common: BasicTransactionFields,
+    //the Cycles policy that approved the attached cycles
+    policy: Option<String>,
+    canister: Principal,
+    method: String,
+    //candid encoded argument
+    arg: Vec<u8>,
+    cycles: Option<u64>,
+    reply: Option<Vec<u8>>,
+}
+
+impl CanisterCallTransaction {
+    fn new(state: TransactionState, canister: Principal, method: String, arg: Vec<u8>, cycles: Option<u64>, memo: Option<String>) -> Self {
+        let mut common = BasicTransactionFields::new(state, None, false);
+        common.memo = memo;
+        CanisterCallTransaction {
+            common,
+            policy: None,
+            canister,
+            method,
+            arg,
+            cycles,
+            reply: None,
+        }
+    }
+}
+
+//attached cycles leave the vault balance the same way a cycles deposit does
+impl TransferCommon for CanisterCallTransaction {
+    fn get_wallet(&self) -> String {
+        String::new()
+    }
+
+    fn get_amount(&self) -> u64 {
+        self.cycles.unwrap_or(0)
+    }
+
+    fn get_currency(&self) -> Currency {
+        Currency::Cycles
+    }
+
+    fn set_policy(&mut self, x: Option<String>) {
+        self.policy = x;
+    }
+
+    fn is_paid_from_vault_cycles(&self) -> bool {
+        true
+    }
+
+    fn get_destination(&self) -> Option<Address> {
+        Some(Address::canister(self.canister))
+    }
+}
+
+#[async_trait]
+impl ITransaction for CanisterCallTransaction {
+    fn get_accepted_roles(&self) -> Vec<VaultRole> {
+        match get_canister_permission(&self.canister, &get_current_state()) {
+            None => { vec![VaultRole::Admin] }
+            Some(permission) => { permission.roles }
+        }
+    }
+
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        let state = get_current_state();
+        if is_protected_canister(&self.canister, &state) {
+            return Err(CallTargetNotAllowed);
+        }
+        let mut t = get_canister_permission(&self.canister, &state)
+            .and_then(|p| p.threshold)
+            .unwrap_or(state.quorum.quorum);
+        if self.get_amount() > 0 {
+            t = t.max(self.define_transfer_threshold()?);
+        }
+        self.set_threshold(t.clone());
+        Ok(t)
+    }
+
+    async fn execute(&mut self, state: VaultState) -> VaultState {
+        match call_raw(self.canister, &self.method, self.arg.clone(), self.cycles.unwrap_or(0)).await {
+            Ok(reply) => {
+                self.reply = Some(reply);
+                self.set_state(Executed);
+            }
+            Err((code, message)) => {
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message: format!("{:?}: {}", code, message) });
+            }
+        }
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::CanisterCallTransactionV(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct CanisterCallTransactionRequest {
+    canister: Principal,
+    method: String,
+    arg: Vec<u8>,
+    cycles: Option<u64>,
+    memo: Option<String>,
+}
+
+pub struct CanisterCallTransactionBuilder {
+    request: CanisterCallTransactionRequest,
+}
+
+impl CanisterCallTransactionBuilder {
+    pub fn init(request: CanisterCallTransactionRequest) -> Self {
+        return CanisterCallTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for CanisterCallTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = CanisterCallTransaction::new(
+            state,
+            self.request.canister.clone(),
+            self.request.method.clone(),
+            self.request.arg.clone(),
+            self.request.cycles.clone(),
+            self.request.memo.clone(),
+        );
+        Box::new(trs)
+    }
+}
@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use candid::{CandidType, Principal};
+use ic_cdk::api::time;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::{TransactionState, VaultRole};
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError::ThresholdDefineError;
+use crate::impl_basic_for_transaction;
+use crate::state::VaultState;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::canister::canister::CanisterPermission;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+
+impl_basic_for_transaction!(CanisterPermissionUpdateTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct CanisterPermissionUpdateTransaction {
+    common: BasicTransactionFields,
+    canister: Principal,
+    //empty roles remove the entry, so the target is admin only again
+    roles: Vec<VaultRole>,
+    threshold: Option<u8>,
+}
+
+impl CanisterPermissionUpdateTransaction {
+    fn new(state: TransactionState, batch_uid: Option<String>, canister: Principal, roles: Vec<VaultRole>, threshold: Option<u8>) -> Self {
+        CanisterPermissionUpdateTransaction {
+            common: BasicTransactionFields::new(state, batch_uid, true),
+            canister,
+            roles,
+            threshold,
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for CanisterPermissionUpdateTransaction {
+    async fn execute(&mut self, mut state: VaultState) -> VaultState {
+        state.canister_permissions.retain(|p| !p.canister.eq(&self.canister));
+        if self.roles.is_empty() {
+            self.set_state(Executed);
+            return state;
+        }
+        if let Some(threshold) = self.threshold {
+            let voting_members = state.members.iter()
+                .filter(|m| self.roles.contains(&m.role))
+                .count();
+            if threshold == 0 || voting_members < threshold as usize {
+                self.set_state(Failed);
+                self.common.error = Some(ThresholdDefineError { message: "Threshold is not reachable".to_string() });
+                return state;
+            }
+        }
+        state.canister_permissions.push(CanisterPermission {
+            canister: self.canister.clone(),
+            roles: self.roles.clone(),
+            threshold: self.threshold.clone(),
+            modified_date: time(),
+        });
+        self.set_state(Executed);
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::CanisterPermissionUpdateTransactionV(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct CanisterPermissionUpdateTransactionRequest {
+    canister: Principal,
+    roles: Vec<VaultRole>,
+    threshold: Option<u8>,
+    batch_uid: Option<String>,
+}
+
+pub struct CanisterPermissionUpdateTransactionBuilder {
+    request: CanisterPermissionUpdateTransactionRequest,
+}
+
+impl CanisterPermissionUpdateTransactionBuilder {
+    pub fn init(request: CanisterPermissionUpdateTransactionRequest) -> Self {
+        return CanisterPermissionUpdateTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for CanisterPermissionUpdateTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = CanisterPermissionUpdateTransaction::new(
+            state,
+            self.request.batch_uid.clone(),
+            self.request.canister.clone(),
+            self.request.roles.clone(),
+            self.request.threshold.clone(),
+        );
+        Box::new(trs)
+    }
+}
@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use candid::{CandidType, Principal};
+use ic_cdk::api::time;
+use ic_cdk::id;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::TransactionState;
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError;
+use crate::errors::VaultError::{CallTargetNotAllowed, CanisterAlreadyManaged};
+use crate::impl_basic_for_transaction;
+use crate::state::{get_current_state, VaultState};
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::canister::canister::{is_managed, ManagedCanister};
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+
+impl_basic_for_transaction!(ManagedCanisterAddTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct ManagedCanisterAddTransaction {
+    common: BasicTransactionFields,
+    canister_id: Principal,
+    name: String,
+}
+
+impl ManagedCanisterAddTransaction {
+    fn new(state: TransactionState, batch_uid: Option<String>, canister_id: Principal, name: String) -> Self {
+        ManagedCanisterAddTransaction {
+            common: BasicTransactionFields::new(state, batch_uid, true),
+            canister_id,
+            name,
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for ManagedCanisterAddTransaction {
+    //the vault upgrades itself through UpgradeTransaction only
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        if self.canister_id == id() {
+            return Err(CallTargetNotAllowed);
+        }
+        let t = get_current_state().quorum.quorum;
+        self.set_threshold(t.clone());
+        Ok(t)
+    }
+
+    async fn execute(&mut self, mut state: VaultState) -> VaultState {
+        if is_managed(&self.canister_id, &state) {
+            self.set_state(Failed);
+            self.common.error = Some(CanisterAlreadyManaged);
+            return state;
+        }
+        state.managed_canisters.push(ManagedCanister {
+            canister_id: self.canister_id.clone(),
+            name: self.name.clone(),
+            created_date: time(),
+            modified_date: time(),
+        });
+        self.set_state(Executed);
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::ManagedCanisterAddTransactionV(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct ManagedCanisterAddTransactionRequest {
+    canister_id: Principal,
+    name: String,
+    batch_uid: Option<String>,
+}
+
+pub struct ManagedCanisterAddTransactionBuilder {
+    request: ManagedCanisterAddTransactionRequest,
+}
+
+impl ManagedCanisterAddTransactionBuilder {
+    pub fn init(request: ManagedCanisterAddTransactionRequest) -> Self {
+        return ManagedCanisterAddTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for ManagedCanisterAddTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = ManagedCanisterAddTransaction::new(
+            state,
+            self.request.batch_uid.clone(),
+            self.request.canister_id.clone(),
+            self.request.name.clone(),
+        );
+        Box::new(trs)
+    }
+}
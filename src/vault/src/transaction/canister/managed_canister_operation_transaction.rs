@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use candid::{CandidType, Principal};
+use ic_cdk::{id, trap};
+use serde::{Deserialize, Serialize};
+
+use crate::canister_management_service::{CanisterOperation, CanisterSnapshot, pin_wasm_hash, run_operation};
+use crate::enums::{TransactionState, VaultRole};
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError;
+use crate::errors::VaultError::{CallTargetNotAllowed, CanisterNotManaged, CanisterReject};
+use crate::impl_basic_for_transaction;
+use crate::state::{get_current_state, VaultState};
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::canister::canister::is_managed;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+
+impl_basic_for_transaction!(ManagedCanisterOperationTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct ManagedCanisterOperationTransaction {
+    common: BasicTransactionFields,
+    canister_id: Principal,
+    operation: CanisterOperation,
+    //sha256 hex of the module to install, pinned when the operation is requested
+    wasm_hash: Option<String>,
+    snapshot: Option<CanisterSnapshot>,
+}
+
+impl ManagedCanisterOperationTransaction {
+    fn new(state: TransactionState, canister_id: Principal, operation: CanisterOperation, wasm_hash: Option<String>, memo: Option<String>) -> Self {
+        let mut common = BasicTransactionFields::new(state, None, false);
+        common.memo = memo;
+        ManagedCanisterOperationTransaction {
+            common,
+            canister_id,
+            operation,
+            wasm_hash,
+            snapshot: None,
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for ManagedCanisterOperationTransaction {
+    //operations on the same canister are executed in the order they were requested
+    fn get_block_predicate(&mut self, tr: &Box<dyn ITransaction>) -> bool {
+        if tr.get_id() >= self.get_id() {
+            return false;
+        }
+        if tr.get_common_ref().is_vault_state {
+            return true;
+        }
+        if let TransactionCandid::ManagedCanisterOperationTransactionV(operation) = tr.to_candid() {
+            return operation.canister_id == self.canister_id;
+        }
+        false
+    }
+
+    fn get_accepted_roles(&self) -> Vec<VaultRole> {
+        vec![VaultRole::Admin]
+    }
+
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        if self.canister_id == id() {
+            return Err(CallTargetNotAllowed);
+        }
+        let state = get_current_state();
+        if !is_managed(&self.canister_id, &state) {
+            return Err(CanisterNotManaged);
+        }
+        let t = state.quorum.quorum;
+        self.set_threshold(t.clone());
+        Ok(t)
+    }
+
+    async fn execute(&mut self, state: VaultState) -> VaultState {
+        match run_operation(self.canister_id, &self.operation, self.wasm_hash.clone()).await {
+            Ok((snapshot, wasm_hash)) => {
+                self.snapshot = snapshot;
+                self.wasm_hash = wasm_hash;
+                self.set_state(Executed);
+            }
+            Err(message) => {
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message });
+            }
+        }
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::ManagedCanisterOperationTransactionV(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct ManagedCanisterOperationTransactionRequest {
+    canister_id: Principal,
+    operation: CanisterOperation,
+    memo: Option<String>,
+}
+
+pub struct ManagedCanisterOperationTransactionBuilder {
+    request: ManagedCanisterOperationTransactionRequest,
+}
+
+impl ManagedCanisterOperationTransactionBuilder {
+    pub fn init(request: ManagedCanisterOperationTransactionRequest) -> Self {
+        return ManagedCanisterOperationTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for ManagedCanisterOperationTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let wasm_hash = match &self.request.operation {
+            CanisterOperation::InstallCode { wasm, .. } => {
+                Some(pin_wasm_hash(wasm).await.unwrap_or_else(|msg| trap(&msg)))
+            }
+            _ => { None }
+        };
+        let trs = ManagedCanisterOperationTransaction::new(
+            state,
+            self.request.canister_id.clone(),
+            self.request.operation.clone(),
+            wasm_hash,
+            self.request.memo.clone(),
+        );
+        Box::new(trs)
+    }
+}
@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use candid::{CandidType, Principal};
+use serde::{Deserialize, Serialize};
+
+use crate::enums::TransactionState;
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError::CanisterNotManaged;
+use crate::impl_basic_for_transaction;
+use crate::state::VaultState;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::canister::canister::is_managed;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+
+//only drops the canister from the registry, the vault stays its controller
+impl_basic_for_transaction!(ManagedCanisterRemoveTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct ManagedCanisterRemoveTransaction {
+    common: BasicTransactionFields,
+    canister_id: Principal,
+}
+
+impl ManagedCanisterRemoveTransaction {
+    fn new(state: TransactionState, batch_uid: Option<String>, canister_id: Principal) -> Self {
+        ManagedCanisterRemoveTransaction {
+            common: BasicTransactionFields::new(state, batch_uid, true),
+            canister_id,
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for ManagedCanisterRemoveTransaction {
+    async fn execute(&mut self, mut state: VaultState) -> VaultState {
+        if !is_managed(&self.canister_id, &state) {
+            self.set_state(Failed);
+            self.common.error = Some(CanisterNotManaged);
+            return state;
+        }
+        state.managed_canisters.retain(|c| !c.canister_id.eq(&self.canister_id));
+        self.set_state(Executed);
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::ManagedCanisterRemoveTransactionV(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct ManagedCanisterRemoveTransactionRequest {
+    canister_id: Principal,
+    batch_uid: Option<String>,
+}
+
+pub struct ManagedCanisterRemoveTransactionBuilder {
+    request: ManagedCanisterRemoveTransactionRequest,
+}
+
+impl ManagedCanisterRemoveTransactionBuilder {
+    pub fn init(request: ManagedCanisterRemoveTransactionRequest) -> Self {
+        return ManagedCanisterRemoveTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for ManagedCanisterRemoveTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = ManagedCanisterRemoveTransaction::new(
+            state,
+            self.request.batch_uid.clone(),
+            self.request.canister_id.clone(),
+        );
+        Box::new(trs)
+    }
+}
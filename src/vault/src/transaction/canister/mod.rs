@@ -0,0 +1,6 @@
+pub mod canister;
+pub mod canister_permission_transaction;
+pub mod canister_call_transaction;
+pub mod managed_canister_add_transaction;
+pub mod managed_canister_remove_transaction;
+pub mod managed_canister_operation_transaction;
@@ -2,6 +2,8 @@ pub mod transaction;
 pub mod transaction_service;
 pub mod transaction_request_handler;
 pub mod transaction_approve_handler;
+pub mod transaction_comment_handler;
+pub mod transaction_metadata_handler;
 pub mod transaction_builder;
 pub mod wallet;
 pub mod basic_transaction;
@@ -10,4 +12,9 @@ pub mod member;
 pub mod policy;
 pub mod transfer;
 pub mod upgrade;
-pub mod purge;
\ No newline at end of file
+pub mod purge;
+pub mod address_book;
+pub mod allowance;
+pub mod retry;
+pub mod withdrawal;
+pub mod canister;
\ No newline at end of file
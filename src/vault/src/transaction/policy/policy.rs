@@ -4,6 +4,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::enums::Currency;
 
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize, PartialEq, Eq)]
+pub enum DestinationScope {
+    Whitelisted,
+    NotWhitelisted,
+    //transfers to destinations outside the address book are rejected
+    DenyNotWhitelisted,
+}
+
 #[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
 pub struct Policy {
     pub uid: String,
@@ -11,13 +19,15 @@ pub struct Policy {
     pub member_threshold: u8,
     pub currency: Currency,
     pub wallets: Vec<String>,
+    pub destination: Option<DestinationScope>,
     pub created_date: u64,
     pub modified_date: u64,
 }
 
 
 impl Policy {
-    pub fn new(uid: String, currency: Currency, amount_threshold: u64, member_threshold: u8, wallets: Vec<String>) -> Self {
+    pub fn new(uid: String, currency: Currency, amount_threshold: u64, member_threshold: u8, wallets: Vec<String>,
+               destination: Option<DestinationScope>) -> Self {
         Policy {
             uid,
             amount_threshold,
@@ -26,6 +36,21 @@ impl Policy {
             modified_date: time(),
             created_date: time(),
             wallets,
+            destination,
+        }
+    }
+
+    //policy without destination scope is applied to any destination
+    pub fn is_applicable_to(&self, whitelisted: bool) -> bool {
+        match self.destination {
+            None => { true }
+            Some(DestinationScope::Whitelisted) => { whitelisted }
+            Some(DestinationScope::NotWhitelisted) => { !whitelisted }
+            Some(DestinationScope::DenyNotWhitelisted) => { !whitelisted }
         }
     }
-}
\ No newline at end of file
+
+    pub fn is_deny(&self) -> bool {
+        self.destination.eq(&Some(DestinationScope::DenyNotWhitelisted))
+    }
+}
@@ -4,12 +4,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::enums::{Currency, TransactionState};
 use crate::enums::TransactionState::{Executed, Failed};
-use crate::errors::VaultError::{ThresholdAlreadyExists, UIDAlreadyExists, WalletNotExists};
+use crate::errors::VaultError::{ICRC1CanisterNotExists, ThresholdAlreadyExists, UIDAlreadyExists, WalletNotExists};
 use crate::impl_basic_for_transaction;
 use crate::state::VaultState;
 use crate::transaction::basic_transaction::BasicTransaction;
 use crate::transaction::basic_transaction::BasicTransactionFields;
-use crate::transaction::policy::policy::Policy;
+use crate::transaction::policy::policy::{DestinationScope, Policy};
 use crate::transaction::transaction::{ITransaction, TransactionCandid};
 use crate::transaction::transaction_builder::TransactionBuilder;
 
@@ -22,11 +22,12 @@ pub struct PolicyCreateTransaction {
     amount_threshold: u64,
     member_threshold: u8,
     wallets: Vec<String>,
+    destination: Option<DestinationScope>,
 }
 
 impl PolicyCreateTransaction {
     fn new(state: TransactionState, batch_uid: Option<String>, uid: String, currency: Currency, amount_threshold: u64,
-           member_threshold: u8, wallets: Vec<String>, ) -> Self {
+           member_threshold: u8, wallets: Vec<String>, destination: Option<DestinationScope>) -> Self {
         PolicyCreateTransaction {
             common: BasicTransactionFields::new(state, batch_uid,  true),
             uid,
@@ -34,6 +35,7 @@ impl PolicyCreateTransaction {
             amount_threshold,
             member_threshold,
             wallets,
+            destination,
         }
     }
 }
@@ -58,7 +60,16 @@ impl ITransaction for PolicyCreateTransaction {
             }
         }
 
+        if let Currency::ICRC1(ledger) = self.currency {
+            if !state.icrc1_canisters.iter().any(|c| c.ledger.eq(&ledger)) {
+                self.set_state(Failed);
+                self.common.error = Some(ICRC1CanisterNotExists);
+                return state;
+            }
+        }
+
         match state.policies.iter()
+            .filter(|policy| policy.currency.eq(&self.currency))
             .filter(|policy| {
                 for w in policy.wallets.clone() {
                     if self.wallets.contains(&w) {
@@ -67,6 +78,7 @@ impl ITransaction for PolicyCreateTransaction {
                 }
                 return false;
             })
+            .filter(|policy| policy.destination.eq(&self.destination))
             .find(|policy| policy.amount_threshold.eq(&self.amount_threshold)) {
             None => {}
             Some(_) => {
@@ -77,7 +89,8 @@ impl ITransaction for PolicyCreateTransaction {
         }
 
         let p = Policy::new(self.uid.clone(), self.currency.clone(),
-                            self.amount_threshold, self.member_threshold, self.wallets.clone());
+                            self.amount_threshold, self.member_threshold, self.wallets.clone(),
+                            self.destination.clone());
         state.policies.push(p);
         self.set_state(Executed);
         state
@@ -96,6 +109,7 @@ pub struct PolicyCreateTransactionRequest {
     amount_threshold: u64,
     member_threshold: u8,
     wallets: Vec<String>,
+    destination: Option<DestinationScope>,
     batch_uid: Option<String>,
 }
 
@@ -121,6 +135,7 @@ impl TransactionBuilder for PolicyCreateTransactionBuilder {
             self.request.amount_threshold.clone(),
             self.request.member_threshold.clone(),
             self.request.wallets.clone(),
+            self.request.destination.clone(),
         );
         Box::new(trs)
     }
@@ -47,6 +47,8 @@ impl ITransaction for PolicyUpdateTransaction {
             Some(policy) => {
                 match state.policies.iter()
                     .filter(|l| l.uid != self.uid)
+                    .filter(|other_policy| other_policy.currency.eq(&policy.currency))
+                    .filter(|other_policy| other_policy.destination.eq(&policy.destination))
                     .filter(|other_policy| {
                         for w in policy.wallets.clone() {
                             if other_policy.wallets.contains(&w) {
@@ -0,0 +1,155 @@
+use async_trait::async_trait;
+use candid::CandidType;
+use ic_cdk::api::time;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::{TransactionState, VaultRole};
+use crate::enums::TransactionState::{Approved, Executed, Failed};
+use crate::errors::VaultError;
+use crate::errors::VaultError::{RetryWindowExpired, TransactionNotExists, TransactionNotRetryable};
+use crate::impl_basic_for_transaction;
+use crate::state::{get_current_state, VaultState};
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+use crate::transaction::transaction_service::{get_all_transactions, restore_transaction};
+use crate::transaction::transfer::transfer_common::TransferCommon;
+
+//the ledger deduplicates transfers for 24 hours and rejects older created_at_time,
+//the permitted clock drift is kept as a margin
+const LEDGER_DEDUP_WINDOW_NANOS: u64 = 24 * 60 * 60 * 1_000_000_000 - 60 * 1_000_000_000;
+
+impl_basic_for_transaction!(RetryTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct RetryTransaction {
+    common: BasicTransactionFields,
+    transaction_id: u64,
+}
+
+impl RetryTransaction {
+    fn new(state: TransactionState, transaction_id: u64, memo: Option<String>) -> Self {
+        let mut common = BasicTransactionFields::new(state, None, false);
+        common.memo = memo;
+        RetryTransaction {
+            common,
+            transaction_id,
+        }
+    }
+
+    //boxed transactions are not Send, so the retry runs on the concrete type from the candid
+    fn find_retryable(&self) -> Result<TransactionCandid, VaultError> {
+        match get_all_transactions().into_iter().find(|t| t.get_id() == self.transaction_id) {
+            None => Err(TransactionNotExists),
+            Some(t) if !t.get_state().eq(&Failed) || !t.is_retryable() => Err(TransactionNotRetryable),
+            //outside of the window a retry could not be told apart from a new transfer and might pay twice
+            Some(t) if is_dedup_window_expired(t.get_dedup_timestamp()) => Err(RetryWindowExpired),
+            Some(t) => Ok(t.to_candid())
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for RetryTransaction {
+    fn get_accepted_roles(&self) -> Vec<VaultRole> {
+        vec![VaultRole::Admin]
+    }
+
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        let state = get_current_state();
+        let t = state.quorum.quorum;
+        self.set_threshold(t.clone());
+        Ok(t)
+    }
+
+    async fn execute(&mut self, state: VaultState) -> VaultState {
+        let candid = match self.find_retryable() {
+            Ok(candid) => candid,
+            Err(error) => {
+                self.set_state(Failed);
+                self.common.error = Some(error);
+                return state;
+            }
+        };
+        let (result, state) = match candid {
+            TransactionCandid::TransferTransactionV(t) => execute_again(t, state).await,
+            TransactionCandid::TransferQuorumTransactionV(t) => execute_again(t, state).await,
+            TransactionCandid::TransferICRC1TransactionV(t) => execute_again(t, state).await,
+            TransactionCandid::TransferICRC1QuorumTransactionV(t) => execute_again(t, state).await,
+            TransactionCandid::TopUpTransactionV(t) => execute_again(t, state).await,
+            TransactionCandid::TopUpQuorumTransactionV(t) => execute_again(t, state).await,
+            _ => (Err(TransactionNotRetryable), state)
+        };
+        match result {
+            Ok(_) => {
+                self.set_state(Executed);
+            }
+            Err(error) => {
+                self.set_state(Failed);
+                self.common.error = Some(error);
+            }
+        }
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::RetryTransactionV(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct RetryTransactionRequest {
+    transaction_id: u64,
+    memo: Option<String>,
+}
+
+pub struct RetryTransactionBuilder {
+    request: RetryTransactionRequest,
+}
+
+impl RetryTransactionBuilder {
+    pub fn init(request: RetryTransactionRequest) -> Self {
+        return RetryTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for RetryTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let mut trs = RetryTransaction::new(
+            state,
+            self.request.transaction_id,
+            self.request.memo.clone(),
+        );
+        //expired retries are rejected up front instead of waiting for the approvals
+        if let Err(RetryWindowExpired) = trs.find_retryable() {
+            trs.set_state(Failed);
+            trs.common.error = Some(RetryWindowExpired);
+        }
+        Box::new(trs)
+    }
+}
+
+fn is_dedup_window_expired(created_at_time: Option<u64>) -> bool {
+    created_at_time.map_or(false, |t| t + LEDGER_DEDUP_WINDOW_NANOS <= time())
+}
+
+//the original created_at_time and memo are kept, so the ledger reports a transfer which already went through as a duplicate
+//the wallet may have been archived or the destination denied since the first attempt
+async fn execute_again<T: TransferCommon + Clone + 'static>(mut target: T, state: VaultState) -> (Result<(), VaultError>, VaultState) {
+    if let Err(error) = target.check_wallet_not_archived().and_then(|_| target.check_destination_not_denied()) {
+        return (Err(error), state);
+    }
+    target.set_state(Approved);
+    target.get_common_mut().error = None;
+    let state = target.execute(state).await;
+    let result = if target.get_state().eq(&Executed) {
+        Ok(())
+    } else {
+        Err(target.get_common_ref().error.clone()
+            .unwrap_or(TransactionNotRetryable))
+    };
+    restore_transaction(Box::new(target));
+    (result, state)
+}
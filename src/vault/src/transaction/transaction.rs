@@ -9,6 +9,12 @@ use crate::enums::TransactionState::{Approved, Blocked, Failed, Pending, Rejecte
 use crate::enums::VaultRole;
 use crate::errors::VaultError;
 use crate::state::{get_current_state, VaultState};
+use crate::transaction::address_book::address_book_add_transaction::AddressBookAddTransaction;
+use crate::transaction::address_book::address_book_remove_transaction::AddressBookRemoveTransaction;
+use crate::transaction::allowance::icrc2_approve_transaction::ICRC2ApproveTransaction;
+use crate::transaction::allowance::icrc2_revoke_transaction::ICRC2RevokeTransaction;
+use crate::transaction::retry::retry_transaction::RetryTransaction;
+use crate::transaction::wallet::wallet_archive_transaction::WalletArchiveTransaction;
 use crate::transaction::basic_transaction::BasicTransaction;
 use crate::transaction::member::member_create_transaction::MemberCreateTransaction;
 use crate::transaction::member::member_create_transaction_v2::MemberCreateTransactionV2;
@@ -25,6 +31,20 @@ use crate::transaction::transaction_service::is_blocked;
 use crate::transaction::transfer::top_up_quorum_transaction::TopUpQuorumTransaction;
 use crate::transaction::transfer::top_up_transaction::TopUpTransaction;
 use crate::transaction::transfer::transfer_icrc1_quorum_transaction::TransferICRC1QuorumTransaction;
+use crate::transaction::transfer::transfer_btc_transaction::TransferBTCTransaction;
+use crate::transaction::transfer::eth_transfer_transaction::EthTransferTransaction;
+use crate::transaction::withdrawal::withdrawal_transaction::WithdrawalTransaction;
+use crate::transaction::upgrade::rollback_transaction::VersionRollbackTransaction;
+use crate::transaction::vault::canister_settings_transaction::CanisterSettingsUpdateTransaction;
+use crate::transaction::transfer::auto_top_up_transaction::AutoTopUpTransaction;
+use crate::transaction::vault::auto_top_up_rule_transaction::AutoTopUpRuleUpdateTransaction;
+use crate::transaction::canister::managed_canister_operation_transaction::ManagedCanisterOperationTransaction;
+use crate::transaction::canister::managed_canister_remove_transaction::ManagedCanisterRemoveTransaction;
+use crate::transaction::canister::managed_canister_add_transaction::ManagedCanisterAddTransaction;
+use crate::transaction::transfer::deposit_cycles_transaction::DepositCyclesTransaction;
+use crate::transaction::canister::canister_call_transaction::CanisterCallTransaction;
+use crate::transaction::canister::canister_permission_transaction::CanisterPermissionUpdateTransaction;
+use crate::transaction::transfer::transfer_icrc1_transaction::TransferICRC1Transaction;
 use crate::transaction::transfer::transfer_quorum_transaction::TransferQuorumTransaction;
 use crate::transaction::transfer::transfer_transaction::TransferTransaction;
 use crate::transaction::upgrade::upgrade_transaction::VersionUpgradeTransaction;
@@ -93,6 +113,10 @@ pub trait ITransaction: BasicTransaction {
         if tr.get_common_ref().is_vault_state || match tr.to_candid() {
             TransactionCandid::TransferTransactionV(_) => { true }
             TransactionCandid::TopUpTransactionV(_) => { true }
+            TransactionCandid::TransferICRC1TransactionV(_) => { true }
+            TransactionCandid::TransferBTCTransactionV(_) => { true }
+            TransactionCandid::EthTransferTransactionV(_) => { true }
+            TransactionCandid::DepositCyclesTransactionV(_) => { true }
             _ => { false }
         } {
             return true;
@@ -120,6 +144,16 @@ pub trait ITransaction: BasicTransaction {
         self.define_state();
     }
 
+    //created_at_time the ledger deduplicates the transfer by
+    fn get_dedup_timestamp(&self) -> Option<u64> {
+        None
+    }
+
+    //only transfers which went through the ledger deduplication can be executed again
+    fn is_retryable(&self) -> bool {
+        self.get_dedup_timestamp().is_some()
+    }
+
     fn update_modified_date(&mut self) {
         self.get_common_mut().modified_date = ic_cdk::api::time();
     }
@@ -214,11 +248,31 @@ pub enum TransactionCandid {
     TransferTransactionV(TransferTransaction),
     TransferQuorumTransactionV(TransferQuorumTransaction),
     TransferICRC1QuorumTransactionV(TransferICRC1QuorumTransaction),
+    TransferICRC1TransactionV(TransferICRC1Transaction),
     TopUpTransactionV(TopUpTransaction),
     UpgradeTransactionV(VersionUpgradeTransaction),
     TopUpQuorumTransactionV(TopUpQuorumTransaction),
     ICRC1CanistersAddTransactionV(ICRC1CanistersAddTransaction),
-    ICRC1CanistersRemoveTransactionV(ICRC1CanistersRemoveTransaction)
+    ICRC1CanistersRemoveTransactionV(ICRC1CanistersRemoveTransaction),
+    AddressBookAddTransactionV(AddressBookAddTransaction),
+    AddressBookRemoveTransactionV(AddressBookRemoveTransaction),
+    ICRC2ApproveTransactionV(ICRC2ApproveTransaction),
+    ICRC2RevokeTransactionV(ICRC2RevokeTransaction),
+    RetryTransactionV(RetryTransaction),
+    WalletArchiveTransactionV(WalletArchiveTransaction),
+    TransferBTCTransactionV(TransferBTCTransaction),
+    EthTransferTransactionV(EthTransferTransaction),
+    WithdrawalTransactionV(WithdrawalTransaction),
+    CanisterPermissionUpdateTransactionV(CanisterPermissionUpdateTransaction),
+    CanisterCallTransactionV(CanisterCallTransaction),
+    DepositCyclesTransactionV(DepositCyclesTransaction),
+    ManagedCanisterAddTransactionV(ManagedCanisterAddTransaction),
+    ManagedCanisterRemoveTransactionV(ManagedCanisterRemoveTransaction),
+    ManagedCanisterOperationTransactionV(ManagedCanisterOperationTransaction),
+    AutoTopUpRuleUpdateTransactionV(AutoTopUpRuleUpdateTransaction),
+    AutoTopUpTransactionV(AutoTopUpTransaction),
+    CanisterSettingsUpdateTransactionV(CanisterSettingsUpdateTransaction),
+    VersionRollbackTransactionV(VersionRollbackTransaction),
 }
 
 pub trait Candid {
@@ -247,10 +301,30 @@ impl Candid for TransactionCandid {
             TransactionCandid::ControllersUpdateTransactionV(tr) => { Box::new(tr.to_owned()) }
             TransactionCandid::TransferQuorumTransactionV(tr) => { Box::new(tr.to_owned()) }
             TransactionCandid::TransferICRC1QuorumTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::TransferICRC1TransactionV(tr) => { Box::new(tr.to_owned()) }
             TransactionCandid::MemberCreateTransactionV2(tr) => { Box::new(tr.to_owned()) }
             TransactionCandid::MemberExtendICRC1AccountTransactionV(tr) => { Box::new(tr.to_owned()) }
             TransactionCandid::ICRC1CanistersAddTransactionV(tr) => { Box::new(tr.to_owned()) }
             TransactionCandid::ICRC1CanistersRemoveTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::AddressBookAddTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::AddressBookRemoveTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::ICRC2ApproveTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::ICRC2RevokeTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::RetryTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::WalletArchiveTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::TransferBTCTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::EthTransferTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::WithdrawalTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::CanisterPermissionUpdateTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::CanisterCallTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::DepositCyclesTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::ManagedCanisterAddTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::ManagedCanisterRemoveTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::ManagedCanisterOperationTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::AutoTopUpRuleUpdateTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::AutoTopUpTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::CanisterSettingsUpdateTransactionV(tr) => { Box::new(tr.to_owned()) }
+            TransactionCandid::VersionRollbackTransactionV(tr) => { Box::new(tr.to_owned()) }
         }
     }
 }
\ No newline at end of file
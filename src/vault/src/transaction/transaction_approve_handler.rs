@@ -19,6 +19,7 @@ use candid::CandidType;
 pub struct TransactionApproveRequest {
     pub transaction_id: u64,
     pub state: TransactionState,
+    pub reason: Option<String>,
 }
 
 
@@ -27,6 +28,7 @@ pub struct Approve {
     pub signer: String,
     pub created_date: u64,
     pub status: TransactionState,
+    pub reason: Option<String>,
 }
 
 impl PartialEq for Approve {
@@ -41,7 +43,7 @@ impl Hash for Approve {
     }
 }
 
-pub fn handle_approve(tr_id: u64, state: TransactionState) -> TransactionCandid {
+pub fn handle_approve(tr_id: u64, state: TransactionState, reason: Option<String>) -> TransactionCandid {
     let mut trs = get_by_id(tr_id);
 
     match trs.get_state() {
@@ -58,6 +60,7 @@ pub fn handle_approve(tr_id: u64, state: TransactionState) -> TransactionCandid
                 signer: caller_to_address(),
                 created_date: time(),
                 status: state,
+                reason,
             };
             trs.handle_approve(approve);
             trs.update_modified_date();
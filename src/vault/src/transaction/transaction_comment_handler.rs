@@ -0,0 +1,49 @@
+use ic_cdk::api::time;
+use ic_cdk::trap;
+use serde::{Deserialize, Serialize};
+use candid::CandidType;
+
+use crate::enums::TransactionState::{Blocked, Pending};
+use crate::transaction::transaction::TransactionCandid;
+use crate::transaction::transaction_service::{get_by_id, restore_transaction};
+use crate::util::caller_to_address;
+
+const MAX_COMMENT_LENGTH: usize = 1024;
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct TransactionCommentRequest {
+    pub transaction_id: u64,
+    pub text: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Comment {
+    pub author: String,
+    pub created_date: u64,
+    pub text: String,
+}
+
+pub fn handle_comment(tr_id: u64, text: String) -> TransactionCandid {
+    let mut trs = get_by_id(tr_id);
+
+    //the discussion is open while the transaction waits for approvals
+    match trs.get_state() {
+        Blocked | Pending => {}
+        _ => {
+            trap("Transaction is immutable")
+        }
+    }
+
+    let text = text.trim().to_string();
+    if text.is_empty() || text.len() > MAX_COMMENT_LENGTH {
+        trap(&format!("Comment should be 1 to {} bytes long", MAX_COMMENT_LENGTH))
+    }
+    let comment = Comment {
+        author: caller_to_address(),
+        created_date: time(),
+        text,
+    };
+    trs.get_common_mut().comments.get_or_insert_with(Vec::new).push(comment);
+    restore_transaction(trs.clone());
+    trs.to_candid()
+}
@@ -0,0 +1,73 @@
+use ic_cdk::trap;
+use serde::{Deserialize, Serialize};
+use candid::CandidType;
+
+use crate::enums::TransactionState::{Blocked, Pending};
+use crate::transaction::transaction::TransactionCandid;
+use crate::transaction::transaction_service::{get_by_id, restore_transaction};
+use crate::util::caller_to_address;
+
+const MAX_TAGS: usize = 16;
+const MAX_FIELD_LENGTH: usize = 256;
+
+#[derive(CandidType, Deserialize, Clone)]
+pub struct TransactionMetadataRequest {
+    pub transaction_id: u64,
+    pub metadata: TransactionMetadata,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct TransactionMetadata {
+    pub tags: Vec<String>,
+    pub invoice_id: Option<String>,
+    pub reference_id: Option<String>,
+    pub counterparty: Option<String>,
+    pub cost_center: Option<String>,
+}
+
+impl TransactionMetadata {
+    fn normalize(self) -> Result<Self, String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.tags {
+            let tag = tag.trim().to_string();
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        if tags.len() > MAX_TAGS {
+            return Err(format!("Up to {} tags are allowed", MAX_TAGS));
+        }
+        let fields = [self.invoice_id, self.reference_id, self.counterparty, self.cost_center]
+            .map(|f| f.map(|v| v.trim().to_string()).filter(|v| !v.is_empty()));
+        let too_long = tags.iter().chain(fields.iter().flatten())
+            .any(|v| v.len() > MAX_FIELD_LENGTH);
+        if too_long {
+            return Err(format!("Metadata values should be up to {} bytes long", MAX_FIELD_LENGTH));
+        }
+        let [invoice_id, reference_id, counterparty, cost_center] = fields;
+        Ok(TransactionMetadata {
+            tags,
+            invoice_id,
+            reference_id,
+            counterparty,
+            cost_center,
+        })
+    }
+}
+
+//the initiator keeps the bookkeeping up to date until the transaction leaves the voting
+pub fn handle_metadata_update(tr_id: u64, metadata: TransactionMetadata) -> TransactionCandid {
+    let mut trs = get_by_id(tr_id);
+
+    if ![Blocked, Pending].contains(trs.get_state()) {
+        trap("Transaction is immutable")
+    }
+    if !trs.get_common_ref().initiator.eq_ignore_ascii_case(&caller_to_address()) {
+        trap("Only the initiator can update the metadata")
+    }
+
+    let metadata = metadata.normalize().unwrap_or_else(|message| trap(&message));
+    trs.get_common_mut().metadata = Some(metadata);
+    restore_transaction(trs.clone());
+    trs.to_candid()
+}
@@ -2,8 +2,26 @@ use candid::CandidType;
 use ic_cdk::api::time;
 use serde::{Deserialize, Serialize};
 
-use crate::enums::TransactionState;
+use crate::enums::{TransactionState, VaultRole};
 use crate::security_service::verify_caller;
+use crate::transaction::address_book::address_book_add_transaction::{AddressBookAddTransactionBuilder, AddressBookAddTransactionRequest};
+use crate::transaction::address_book::address_book_remove_transaction::{AddressBookRemoveTransactionBuilder, AddressBookRemoveTransactionRequest};
+use crate::transaction::allowance::icrc2_approve_transaction::{ICRC2ApproveTransactionBuilder, ICRC2ApproveTransactionRequest};
+use crate::transaction::allowance::icrc2_revoke_transaction::{ICRC2RevokeTransactionBuilder, ICRC2RevokeTransactionRequest};
+use crate::transaction::retry::retry_transaction::{RetryTransactionBuilder, RetryTransactionRequest};
+use crate::transaction::wallet::wallet_archive_transaction::{WalletArchiveTransactionBuilder, WalletArchiveTransactionRequest};
+use crate::transaction::transfer::transfer_btc_transaction::{TransferBTCTransactionBuilder, TransferBTCTransactionRequest};
+use crate::transaction::transfer::eth_transfer_transaction::{EthTransferTransactionBuilder, EthTransferTransactionRequest};
+use crate::transaction::withdrawal::withdrawal_transaction::{WithdrawalTransactionBuilder, WithdrawalTransactionRequest};
+use crate::transaction::upgrade::rollback_transaction::{VersionRollbackTransactionBuilder, VersionRollbackTransactionRequest};
+use crate::transaction::vault::canister_settings_transaction::{CanisterSettingsUpdateTransactionBuilder, CanisterSettingsUpdateTransactionRequest};
+use crate::transaction::vault::auto_top_up_rule_transaction::{AutoTopUpRuleUpdateTransactionBuilder, AutoTopUpRuleUpdateTransactionRequest};
+use crate::transaction::canister::managed_canister_operation_transaction::{ManagedCanisterOperationTransactionBuilder, ManagedCanisterOperationTransactionRequest};
+use crate::transaction::canister::managed_canister_remove_transaction::{ManagedCanisterRemoveTransactionBuilder, ManagedCanisterRemoveTransactionRequest};
+use crate::transaction::canister::managed_canister_add_transaction::{ManagedCanisterAddTransactionBuilder, ManagedCanisterAddTransactionRequest};
+use crate::transaction::transfer::deposit_cycles_transaction::{DepositCyclesTransactionBuilder, DepositCyclesTransactionRequest};
+use crate::transaction::canister::canister_call_transaction::{CanisterCallTransactionBuilder, CanisterCallTransactionRequest};
+use crate::transaction::canister::canister_permission_transaction::{CanisterPermissionUpdateTransactionBuilder, CanisterPermissionUpdateTransactionRequest};
 use crate::transaction::member::member_create_transaction::{MemberCreateTransactionBuilder, MemberCreateTransactionRequest};
 use crate::transaction::member::member_create_transaction_v2::{MemberCreateTransactionBuilderV2, MemberCreateTransactionRequestV2};
 use crate::transaction::member::member_extend_account_transaction::{MemberExtendICRC1AccountBuilder, MemberExtendICRC1AccountRequest};
@@ -21,6 +39,7 @@ use crate::transaction::transaction_service::store_transaction;
 use crate::transaction::transfer::top_up_quorum_transaction::{TopUpQuorumTransaction, TopUpQuorumTransactionBuilder, TopUpQuorumTransactionRequest};
 use crate::transaction::transfer::top_up_transaction::{TopUpTransactionBuilder, TopUpTransactionRequest};
 use crate::transaction::transfer::transfer_icrc1_quorum_transaction::{TransferICRC1QuorumTransactionBuilder, TransferICRC1QuorumTransactionRequest};
+use crate::transaction::transfer::transfer_icrc1_transaction::{TransferICRC1TransactionBuilder, TransferICRC1TransactionRequest};
 use crate::transaction::transfer::transfer_quorum_transaction::{TransferQuorumTransactionBuilder, TransferQuorumTransactionRequest};
 use crate::transaction::transfer::transfer_transaction::{TransferTransactionBuilder, TransferTransactionRequest};
 use crate::transaction::upgrade::upgrade_transaction::{VersionUpgradeTransactionBuilder, VersionUpgradeTransactionRequest};
@@ -56,8 +75,27 @@ pub enum TransactionRequest {
     VersionUpgradeTransactionRequestV(VersionUpgradeTransactionRequest),
     ControllersUpdateTransactionRequestV(ControllersUpdateTransactionRequest),
     TransferICRC1QuorumTransactionRequestV(TransferICRC1QuorumTransactionRequest),
+    TransferICRC1TransactionRequestV(TransferICRC1TransactionRequest),
     ICRC1CanistersAddTransactionRequestV(ICRC1CanistersAddTransactionRequest),
     ICRC1CanistersRemoveTransactionRequestV(ICRC1CanistersRemoveTransactionRequest),
+    AddressBookAddTransactionRequestV(AddressBookAddTransactionRequest),
+    AddressBookRemoveTransactionRequestV(AddressBookRemoveTransactionRequest),
+    ICRC2ApproveTransactionRequestV(ICRC2ApproveTransactionRequest),
+    ICRC2RevokeTransactionRequestV(ICRC2RevokeTransactionRequest),
+    RetryTransactionRequestV(RetryTransactionRequest),
+    WalletArchiveTransactionRequestV(WalletArchiveTransactionRequest),
+    TransferBTCTransactionRequestV(TransferBTCTransactionRequest),
+    EthTransferTransactionRequestV(EthTransferTransactionRequest),
+    WithdrawalTransactionRequestV(WithdrawalTransactionRequest),
+    CanisterPermissionUpdateTransactionRequestV(CanisterPermissionUpdateTransactionRequest),
+    CanisterCallTransactionRequestV(CanisterCallTransactionRequest),
+    DepositCyclesTransactionRequestV(DepositCyclesTransactionRequest),
+    ManagedCanisterAddTransactionRequestV(ManagedCanisterAddTransactionRequest),
+    ManagedCanisterRemoveTransactionRequestV(ManagedCanisterRemoveTransactionRequest),
+    ManagedCanisterOperationTransactionRequestV(ManagedCanisterOperationTransactionRequest),
+    AutoTopUpRuleUpdateTransactionRequestV(AutoTopUpRuleUpdateTransactionRequest),
+    CanisterSettingsUpdateTransactionRequestV(CanisterSettingsUpdateTransactionRequest),
+    VersionRollbackTransactionRequestV(VersionRollbackTransactionRequest),
 }
 
 
@@ -112,6 +150,8 @@ pub async fn handle_transaction_request(trr: TransactionRequest) -> TransactionC
             TopUpQuorumTransactionBuilder::init(request).build().await
         }
         TransactionRequest::VersionUpgradeTransactionRequestV(request) => {
+            //the builder calls the repo, so the role is checked before it
+            verify_caller(vec![VaultRole::Admin]);
             VersionUpgradeTransactionBuilder::init(request).build().await
         }
         TransactionRequest::PurgeTransactionRequestV(request) => {
@@ -126,18 +166,79 @@ pub async fn handle_transaction_request(trr: TransactionRequest) -> TransactionC
         TransactionRequest::TransferICRC1QuorumTransactionRequestV(request) => {
             TransferICRC1QuorumTransactionBuilder::init(request).build().await
         }
+        TransactionRequest::TransferICRC1TransactionRequestV(request) => {
+            TransferICRC1TransactionBuilder::init(request).build().await
+        }
         TransactionRequest::ICRC1CanistersAddTransactionRequestV(request) => {
             ICRC1CanistersAddTransactionBuilder::init(request).build().await
         }
         TransactionRequest::ICRC1CanistersRemoveTransactionRequestV(request) => {
             ICRC1CanistersRemoveTransactionBuilder::init(request).build().await
         }
+        TransactionRequest::AddressBookAddTransactionRequestV(request) => {
+            AddressBookAddTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::AddressBookRemoveTransactionRequestV(request) => {
+            AddressBookRemoveTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::ICRC2ApproveTransactionRequestV(request) => {
+            ICRC2ApproveTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::ICRC2RevokeTransactionRequestV(request) => {
+            ICRC2RevokeTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::RetryTransactionRequestV(request) => {
+            RetryTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::WalletArchiveTransactionRequestV(request) => {
+            WalletArchiveTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::TransferBTCTransactionRequestV(request) => {
+            TransferBTCTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::EthTransferTransactionRequestV(request) => {
+            EthTransferTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::WithdrawalTransactionRequestV(request) => {
+            WithdrawalTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::CanisterPermissionUpdateTransactionRequestV(request) => {
+            CanisterPermissionUpdateTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::CanisterCallTransactionRequestV(request) => {
+            CanisterCallTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::DepositCyclesTransactionRequestV(request) => {
+            DepositCyclesTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::ManagedCanisterAddTransactionRequestV(request) => {
+            ManagedCanisterAddTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::ManagedCanisterRemoveTransactionRequestV(request) => {
+            ManagedCanisterRemoveTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::ManagedCanisterOperationTransactionRequestV(request) => {
+            //the builder pins the wasm hash, which can reach the repo
+            verify_caller(vec![VaultRole::Admin]);
+            ManagedCanisterOperationTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::AutoTopUpRuleUpdateTransactionRequestV(request) => {
+            AutoTopUpRuleUpdateTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::CanisterSettingsUpdateTransactionRequestV(request) => {
+            CanisterSettingsUpdateTransactionBuilder::init(request).build().await
+        }
+        TransactionRequest::VersionRollbackTransactionRequestV(request) => {
+            verify_caller(vec![VaultRole::Admin]);
+            VersionRollbackTransactionBuilder::init(request).build().await
+        }
     };
     verify_caller(trs.get_accepted_roles());
     let approve = Approve {
         signer: caller_to_address(),
         created_date: time(),
         status: TransactionState::Approved,
+        reason: None,
     };
     trs.handle_approve(approve);
     store_transaction(trs.clone());
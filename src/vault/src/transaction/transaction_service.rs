@@ -6,7 +6,11 @@ use nfid_certified::update_trusted_origins;
 
 use crate::config::{Conf, CONF};
 use crate::enums::TransactionState::{Approved, Executed, Failed, Purged, Rejected};
+use crate::bitcoin_service::{get_pending_spends, PendingSpend, restore_pending_spends};
+use crate::deposit_service::{DepositStorage, get_deposit_storage, restore_deposit_storage};
 use crate::execute;
+use crate::inbox_service::{Acknowledgement, get_acknowledgements, restore_acknowledgements};
+use crate::reconciliation_service::{get_discrepancies, restore_discrepancies, TransferDiscrepancy};
 use crate::state::{define_state, get_current_state, get_vault_state, restore_state};
 use crate::transaction::transaction::{Candid, ITransaction, TransactionCandid, TransactionIterator};
 
@@ -150,6 +154,10 @@ pub fn get_id() -> u64 {
 struct Memory {
     transactions: Vec<TransactionCandid>,
     config: Conf,
+    discrepancies: Option<Vec<TransferDiscrepancy>>,
+    deposits: Option<DepositStorage>,
+    acknowledgements: Option<Vec<Acknowledgement>>,
+    btc_pending_spends: Option<Vec<PendingSpend>>,
 }
 
 
@@ -167,6 +175,10 @@ pub fn stable_save() {
     let mem = Memory {
         config: conf,
         transactions: trs,
+        discrepancies: Some(get_discrepancies()),
+        deposits: Some(get_deposit_storage()),
+        acknowledgements: Some(get_acknowledgements()),
+        btc_pending_spends: Some(get_pending_spends()),
     };
     storage::stable_save((mem, )).unwrap();
 }
@@ -178,6 +190,10 @@ pub async fn stable_restore() {
         update_trusted_origins(mo.config.origins.clone());
         conf.replace(mo.config.clone())
     });
+    restore_discrepancies(mo.discrepancies.unwrap_or_default());
+    restore_deposit_storage(mo.deposits.unwrap_or_default());
+    restore_acknowledgements(mo.acknowledgements.unwrap_or_default());
+    restore_pending_spends(mo.btc_pending_spends.unwrap_or_default());
     let mut trs: Vec<Box<dyn ITransaction>> = mo.transactions
         .into_iter()
         .map(|x| x.to_transaction())
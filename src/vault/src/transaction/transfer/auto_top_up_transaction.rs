@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use candid::CandidType;
+use ic_cdk::api::time;
+use ic_cdk::id;
+use ic_ledger_types::BlockIndex;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::TransactionState;
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError;
+use crate::errors::VaultError::CanisterReject;
+use crate::impl_basic_for_transaction;
+use crate::state::VaultState;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_service::restore_transaction;
+use crate::transaction::transfer::top_up_transaction::{calculate_cycle_minter_id, MEMO_TOP_UP_CANISTER, notify_top_up};
+use crate::transfer_service::transfer;
+
+//created by the vault when the auto top-up rule triggers, the rule itself was approved by the quorum
+impl_basic_for_transaction!(AutoTopUpTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct AutoTopUpTransaction {
+    common: BasicTransactionFields,
+    pub wallet: String,
+    pub amount: u64,
+    //the balance which triggered the top-up
+    cycles_balance: u64,
+    pub block_index: Option<BlockIndex>,
+    created_at_time: Option<u64>,
+}
+
+impl AutoTopUpTransaction {
+    pub fn new(state: TransactionState, wallet: String, amount: u64, cycles_balance: u64) -> Self {
+        AutoTopUpTransaction {
+            common: BasicTransactionFields::new_system(state),
+            wallet,
+            amount,
+            cycles_balance,
+            block_index: None,
+            created_at_time: None,
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for AutoTopUpTransaction {
+    //a vault running out of cycles must not wait for other pending transactions
+    fn get_block_predicate(&mut self, _: &Box<dyn ITransaction>) -> bool {
+        false
+    }
+
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        self.set_threshold(0);
+        Ok(0)
+    }
+
+    async fn execute(&mut self, state: VaultState) -> VaultState {
+        let to = calculate_cycle_minter_id(id()).await;
+        if self.created_at_time.is_none() {
+            self.created_at_time = Some(time());
+            restore_transaction(self.clone_self());
+        }
+        match transfer(self.amount.clone(), to, self.wallet.clone(), Some(MEMO_TOP_UP_CANISTER), self.created_at_time).await {
+            Ok(block_index) => {
+                self.block_index = Some(block_index);
+                match notify_top_up(block_index, id()).await {
+                    Ok(_) => {
+                        self.set_state(Executed);
+                    }
+                    Err(message) => {
+                        self.set_state(Failed);
+                        self.common.error = Some(CanisterReject { message });
+                    }
+                }
+            }
+            Err(message) => {
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message });
+            }
+        }
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::AutoTopUpTransactionV(self.clone())
+    }
+}
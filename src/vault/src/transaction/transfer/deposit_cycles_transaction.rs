@@ -0,0 +1,128 @@
+use async_trait::async_trait;
+use candid::{CandidType, Principal};
+use ic_cdk::api::management_canister::main::{CanisterIdRecord, deposit_cycles};
+use ic_cdk::id;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::{Currency, TransactionState};
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError;
+use crate::errors::VaultError::CanisterReject;
+use crate::impl_basic_for_transaction;
+use crate::state::VaultState;
+use crate::transaction::address_book::address_book::Address;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+use crate::transaction::transfer::transfer_common::TransferCommon;
+
+//sends cycles from the vault balance, so it is approved by the Cycles policies
+impl_basic_for_transaction!(DepositCyclesTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct DepositCyclesTransaction {
+    common: BasicTransactionFields,
+    policy: Option<String>,
+    canister: Principal,
+    amount: u64,
+}
+
+impl DepositCyclesTransaction {
+    fn new(state: TransactionState, canister: Principal, amount: u64, memo: Option<String>) -> Self {
+        let mut common = BasicTransactionFields::new(state, None, false);
+        common.memo = memo;
+        DepositCyclesTransaction {
+            common,
+            policy: None,
+            canister,
+            amount,
+        }
+    }
+}
+
+impl TransferCommon for DepositCyclesTransaction {
+    //the vault cycle balance is not a wallet
+    fn get_wallet(&self) -> String {
+        String::new()
+    }
+
+    fn get_amount(&self) -> u64 {
+        self.amount.clone()
+    }
+
+    fn get_currency(&self) -> Currency {
+        Currency::Cycles
+    }
+
+    fn set_policy(&mut self, x: Option<String>) {
+        self.policy = x;
+    }
+
+    fn is_paid_from_vault_cycles(&self) -> bool {
+        true
+    }
+
+    fn get_destination(&self) -> Option<Address> {
+        Some(self.canister).filter(|c| !c.eq(&id())).map(Address::canister)
+    }
+}
+
+#[async_trait]
+impl ITransaction for DepositCyclesTransaction {
+    fn get_block_predicate(&mut self, tr: &Box<dyn ITransaction>) -> bool {
+        self.get_transfer_block_predicate(tr)
+    }
+
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        self.define_transfer_threshold()
+    }
+
+    async fn execute(&mut self, state: VaultState) -> VaultState {
+        match deposit_cycles(CanisterIdRecord { canister_id: self.canister }, self.amount as u128).await {
+            Ok(_) => {
+                self.set_state(Executed);
+            }
+            Err((code, message)) => {
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message: format!("{:?}: {}", code, message) });
+            }
+        }
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        let trs: DepositCyclesTransaction = self.clone();
+        TransactionCandid::DepositCyclesTransactionV(trs)
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct DepositCyclesTransactionRequest {
+    canister: Principal,
+    amount: u64,
+    memo: Option<String>,
+}
+
+pub struct DepositCyclesTransactionBuilder {
+    request: DepositCyclesTransactionRequest,
+}
+
+impl DepositCyclesTransactionBuilder {
+    pub fn init(request: DepositCyclesTransactionRequest) -> Self {
+        return DepositCyclesTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for DepositCyclesTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = DepositCyclesTransaction::new(
+            state,
+            self.request.canister.clone(),
+            self.request.amount.clone(),
+            self.request.memo.clone(),
+        );
+        Box::new(trs)
+    }
+}
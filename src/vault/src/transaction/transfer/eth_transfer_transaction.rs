@@ -0,0 +1,183 @@
+use std::convert::TryFrom;
+
+use async_trait::async_trait;
+use candid::{CandidType, Nat};
+use serde::{Deserialize, Serialize};
+
+use crate::enums::{Currency, Network, TransactionState};
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError;
+use crate::errors::VaultError::{CanisterReject, InvalidAddress, InvalidNetwork, WalletNotExists};
+use crate::eth_service::{decode_address, Eip1559Transaction, sign_eip1559};
+use crate::impl_basic_for_transaction;
+use crate::state::{get_current_state, VaultState};
+use crate::transaction::address_book::address_book::Address;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+use crate::transaction::transfer::transfer_common::TransferCommon;
+
+impl_basic_for_transaction!(EthTransferTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct EthTransferTransaction {
+    common: BasicTransactionFields,
+    policy: Option<String>,
+    pub wallet: String,
+    chain_id: u64,
+    nonce: u64,
+    max_priority_fee_per_gas: Nat,
+    max_fee_per_gas: Nat,
+    gas_limit: u64,
+    pub to: String,
+    //wei
+    pub value: Nat,
+    data: Option<Vec<u8>>,
+    //0x prefixed raw transaction, broadcast by the client
+    signed_transaction: Option<String>,
+    pub transaction_hash: Option<String>,
+}
+
+impl EthTransferTransaction {
+    fn new(state: TransactionState, request: EthTransferTransactionRequest) -> Self {
+        let mut common = BasicTransactionFields::new(state, None, false);
+        common.memo = request.memo;
+        EthTransferTransaction {
+            common,
+            policy: None,
+            wallet: request.wallet,
+            chain_id: request.chain_id,
+            nonce: request.nonce,
+            max_priority_fee_per_gas: request.max_priority_fee_per_gas,
+            max_fee_per_gas: request.max_fee_per_gas,
+            gas_limit: request.gas_limit,
+            to: request.to,
+            value: request.value,
+            data: request.data,
+            signed_transaction: None,
+            transaction_hash: None,
+        }
+    }
+
+    fn validate(&self) -> Result<(), VaultError> {
+        let state = get_current_state();
+        match state.wallets.iter().find(|w| w.uid.eq(&self.wallet)) {
+            None => { return Err(WalletNotExists); }
+            Some(wallet) => {
+                if wallet.network != Network::ETH {
+                    return Err(InvalidNetwork);
+                }
+            }
+        }
+        decode_address(&self.to).map_err(|_| InvalidAddress)?;
+        Ok(())
+    }
+}
+
+impl TransferCommon for EthTransferTransaction {
+    fn get_wallet(&self) -> String {
+        self.wallet.clone()
+    }
+
+    //policy thresholds are u64, larger wei amounts are above any of them
+    fn get_amount(&self) -> u64 {
+        u64::try_from(&self.value.0).unwrap_or(u64::MAX)
+    }
+
+    fn get_currency(&self) -> Currency {
+        Currency::ETH
+    }
+
+    fn set_policy(&mut self, x: Option<String>) {
+        self.policy = x;
+    }
+
+    fn get_destination(&self) -> Option<Address> {
+        Some(Address::ETH(self.to.clone()))
+    }
+}
+
+#[async_trait]
+impl ITransaction for EthTransferTransaction {
+    //signed transactions of a wallet are ordered by nonce, so they wait for each other
+    fn get_block_predicate(&mut self, tr: &Box<dyn ITransaction>) -> bool {
+        if tr.get_id() >= self.get_id() {
+            return false;
+        }
+        if tr.get_common_ref().is_vault_state {
+            return true;
+        }
+        if let TransactionCandid::EthTransferTransactionV(transfer) = tr.to_candid() {
+            return transfer.wallet == self.wallet;
+        }
+        false
+    }
+
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        self.validate()?;
+        self.define_transfer_threshold()
+    }
+
+    async fn execute(&mut self, state: VaultState) -> VaultState {
+        let tx = Eip1559Transaction {
+            chain_id: self.chain_id.clone(),
+            nonce: self.nonce.clone(),
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas.clone(),
+            max_fee_per_gas: self.max_fee_per_gas.clone(),
+            gas_limit: self.gas_limit.clone(),
+            to: self.to.clone(),
+            value: self.value.clone(),
+            data: self.data.clone().unwrap_or_default(),
+        };
+        match sign_eip1559(&self.wallet, &tx).await {
+            Ok(signed) => {
+                self.signed_transaction = Some(signed.raw);
+                self.transaction_hash = Some(signed.hash);
+                self.set_state(Executed);
+            }
+            Err(message) => {
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message });
+            }
+        }
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        let trs: EthTransferTransaction = self.clone();
+        TransactionCandid::EthTransferTransactionV(trs)
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct EthTransferTransactionRequest {
+    wallet: String,
+    chain_id: u64,
+    nonce: u64,
+    max_priority_fee_per_gas: Nat,
+    max_fee_per_gas: Nat,
+    gas_limit: u64,
+    to: String,
+    value: Nat,
+    data: Option<Vec<u8>>,
+    memo: Option<String>,
+}
+
+pub struct EthTransferTransactionBuilder {
+    request: EthTransferTransactionRequest,
+}
+
+impl EthTransferTransactionBuilder {
+    pub fn init(request: EthTransferTransactionRequest) -> Self {
+        return EthTransferTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for EthTransferTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = EthTransferTransaction::new(state, self.request.clone());
+        Box::new(trs)
+    }
+}
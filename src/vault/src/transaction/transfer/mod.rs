@@ -1,7 +1,13 @@
 pub mod transfer_transaction;
 pub mod top_up_transaction;
 pub mod transfer_quorum_transaction;
-mod transfer_executor_common;
-mod transfer_common;
+pub mod transfer_executor_common;
+pub mod transfer_common;
 pub mod transfer_icrc1_quorum_transaction;
 pub mod top_up_quorum_transaction;
+pub mod transfer_icrc1_transaction;
+pub mod transfer_btc_transaction;
+pub mod eth_transfer_transaction;
+pub mod deposit_cycles_transaction;
+pub mod auto_top_up_transaction;
+pub mod transfer_icrc1_executor_common;
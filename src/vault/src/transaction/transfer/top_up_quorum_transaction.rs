@@ -1,5 +1,6 @@
 use async_trait::async_trait;
-use candid::{CandidType};
+use candid::{CandidType, Principal};
+use ic_cdk::api::time;
 use ic_cdk::id;
 use ic_ledger_types::BlockIndex;
 use serde::{Deserialize, Serialize};
@@ -10,12 +11,14 @@ use crate::enums::TransactionState::{Executed, Failed};
 use crate::errors::VaultError;
 use crate::errors::VaultError::CanisterReject;
 use crate::state::{get_current_state, VaultState};
+use crate::transaction::address_book::address_book::Address;
 use crate::transaction::basic_transaction::BasicTransaction;
 use crate::transaction::basic_transaction::BasicTransactionFields;
 use crate::transaction::transaction::{ITransaction, TransactionCandid};
 use crate::transaction::transaction_builder::TransactionBuilder;
+use crate::transaction::transaction_service::restore_transaction;
 use crate::transaction::transfer::top_up_transaction::{calculate_cycle_minter_id, MEMO_TOP_UP_CANISTER, notify_top_up};
-use crate::transaction::transfer::transfer_common::TransferCommon;
+use crate::transaction::transfer::transfer_common::{TransferCommon, trap_if_not_icp};
 use crate::transfer_service::transfer;
 /*
 if you make any changes to this file
@@ -28,22 +31,31 @@ impl_basic_for_transaction!(TopUpQuorumTransaction);
 pub struct TopUpQuorumTransaction {
     common: BasicTransactionFields,
     wallet: String,
-    block_index: Option<BlockIndex>,
+    pub block_index: Option<BlockIndex>,
+    created_at_time: Option<u64>,
     amount: u64,
     currency: Currency,
+    //the vault itself when not set
+    canister: Option<Principal>,
 }
 
 impl TopUpQuorumTransaction {
     fn new(state: TransactionState, currency: Currency,
-           wallet: String, amount: u64, ) -> Self {
+           wallet: String, amount: u64, canister: Option<Principal>) -> Self {
         TopUpQuorumTransaction {
             common: BasicTransactionFields::new(state, None, false),
             wallet,
             currency,
             block_index: None,
+            created_at_time: None,
             amount,
+            canister,
         }
     }
+
+    fn get_target(&self) -> Principal {
+        self.canister.unwrap_or_else(id)
+    }
 }
 
 #[async_trait]
@@ -57,6 +69,8 @@ impl ITransaction for TopUpQuorumTransaction {
     }
 
     fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        self.check_wallet_not_archived()?;
+        self.check_destination_not_denied()?;
         let state = get_current_state();
         let threshold = state.quorum.quorum;
         self.set_threshold(threshold.clone());
@@ -64,13 +78,18 @@ impl ITransaction for TopUpQuorumTransaction {
     }
 
     async fn execute(&mut self, state: VaultState) -> VaultState {
-        let to = calculate_cycle_minter_id().await;
-        let transfer = transfer(self.amount.clone(), to, self.wallet.clone(), Some(MEMO_TOP_UP_CANISTER))
+        let to = calculate_cycle_minter_id(self.get_target()).await;
+        //the timestamp is stored before the ledger call so a re-execution is deduplicated by the ledger
+        if self.created_at_time.is_none() {
+            self.created_at_time = Some(time());
+            restore_transaction(self.clone_self());
+        }
+        let transfer = transfer(self.amount.clone(), to, self.wallet.clone(), Some(MEMO_TOP_UP_CANISTER), self.created_at_time)
             .await;
         match transfer {
             Ok(result) => {
                 self.block_index = Some(result);
-                let notify_res = notify_top_up(result.clone(), id()).await;
+                let notify_res = notify_top_up(result.clone(), self.get_target()).await;
                 match notify_res {
                     Ok(_) => {
                         self.set_state(Executed);
@@ -89,6 +108,10 @@ impl ITransaction for TopUpQuorumTransaction {
         state
     }
 
+    fn get_dedup_timestamp(&self) -> Option<u64> {
+        self.created_at_time
+    }
+
     fn to_candid(&self) -> TransactionCandid {
         let trs: TopUpQuorumTransaction = self.clone();
         TransactionCandid::TopUpQuorumTransactionV(trs)
@@ -100,6 +123,7 @@ pub struct TopUpQuorumTransactionRequest {
     wallet: String,
     amount: u64,
     currency: Currency,
+    canister: Option<Principal>,
 }
 
 pub struct TopUpQuorumTransactionBuilder {
@@ -116,11 +140,13 @@ impl TopUpQuorumTransactionBuilder {
 
 impl TransactionBuilder for TopUpQuorumTransactionBuilder {
     async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        trap_if_not_icp(&self.request.currency);
         let trs = TopUpQuorumTransaction::new(
             state,
             self.request.currency.clone(),
             self.request.wallet.clone(),
             self.request.amount.clone(),
+            self.request.canister.clone(),
         );
         Box::new(trs)
     }
@@ -136,10 +162,19 @@ impl TransferCommon for TopUpQuorumTransaction {
         self.amount.clone()
     }
 
+    fn get_currency(&self) -> Currency {
+        Currency::ICP
+    }
+
     fn set_policy(&mut self, _: Option<String>) {
         self.set_state(Failed);
         self.common.error = Some(VaultError::CouldNotDefinePolicy);
     }
+
+    //top-ups of other canisters leave the vault and are checked against the address book
+    fn get_destination(&self) -> Option<Address> {
+        self.canister.filter(|c| !c.eq(&id())).map(Address::canister)
+    }
 }
 
 
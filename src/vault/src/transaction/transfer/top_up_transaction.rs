@@ -1,23 +1,26 @@
 use async_trait::async_trait;
 use candid::{CandidType, Principal};
+use ic_cdk::api::time;
 use ic_cdk::id;
 use ic_ledger_types::{AccountIdentifier, BlockIndex, Subaccount};
 use serde::{Deserialize, Serialize};
 
-use crate::{impl_basic_for_transaction, impl_transfer_common_for_transaction};
+use crate::impl_basic_for_transaction;
 use crate::enums::{Currency, TransactionState};
 use crate::enums::TransactionState::{Executed, Failed};
 use crate::errors::VaultError;
 use crate::errors::VaultError::CanisterReject;
 use crate::state::VaultState;
+use crate::transaction::address_book::address_book::Address;
 use crate::transaction::basic_transaction::BasicTransaction;
 use crate::transaction::basic_transaction::BasicTransactionFields;
 use crate::transaction::transaction::{ITransaction, TransactionCandid};
 use crate::transaction::transaction_builder::TransactionBuilder;
-use crate::transaction::transfer::transfer_common::TransferCommon;
+use crate::transaction::transaction_service::restore_transaction;
+use crate::transaction::transfer::transfer_common::{TransferCommon, trap_if_not_icp};
 use crate::transfer_service::transfer;
 
-const CYCLE_MINTER_CANISTER_ID: &str = "rkp4c-7iaaa-aaaaa-aaaca-cai";
+pub const CYCLE_MINTER_CANISTER_ID: &str = "rkp4c-7iaaa-aaaaa-aaaca-cai";
 pub const MEMO_TOP_UP_CANISTER: u64 = 1347768404_u64;
 
 /*
@@ -27,29 +30,37 @@ if you make any changes to this file
 */
 
 impl_basic_for_transaction!(TopUpTransaction);
-impl_transfer_common_for_transaction!(TopUpTransaction);
 #[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
 pub struct TopUpTransaction {
     common: BasicTransactionFields,
     policy: Option<String>,
     wallet: String,
-    block_index: Option<BlockIndex>,
+    pub block_index: Option<BlockIndex>,
+    created_at_time: Option<u64>,
     amount: u64,
     currency: Currency,
+    //the vault itself when not set
+    canister: Option<Principal>,
 }
 
 impl TopUpTransaction {
     fn new(state: TransactionState, currency: Currency,
-           wallet: String, amount: u64, ) -> Self {
+           wallet: String, amount: u64, canister: Option<Principal>) -> Self {
         TopUpTransaction {
             common: BasicTransactionFields::new(state, None, false),
             wallet,
             policy: None,
             currency,
             block_index: None,
+            created_at_time: None,
             amount,
+            canister,
         }
     }
+
+    fn get_target(&self) -> Principal {
+        self.canister.unwrap_or_else(id)
+    }
 }
 
 #[async_trait]
@@ -63,13 +74,18 @@ impl ITransaction for TopUpTransaction {
     }
 
     async fn execute(&mut self, state: VaultState) -> VaultState {
-        let to = calculate_cycle_minter_id().await;
-        let transfer = transfer(self.amount.clone(), to, self.wallet.clone(), Some(MEMO_TOP_UP_CANISTER))
+        let to = calculate_cycle_minter_id(self.get_target()).await;
+        //the timestamp is stored before the ledger call so a re-execution is deduplicated by the ledger
+        if self.created_at_time.is_none() {
+            self.created_at_time = Some(time());
+            restore_transaction(self.clone_self());
+        }
+        let transfer = transfer(self.amount.clone(), to, self.wallet.clone(), Some(MEMO_TOP_UP_CANISTER), self.created_at_time)
             .await;
         match transfer {
             Ok(result) => {
                 self.block_index = Some(result);
-                let notify_res = notify_top_up(result.clone(), id()).await;
+                let notify_res = notify_top_up(result.clone(), self.get_target()).await;
                 match notify_res {
                     Ok(_) => {
                         self.set_state(Executed);
@@ -88,6 +104,10 @@ impl ITransaction for TopUpTransaction {
         state
     }
 
+    fn get_dedup_timestamp(&self) -> Option<u64> {
+        self.created_at_time
+    }
+
     fn to_candid(&self) -> TransactionCandid {
         let trs: TopUpTransaction = self.clone();
         TransactionCandid::TopUpTransactionV(trs)
@@ -99,6 +119,7 @@ pub struct TopUpTransactionRequest {
     wallet: String,
     amount: u64,
     currency: Currency,
+    canister: Option<Principal>,
 }
 
 pub struct TopUpTransactionBuilder {
@@ -115,17 +136,43 @@ impl TopUpTransactionBuilder {
 
 impl TransactionBuilder for TopUpTransactionBuilder {
     async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        trap_if_not_icp(&self.request.currency);
         let trs = TopUpTransaction::new(
             state,
             self.request.currency.clone(),
             self.request.wallet.clone(),
             self.request.amount.clone(),
+            self.request.canister.clone(),
         );
         Box::new(trs)
     }
 }
 
 
+impl TransferCommon for TopUpTransaction {
+    fn get_wallet(&self) -> String {
+        self.wallet.clone()
+    }
+
+    fn get_amount(&self) -> u64 {
+        self.amount.clone()
+    }
+
+    fn get_currency(&self) -> Currency {
+        Currency::ICP
+    }
+
+    fn set_policy(&mut self, x: Option<String>) {
+        self.policy = x;
+    }
+
+    //top-ups of other canisters leave the vault and are checked against the address book
+    fn get_destination(&self) -> Option<Address> {
+        self.canister.filter(|c| !c.eq(&id())).map(Address::canister)
+    }
+}
+
+
 #[derive(CandidType)]
 pub struct NotifyCanisterArgs {
     pub block_index: u64,
@@ -162,9 +209,8 @@ pub async fn notify_top_up(
     Ok(())
 }
 
-pub async fn calculate_cycle_minter_id() -> AccountIdentifier {
+pub async fn calculate_cycle_minter_id(canister: Principal) -> AccountIdentifier {
     let cycle_minter_id = Principal::from_text(CYCLE_MINTER_CANISTER_ID).unwrap();
-    let id = id();
-    let to_subaccount = Subaccount::from(id);
+    let to_subaccount = Subaccount::from(canister);
     return AccountIdentifier::new(&cycle_minter_id, &to_subaccount);
 }
@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+use crate::bitcoin_service::{address_to_script, send_btc};
+use crate::config::get_btc_network;
+use crate::enums::{Currency, Network, TransactionState};
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError;
+use crate::errors::VaultError::{CanisterReject, InvalidAddress, InvalidNetwork, WalletNotExists};
+use crate::impl_basic_for_transaction;
+use crate::state::{get_current_state, VaultState};
+use crate::transaction::address_book::address_book::Address;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+use crate::transaction::transfer::transfer_common::TransferCommon;
+
+impl_basic_for_transaction!(TransferBTCTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct TransferBTCTransaction {
+    common: BasicTransactionFields,
+    policy: Option<String>,
+    wallet: String,
+    //satoshi
+    amount: u64,
+    address: String,
+    pub txid: Option<String>,
+}
+
+impl TransferBTCTransaction {
+    fn new(state: TransactionState, address: String, wallet: String, amount: u64, memo: Option<String>) -> Self {
+        let mut common = BasicTransactionFields::new(state, None, false);
+        common.memo = memo;
+        TransferBTCTransaction {
+            common,
+            policy: None,
+            wallet,
+            amount,
+            address,
+            txid: None,
+        }
+    }
+}
+
+impl TransferCommon for TransferBTCTransaction {
+    fn get_wallet(&self) -> String {
+        self.wallet.clone()
+    }
+
+    fn get_amount(&self) -> u64 {
+        self.amount.clone()
+    }
+
+    fn get_currency(&self) -> Currency {
+        Currency::BTC
+    }
+
+    fn set_policy(&mut self, x: Option<String>) {
+        self.policy = x;
+    }
+
+    fn get_destination(&self) -> Option<Address> {
+        Some(Address::BTC(self.address.clone()))
+    }
+}
+
+impl TransferBTCTransaction {
+    fn validate(&self) -> Result<(), VaultError> {
+        match get_current_state().wallets.iter().find(|w| w.uid.eq(&self.wallet)) {
+            None => { return Err(WalletNotExists); }
+            Some(wallet) => {
+                if wallet.network != Network::BTC {
+                    return Err(InvalidNetwork);
+                }
+            }
+        }
+        address_to_script(&self.address, get_btc_network()).map_err(|_| InvalidAddress)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ITransaction for TransferBTCTransaction {
+    fn get_block_predicate(&mut self, tr: &Box<dyn ITransaction>) -> bool {
+        self.get_transfer_block_predicate(tr)
+    }
+
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        self.validate()?;
+        self.define_transfer_threshold()
+    }
+
+    async fn execute(&mut self, state: VaultState) -> VaultState {
+        match send_btc(&self.wallet, &self.address, self.amount).await {
+            Ok(txid) => {
+                self.txid = Some(txid);
+                self.set_state(Executed);
+            }
+            Err(message) => {
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message });
+            }
+        }
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        let trs: TransferBTCTransaction = self.clone();
+        TransactionCandid::TransferBTCTransactionV(trs)
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct TransferBTCTransactionRequest {
+    wallet: String,
+    amount: u64,
+    address: String,
+    memo: Option<String>,
+}
+
+pub struct TransferBTCTransactionBuilder {
+    request: TransferBTCTransactionRequest,
+}
+
+impl TransferBTCTransactionBuilder {
+    pub fn init(request: TransferBTCTransactionRequest) -> Self {
+        return TransferBTCTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for TransferBTCTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = TransferBTCTransaction::new(
+            state,
+            self.request.address.clone(),
+            self.request.wallet.clone(),
+            self.request.amount.clone(),
+            self.request.memo.clone(),
+        );
+        Box::new(trs)
+    }
+}
@@ -1,7 +1,13 @@
+use crate::enums::Currency;
+use ic_cdk::trap;
+
 use crate::errors::VaultError;
-use crate::errors::VaultError::CouldNotDefinePolicy;
+use crate::errors::VaultError::{CouldNotDefinePolicy, DestinationNotAllowed};
 use crate::state::get_current_state;
+use crate::transaction::address_book::address_book::{Address, is_whitelisted};
+use crate::transaction::policy::policy::Policy;
 use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::wallet::wallet::check_wallet_not_archived;
 
 /*
 if you make any changes to this file
@@ -9,20 +15,63 @@ if you make any changes to this file
  top_up_transaction.test.ts
 */
 
+//ICP transfers and top-ups are always paid from the ICP ledger, another currency would only pick another set of policies
+pub fn trap_if_not_icp(currency: &Currency) {
+    if !currency.eq(&Currency::ICP) {
+        trap("Only ICP can be transferred from the ICP ledger")
+    }
+}
+
 pub trait TransferCommon: ITransaction {
     fn get_wallet(&self) -> String;
     fn get_amount(&self) -> u64;
+    fn get_currency(&self) -> Currency;
     fn set_policy(&mut self, x: Option<String>);
+    //None is for transfers that never leave the vault (e.g. top-ups of the vault itself) and are treated as whitelisted
+    fn get_destination(&self) -> Option<Address>;
 
-    fn define_transfer_threshold(&mut self) -> Result<u8, VaultError> {
+    //true for the transactions paid from the vault cycle balance, which is not a wallet
+    fn is_paid_from_vault_cycles(&self) -> bool {
+        false
+    }
+
+    fn check_wallet_not_archived(&self) -> Result<(), VaultError> {
+        check_wallet_not_archived(&self.get_wallet())
+    }
+
+    //deny policies apply to the quorum transfers as well, the admin quorum does not override them
+    fn check_destination_not_denied(&self) -> Result<(), VaultError> {
+        if self.get_applicable_policies().iter().any(|p| p.is_deny()) {
+            return Err(DestinationNotAllowed);
+        }
+        Ok(())
+    }
+
+    fn get_applicable_policies(&self) -> Vec<Policy> {
         let state = get_current_state();
         let wallet = self.get_wallet();
         let amount = self.get_amount();
-        let policy = state.policies.iter()
-            .filter(|p| p.wallets.contains(&wallet))
+        let currency = self.get_currency();
+        let whitelisted = match self.get_destination() {
+            None => { true }
+            Some(address) => { is_whitelisted(&address, &state) }
+        };
+        state.policies.into_iter()
+            .filter(|p| p.currency.eq(&currency))
+            .filter(|p| self.is_paid_from_vault_cycles() || p.wallets.contains(&wallet))
+            .filter(|p| p.is_applicable_to(whitelisted))
             .filter(|p| p.amount_threshold < amount)
+            .collect()
+    }
+
+    fn define_transfer_threshold(&mut self) -> Result<u8, VaultError> {
+        self.check_wallet_not_archived()?;
+        self.check_destination_not_denied()?;
+        //on equal amount thresholds the policy with explicit destination scope wins
+        let policy = self.get_applicable_policies().into_iter()
             .max_by(|a, b| {
                 a.amount_threshold.cmp(&b.amount_threshold)
+                    .then(a.destination.is_some().cmp(&b.destination.is_some()))
             });
         match policy {
             None => {
@@ -56,6 +105,21 @@ pub trait TransferCommon: ITransaction {
         if let TransactionCandid::TopUpQuorumTransactionV(transfer) = tr.to_candid() {
             return transfer.get_wallet() == self.get_wallet();
         }
+        if let TransactionCandid::TransferICRC1TransactionV(transfer) = tr.to_candid() {
+            return TransferCommon::get_wallet(&transfer) == self.get_wallet();
+        }
+        if let TransactionCandid::TransferICRC1QuorumTransactionV(transfer) = tr.to_candid() {
+            return TransferCommon::get_wallet(&transfer) == self.get_wallet();
+        }
+        if let TransactionCandid::TransferBTCTransactionV(transfer) = tr.to_candid() {
+            return transfer.get_wallet() == self.get_wallet();
+        }
+        if let TransactionCandid::DepositCyclesTransactionV(transfer) = tr.to_candid() {
+            return transfer.get_wallet() == self.get_wallet();
+        }
+        if let TransactionCandid::EthTransferTransactionV(transfer) = tr.to_candid() {
+            return transfer.wallet == self.get_wallet();
+        }
         false
     }
 }
@@ -73,9 +137,17 @@ macro_rules! impl_transfer_common_for_transaction {
                self.amount.clone()
               }
 
+           fn get_currency(&self) -> Currency {
+               Currency::ICP
+              }
+
            fn set_policy(&mut self, x: Option<String>) {
                self.policy = x;
               }
+
+           fn get_destination(&self) -> Option<Address> {
+               Some(Address::AccountIdentifier(self.address.clone()))
+              }
         }
     };
 }
@@ -1,12 +1,14 @@
 use std::convert::TryFrom;
 
 use hex::FromHexError;
+use ic_cdk::api::time;
 use ic_ledger_types::{AccountIdentifier, BlockIndex};
 
 use crate::enums::TransactionState::{Executed, Failed};
 use crate::errors::VaultError::CanisterReject;
 use crate::state::VaultState;
 use crate::transaction::transaction::ITransaction;
+use crate::transaction::transaction_service::restore_transaction;
 use crate::transfer_service::transfer;
 use crate::util::to_array;
 
@@ -14,7 +16,10 @@ pub trait TransferExecutor: ITransaction {
     fn get_address(&self) -> String;
     fn get_amount(&self) -> u64;
     fn get_wallet(&self) -> String;
+    fn get_block_index(&self) -> Option<BlockIndex>;
     fn set_block_index(&mut self, bi: Option<BlockIndex>);
+    fn get_created_at_time(&self) -> Option<u64>;
+    fn set_created_at_time(&mut self, t: u64);
     async fn execute_transfer(&mut self, state: VaultState) -> VaultState {
         let to_decoded = match hex::decode(self.get_address().clone()) {
             Ok(x) => { x }
@@ -44,7 +49,13 @@ pub trait TransferExecutor: ITransaction {
                 return state;
             }
         };
-        let transfer = transfer(self.get_amount(), to, self.get_wallet().clone(), None)
+        //the timestamp is stored before the ledger call so a re-execution is deduplicated by the ledger
+        if self.get_created_at_time().is_none() {
+            self.set_created_at_time(time());
+            restore_transaction(self.clone_self());
+        }
+        let transfer = transfer(self.get_amount(), to, self.get_wallet().clone(),
+                                Some(self.get_id()), self.get_created_at_time())
             .await;
         match transfer {
             Ok(result) => {
@@ -69,10 +80,22 @@ macro_rules! impl_transfer_executor_for_transaction {
               self.address.clone()
               }
 
+            fn get_block_index(&self) -> Option<BlockIndex> {
+              self.block_index.clone()
+              }
+
             fn set_block_index(&mut self, bi: Option<BlockIndex>) {
               self.block_index=bi
               }
 
+            fn get_created_at_time(&self) -> Option<u64> {
+              self.created_at_time
+              }
+
+            fn set_created_at_time(&mut self, t: u64) {
+              self.created_at_time=Some(t)
+              }
+
            fn get_wallet(&self) -> String {
               self.wallet.clone()
               }
@@ -0,0 +1,116 @@
+use candid::Principal;
+use ic_cdk::api::time;
+use icrc_ledger_types::icrc1::account::Subaccount;
+use icrc_ledger_types::icrc1::transfer::{BlockIndex, Memo, TransferError};
+
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError::CanisterReject;
+use crate::state::VaultState;
+use crate::transaction::transaction::ITransaction;
+use crate::transaction::transaction_service::restore_transaction;
+use crate::transfer_service::{transfer_icrc1, TransferResult};
+
+pub trait TransferICRC1Executor: ITransaction {
+    fn get_ledger_id(&self) -> Principal;
+    fn get_to_principal(&self) -> Principal;
+    fn get_to_subaccount(&self) -> Option<Subaccount>;
+    fn get_icrc1_memo(&self) -> Option<Memo>;
+    fn get_amount(&self) -> u64;
+    fn get_wallet(&self) -> String;
+    fn get_block_index(&self) -> Option<BlockIndex>;
+    fn set_block_index(&mut self, bi: Option<BlockIndex>);
+    fn get_created_at_time(&self) -> Option<u64>;
+    fn set_created_at_time(&mut self, t: u64);
+    async fn execute_icrc1_transfer(&mut self, state: VaultState) -> VaultState {
+        //the timestamp is stored before the ledger call so a re-execution is deduplicated by the ledger
+        if self.get_created_at_time().is_none() {
+            self.set_created_at_time(time());
+            restore_transaction(self.clone_self());
+        }
+        let memo = self.get_icrc1_memo()
+            .unwrap_or_else(|| Memo::from(self.get_id()));
+        let transfer = transfer_icrc1(self.get_ledger_id(),
+                                      self.get_amount(),
+                                      self.get_to_principal(),
+                                      self.get_to_subaccount(),
+                                      self.get_wallet(),
+                                      Some(memo),
+                                      self.get_created_at_time())
+            .await;
+        match transfer {
+            Ok(result) => {
+                match result.0 {
+                    TransferResult::Ok(x) => {
+                        self.set_block_index(Some(x));
+                        self.set_state(Executed);
+                    }
+                    TransferResult::Err(TransferError::Duplicate { duplicate_of }) => {
+                        self.set_block_index(Some(duplicate_of));
+                        self.set_state(Executed);
+                    }
+                    TransferResult::Err(message) => {
+                        self.set_state(Failed);
+                        self.get_common_mut().error = Some(CanisterReject {
+                            message: message.to_string()
+                        });
+                    }
+                }
+            }
+            Err(message) => {
+                self.set_state(Failed);
+                self.get_common_mut().error = Some(CanisterReject {
+                    message: message.1,
+                });
+            }
+        }
+        state
+    }
+}
+
+
+#[macro_export]
+macro_rules! impl_transfer_icrc1_executor_for_transaction {
+    ($type:ty) => {
+        impl TransferICRC1Executor for $type {
+            fn get_ledger_id(&self) -> Principal {
+              self.ledger_id
+              }
+
+            fn get_to_principal(&self) -> Principal {
+              self.to_principal
+              }
+
+            fn get_to_subaccount(&self) -> Option<Subaccount> {
+              self.to_subaccount
+              }
+
+            fn get_icrc1_memo(&self) -> Option<Memo> {
+              self.icrc1_memo.clone()
+              }
+
+            fn get_block_index(&self) -> Option<BlockIndex> {
+              self.block_index.clone()
+              }
+
+            fn set_block_index(&mut self, bi: Option<BlockIndex>) {
+              self.block_index=bi
+              }
+
+            fn get_created_at_time(&self) -> Option<u64> {
+              self.created_at_time
+              }
+
+            fn set_created_at_time(&mut self, t: u64) {
+              self.created_at_time=Some(t)
+              }
+
+           fn get_wallet(&self) -> String {
+              self.wallet.clone()
+              }
+
+           fn get_amount(&self) -> u64 {
+               self.amount.clone()
+              }
+        }
+    };
+}
@@ -1,49 +1,57 @@
 use async_trait::async_trait;
 use candid::{CandidType, Principal};
-use icrc1::transfer::BlockIndex;
+use icrc1::transfer::{BlockIndex, Memo};
 use icrc_ledger_types::icrc1;
 use icrc_ledger_types::icrc1::account::Subaccount;
 use serde::{Deserialize, Serialize};
 
-use crate::enums::{TransactionState, VaultRole};
-use crate::enums::TransactionState::{Executed, Failed};
+use crate::{impl_basic_for_transaction, impl_transfer_icrc1_executor_for_transaction};
+use crate::enums::{Currency, TransactionState, VaultRole};
 use crate::errors::VaultError;
-use crate::errors::VaultError::CanisterReject;
-use crate::impl_basic_for_transaction;
 use crate::state::{get_current_state, VaultState};
+use crate::transaction::address_book::address_book::Address;
 use crate::transaction::basic_transaction::BasicTransaction;
 use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::member::members::Account;
 use crate::transaction::transaction::{ITransaction, TransactionCandid};
 use crate::transaction::transaction_builder::TransactionBuilder;
 use crate::transaction::transfer::transfer_common::TransferCommon;
-use crate::transfer_service::{transfer_icrc1, TransferResult};
+use crate::transaction::transfer::transfer_icrc1_executor_common::TransferICRC1Executor;
 
 impl_basic_for_transaction!(TransferICRC1QuorumTransaction);
+impl_transfer_icrc1_executor_for_transaction!(TransferICRC1QuorumTransaction);
 #[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
 pub struct TransferICRC1QuorumTransaction {
     common: BasicTransactionFields,
+    policy: Option<String>,
     wallet: String,
     block_index: Option<BlockIndex>,
+    created_at_time: Option<u64>,
     amount: u64,
     ledger_id: Principal,
     to_principal: Principal,
     to_subaccount: Option<Subaccount>,
+    icrc1_memo: Option<Memo>,
 }
 
 
 impl TransferICRC1QuorumTransaction {
     fn new(state: TransactionState, to_principal: Principal, to_subaccount: Option<Subaccount>,
-           wallet: String, amount: u64, ledger_id: Principal, vault_memo: Option<String>) -> Self {
+           wallet: String, amount: u64, ledger_id: Principal, vault_memo: Option<String>,
+           icrc1_memo: Option<Memo>) -> Self {
         let mut common = BasicTransactionFields::new(state, None, false);
         common.memo = vault_memo;
         TransferICRC1QuorumTransaction {
             common,
+            policy: None,
             wallet,
             to_principal,
             block_index: None,
+            created_at_time: None,
             amount,
             to_subaccount,
             ledger_id,
+            icrc1_memo,
         }
     }
 }
@@ -54,49 +62,33 @@ impl ITransaction for TransferICRC1QuorumTransaction {
         self.get_transfer_block_predicate(tr)
     }
 
+    //wallets without policies for the ledger keep using the vault quorum
     fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        self.check_wallet_not_archived()?;
+        self.check_destination_not_denied()?;
         let state = get_current_state();
+        let currency = self.get_currency();
+        if state.policies.iter()
+            .any(|p| p.currency.eq(&currency) && p.wallets.contains(&self.wallet)) {
+            return self.define_transfer_threshold();
+        }
         let t = state.quorum.quorum;
         self.set_threshold(t.clone());
         Ok(t)
     }
 
     async fn execute(&mut self, state: VaultState) -> VaultState {
-        let transfer = transfer_icrc1(self.ledger_id,
-                                      self.amount.clone(),
-                                      self.to_principal,
-                                      self.to_subaccount.clone(),
-                                      self.wallet.clone())
-            .await;
-        match transfer {
-            Ok(result) => {
-                match result.0 {
-                    TransferResult::Ok(x) => {
-                        self.block_index = Some(x);
-                        self.set_state(Executed);
-                    }
-                    TransferResult::Err(message) => {
-                        self.set_state(Failed);
-                        self.get_common_mut().error = Some(CanisterReject {
-                            message: message.to_string()
-                        });
-                    }
-                }
-            }
-            Err(message) => {
-                self.set_state(Failed);
-                self.get_common_mut().error = Some(CanisterReject {
-                    message: message.1,
-                });
-            }
-        }
-        state
+        self.execute_icrc1_transfer(state).await
     }
 
     fn get_accepted_roles(&self) -> Vec<VaultRole> {
         vec![VaultRole::Admin]
     }
 
+    fn get_dedup_timestamp(&self) -> Option<u64> {
+        self.created_at_time
+    }
+
     fn to_candid(&self) -> TransactionCandid {
         let trs: TransferICRC1QuorumTransaction = self.clone();
         TransactionCandid::TransferICRC1QuorumTransactionV(trs)
@@ -111,6 +103,7 @@ pub struct TransferICRC1QuorumTransactionRequest {
     to_principal: Principal,
     to_subaccount: Option<Subaccount>,
     memo: Option<String>,
+    icrc1_memo: Option<Memo>,
 }
 
 pub struct TransferICRC1QuorumTransactionBuilder {
@@ -135,6 +128,7 @@ impl TransactionBuilder for TransferICRC1QuorumTransactionBuilder {
             self.request.amount.clone(),
             self.request.ledger_id.clone(),
             self.request.memo.clone(),
+            self.request.icrc1_memo.clone(),
         );
         Box::new(trs)
     }
@@ -150,13 +144,18 @@ impl TransferCommon for TransferICRC1QuorumTransaction {
         self.amount.clone()
     }
 
-    fn set_policy(&mut self, _: Option<String>) {
-        self.set_state(Failed);
-        self.common.error = Some(VaultError::CouldNotDefinePolicy);
+    fn get_currency(&self) -> Currency {
+        Currency::ICRC1(self.ledger_id)
     }
-}
-
-
-
 
+    fn set_policy(&mut self, x: Option<String>) {
+        self.policy = x;
+    }
 
+    fn get_destination(&self) -> Option<Address> {
+        Some(Address::ICRC1(Account {
+            owner: self.to_principal,
+            subaccount: self.to_subaccount,
+        }))
+    }
+}
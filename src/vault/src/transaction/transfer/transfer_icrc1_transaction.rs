@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use candid::{CandidType, Principal};
+use icrc1::transfer::{BlockIndex, Memo};
+use icrc_ledger_types::icrc1;
+use icrc_ledger_types::icrc1::account::Subaccount;
+use serde::{Deserialize, Serialize};
+
+use crate::{impl_basic_for_transaction, impl_transfer_icrc1_executor_for_transaction};
+use crate::enums::{Currency, TransactionState};
+use crate::errors::VaultError;
+use crate::state::VaultState;
+use crate::transaction::address_book::address_book::Address;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::member::members::Account;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+use crate::transaction::transfer::transfer_common::TransferCommon;
+use crate::transaction::transfer::transfer_icrc1_executor_common::TransferICRC1Executor;
+
+impl_basic_for_transaction!(TransferICRC1Transaction);
+impl_transfer_icrc1_executor_for_transaction!(TransferICRC1Transaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct TransferICRC1Transaction {
+    common: BasicTransactionFields,
+    policy: Option<String>,
+    wallet: String,
+    block_index: Option<BlockIndex>,
+    created_at_time: Option<u64>,
+    amount: u64,
+    ledger_id: Principal,
+    to_principal: Principal,
+    to_subaccount: Option<Subaccount>,
+    icrc1_memo: Option<Memo>,
+}
+
+
+impl TransferICRC1Transaction {
+    fn new(state: TransactionState, to_principal: Principal, to_subaccount: Option<Subaccount>,
+           wallet: String, amount: u64, ledger_id: Principal, vault_memo: Option<String>,
+           icrc1_memo: Option<Memo>) -> Self {
+        let mut common = BasicTransactionFields::new(state, None, false);
+        common.memo = vault_memo;
+        TransferICRC1Transaction {
+            common,
+            policy: None,
+            wallet,
+            to_principal,
+            block_index: None,
+            created_at_time: None,
+            amount,
+            to_subaccount,
+            ledger_id,
+            icrc1_memo,
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for TransferICRC1Transaction {
+    fn get_block_predicate(&mut self, tr: &Box<dyn ITransaction>) -> bool {
+        self.get_transfer_block_predicate(tr)
+    }
+
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        self.define_transfer_threshold()
+    }
+
+    async fn execute(&mut self, state: VaultState) -> VaultState {
+        self.execute_icrc1_transfer(state).await
+    }
+
+    fn get_dedup_timestamp(&self) -> Option<u64> {
+        self.created_at_time
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        let trs: TransferICRC1Transaction = self.clone();
+        TransactionCandid::TransferICRC1TransactionV(trs)
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct TransferICRC1TransactionRequest {
+    wallet: String,
+    amount: u64,
+    ledger_id: Principal,
+    to_principal: Principal,
+    to_subaccount: Option<Subaccount>,
+    memo: Option<String>,
+    icrc1_memo: Option<Memo>,
+}
+
+pub struct TransferICRC1TransactionBuilder {
+    request: TransferICRC1TransactionRequest,
+}
+
+impl TransferICRC1TransactionBuilder {
+    pub fn init(request: TransferICRC1TransactionRequest) -> Self {
+        return TransferICRC1TransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for TransferICRC1TransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = TransferICRC1Transaction::new(
+            state,
+            self.request.to_principal.clone(),
+            self.request.to_subaccount.clone(),
+            self.request.wallet.clone(),
+            self.request.amount.clone(),
+            self.request.ledger_id.clone(),
+            self.request.memo.clone(),
+            self.request.icrc1_memo.clone(),
+        );
+        Box::new(trs)
+    }
+}
+
+
+impl TransferCommon for TransferICRC1Transaction {
+    fn get_wallet(&self) -> String {
+        self.wallet.clone()
+    }
+
+    fn get_amount(&self) -> u64 {
+        self.amount.clone()
+    }
+
+    fn get_currency(&self) -> Currency {
+        Currency::ICRC1(self.ledger_id)
+    }
+
+    fn set_policy(&mut self, x: Option<String>) {
+        self.policy = x;
+    }
+
+    fn get_destination(&self) -> Option<Address> {
+        Some(Address::ICRC1(Account {
+            owner: self.to_principal,
+            subaccount: self.to_subaccount,
+        }))
+    }
+}
@@ -8,12 +8,13 @@ use crate::enums::{Currency, TransactionState, VaultRole};
 use crate::enums::TransactionState::Failed;
 use crate::errors::VaultError;
 use crate::state::{get_current_state, VaultState};
+use crate::transaction::address_book::address_book::Address;
 use crate::transaction::basic_transaction::BasicTransaction;
 use crate::transaction::basic_transaction::BasicTransactionFields;
 use crate::transaction::transaction::{ITransaction, TransactionCandid};
 use crate::transaction::transaction_builder::TransactionBuilder;
 use crate::transaction::transfer::transfer_executor_common::TransferExecutor;
-use crate::transaction::transfer::transfer_common::TransferCommon;
+use crate::transaction::transfer::transfer_common::{TransferCommon, trap_if_not_icp};
 
 
 impl_basic_for_transaction!(TransferQuorumTransaction);
@@ -23,6 +24,7 @@ pub struct TransferQuorumTransaction {
     common: BasicTransactionFields,
     wallet: String,
     block_index: Option<BlockIndex>,
+    created_at_time: Option<u64>,
     amount: u64,
     currency: Currency,
     address: String,
@@ -39,6 +41,7 @@ impl TransferQuorumTransaction {
             wallet,
             currency,
             block_index: None,
+            created_at_time: None,
             amount,
             address,
         }
@@ -56,12 +59,18 @@ impl ITransaction for TransferQuorumTransaction {
     }
 
     fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        self.check_wallet_not_archived()?;
+        self.check_destination_not_denied()?;
         let state = get_current_state();
         let t = state.quorum.quorum;
         self.set_threshold(t.clone());
         Ok(t)
     }
 
+    fn get_dedup_timestamp(&self) -> Option<u64> {
+        self.created_at_time
+    }
+
     fn to_candid(&self) -> TransactionCandid {
         let trs: TransferQuorumTransaction = self.clone();
         TransactionCandid::TransferQuorumTransactionV(trs)
@@ -95,6 +104,7 @@ impl TransferQuorumTransactionBuilder {
 
 impl TransactionBuilder for TransferQuorumTransactionBuilder {
     async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        trap_if_not_icp(&self.request.currency);
         let trs = TransferQuorumTransaction::new(
             state,
             self.request.address.clone(),
@@ -117,10 +127,18 @@ impl TransferCommon for TransferQuorumTransaction {
         self.amount.clone()
     }
 
+    fn get_currency(&self) -> Currency {
+        Currency::ICP
+    }
+
     fn set_policy(&mut self, _: Option<String>) {
         self.set_state(Failed);
         self.common.error = Some(VaultError::CouldNotDefinePolicy);
     }
+
+    fn get_destination(&self) -> Option<Address> {
+        Some(Address::AccountIdentifier(self.address.clone()))
+    }
 }
 
 
@@ -7,12 +7,13 @@ use crate::{impl_basic_for_transaction, impl_transfer_executor_for_transaction,
 use crate::enums::{Currency, TransactionState};
 use crate::errors::VaultError;
 use crate::state::VaultState;
+use crate::transaction::address_book::address_book::Address;
 use crate::transaction::basic_transaction::BasicTransaction;
 use crate::transaction::basic_transaction::BasicTransactionFields;
 use crate::transaction::transaction::{ITransaction, TransactionCandid};
 use crate::transaction::transaction_builder::TransactionBuilder;
 use crate::transaction::transfer::transfer_executor_common::TransferExecutor;
-use crate::transaction::transfer::transfer_common::TransferCommon;
+use crate::transaction::transfer::transfer_common::{TransferCommon, trap_if_not_icp};
 
 impl_transfer_executor_for_transaction!(TransferTransaction);
 impl_basic_for_transaction!(TransferTransaction);
@@ -23,6 +24,7 @@ pub struct TransferTransaction {
     policy: Option<String>,
     wallet: String,
     block_index: Option<BlockIndex>,
+    created_at_time: Option<u64>,
     amount: u64,
     currency: Currency,
     address: String,
@@ -40,6 +42,7 @@ impl TransferTransaction {
             policy: None,
             currency,
             block_index: None,
+            created_at_time: None,
             amount,
             address,
         }
@@ -56,6 +59,10 @@ impl ITransaction for TransferTransaction {
         self.define_transfer_threshold()
     }
 
+    fn get_dedup_timestamp(&self) -> Option<u64> {
+        self.created_at_time
+    }
+
     fn to_candid(&self) -> TransactionCandid {
         let trs: TransferTransaction = self.clone();
         TransactionCandid::TransferTransactionV(trs)
@@ -89,6 +96,7 @@ impl TransferTransactionBuilder {
 
 impl TransactionBuilder for TransferTransactionBuilder {
     async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        trap_if_not_icp(&self.request.currency);
         let trs = TransferTransaction::new(
             state,
             self.request.address.clone(),
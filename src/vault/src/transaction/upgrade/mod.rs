@@ -1 +1,2 @@
-pub mod upgrade_transaction;
\ No newline at end of file
+pub mod upgrade_transaction;
+pub mod rollback_transaction;
\ No newline at end of file
@@ -0,0 +1,169 @@
+use async_trait::async_trait;
+use candid::CandidType;
+use ic_cdk::trap;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use crate::{impl_basic_for_transaction, VERSION};
+use crate::enums::{TransactionState, VaultRole};
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError;
+use crate::errors::VaultError::{CanisterReject, IncompatibleSchemaVersion};
+use crate::schema_const::SCHEMA_VERSION;
+use crate::state::{get_current_state, VaultState};
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+use crate::transaction::upgrade::upgrade_transaction::{get_repo_wasm, get_wasm_hash, install_vault_wasm};
+
+impl_basic_for_transaction!(VersionRollbackTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct VersionRollbackTransaction {
+    common: BasicTransactionFields,
+    version: String,
+    initial_version: String,
+    hash: Option<String>,
+    //schema the repo declares for the target version
+    schema_version: Option<u32>,
+}
+
+impl VersionRollbackTransaction {
+    fn new(state: TransactionState, version: String, hash: Option<String>, schema_version: Option<u32>) -> Self {
+        VersionRollbackTransaction {
+            common: BasicTransactionFields::new(state, None, true),
+            initial_version: VERSION.to_string(),
+            version,
+            hash,
+            schema_version,
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for VersionRollbackTransaction {
+    fn get_accepted_roles(&self) -> Vec<VaultRole> {
+        vec![VaultRole::Admin]
+    }
+
+    //a rollback needs every admin, the regular quorum is not enough
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        let t = get_current_state().members.iter()
+            .filter(|m| m.role.eq(&VaultRole::Admin))
+            .count() as u8;
+        self.set_threshold(t.clone());
+        Ok(t)
+    }
+
+    async fn execute(&mut self, state: VaultState) -> VaultState {
+        if self.get_state().eq(&Executed) {
+            return state;
+        }
+        let current_version = Version::parse(VERSION).unwrap();
+        let expected_version = match Version::parse(&self.version) {
+            Ok(x) => {
+                x
+            }
+            Err(msg) => {
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message: format!("Failed to parse semver!: {}", msg) });
+                return state;
+            }
+        };
+        //the older build finishes the transaction after it is installed
+        if expected_version == current_version {
+            self.set_state(Executed);
+            return state;
+        }
+        if expected_version > current_version {
+            self.set_state(Failed);
+            self.common.error = Some(CanisterReject { message: format!("{} is not older than {}", expected_version, current_version) });
+            return state;
+        }
+        let wasm = match get_repo_wasm(&expected_version).await {
+            Ok(x) => { x }
+            Err(message) => {
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message });
+                return state;
+            }
+        };
+        if wasm.schema_version != Some(SCHEMA_VERSION) {
+            self.set_state(Failed);
+            self.common.error = Some(IncompatibleSchemaVersion);
+            return state;
+        }
+        match install_vault_wasm(wasm, self.hash.clone()).await {
+            Ok(_) => {
+                self.set_state(Executed);
+            }
+            Err(message) => {
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message });
+            }
+        }
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::VersionRollbackTransactionV(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct VersionRollbackTransactionRequest {
+    version: String,
+    memo: Option<String>,
+}
+
+pub struct VersionRollbackTransactionBuilder {
+    request: VersionRollbackTransactionRequest,
+}
+
+impl VersionRollbackTransactionBuilder {
+    pub fn init(request: VersionRollbackTransactionRequest) -> Self {
+        return VersionRollbackTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for VersionRollbackTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, mut state: TransactionState) -> Box<dyn ITransaction> {
+        let current_version = Version::parse(VERSION).unwrap();
+        let expected_version = match Version::parse(&self.request.version) {
+            Ok(x) => {
+                x
+            }
+            Err(_) => {
+                trap("Failed to parse semver!");
+            }
+        };
+        let mut hash = None;
+        let mut schema_version = None;
+        let mut error = None;
+        if expected_version >= current_version {
+            state = Failed;
+        } else {
+            let wasm = get_repo_wasm(&expected_version).await
+                .unwrap_or_else(|msg| trap(&msg));
+            let module_hash = get_wasm_hash(&wasm.wasm_module);
+            if module_hash != wasm.hash {
+                trap(&format!("Hashes do not match {} {}", module_hash, wasm.hash));
+            }
+            hash = Some(module_hash);
+            schema_version = wasm.schema_version;
+            //builds without a declared schema are treated as incompatible
+            if schema_version != Some(SCHEMA_VERSION) {
+                state = Failed;
+                error = Some(IncompatibleSchemaVersion);
+            }
+        }
+        let mut trs = VersionRollbackTransaction::new(
+            state, self.request.version.clone(), hash, schema_version,
+        );
+        trs.common.memo = self.request.memo.clone();
+        trs.common.error = error;
+        Box::new(trs)
+    }
+}
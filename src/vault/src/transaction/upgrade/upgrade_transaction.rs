@@ -5,6 +5,7 @@ use ic_cdk::api::management_canister::main::{CanisterInstallMode, InstallCodeArg
 use ic_cdk::{id, trap};
 use semver::Version;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::{impl_basic_for_transaction, VERSION};
 use crate::config::get_repo_canister_id;
@@ -21,16 +22,32 @@ impl_basic_for_transaction!(VersionUpgradeTransaction);
 #[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
 pub struct VersionUpgradeTransaction {
     common: BasicTransactionFields,
-    version: String,
+    pub version: String,
     initial_version: String,
+    //sha256 of the module published in the repo, pinned when the transaction is requested
+    hash: Option<String>,
 }
 
 impl VersionUpgradeTransaction {
-    fn new(state: TransactionState, batch_uid: Option<String>, version: String) -> Self {
+    fn new(state: TransactionState, batch_uid: Option<String>, version: String, hash: Option<String>) -> Self {
         VersionUpgradeTransaction {
             common: BasicTransactionFields::new(state, batch_uid, true),
             initial_version: VERSION.to_string(),
             version,
+            hash,
+        }
+    }
+
+    //upgrade proposed by the vault itself when the repo publishes a newer version
+    pub fn new_system(version: String, hash: String, memo: Option<String>) -> Self {
+        let mut common = BasicTransactionFields::new_system(TransactionState::Blocked);
+        common.is_vault_state = true;
+        common.memo = memo;
+        VersionUpgradeTransaction {
+            common,
+            initial_version: VERSION.to_string(),
+            version,
+            hash: Some(hash),
         }
     }
 }
@@ -64,11 +81,15 @@ impl TransactionBuilder for VersionUpgradeTransactionBuilder {
                 trap("Failed to parse semver!");
             }
         };
+        let mut hash = None;
         if expected_version <= initial_version {
             state = Failed;
+        } else {
+            hash = Some(get_verified_hash(&expected_version).await
+                .unwrap_or_else(|msg| trap(&msg)));
         }
         let trs = VersionUpgradeTransaction::new(
-            state, None, self.request.version.clone(),
+            state, None, self.request.version.clone(), hash,
         );
         Box::new(trs)
     }
@@ -77,6 +98,10 @@ impl TransactionBuilder for VersionUpgradeTransactionBuilder {
 #[async_trait]
 impl ITransaction for VersionUpgradeTransaction {
     async fn execute(&mut self, state: VaultState) -> VaultState {
+        //replayed upgrades must not reinstall a version that was rolled back since
+        if self.get_state().eq(&Executed) {
+            return state;
+        }
         let initial_version = Version::parse(VERSION).unwrap();
         let expected_version =  match Version::parse(&self.version) {
             Ok(x) => {
@@ -92,7 +117,7 @@ impl ITransaction for VersionUpgradeTransaction {
             self.set_state(Executed);
             state
         } else {
-            match upgrade_self(expected_version.to_string()).await {
+            match upgrade_self(expected_version.to_string(), self.hash.clone()).await {
                 Ok(_) => {
                     self.set_state(Executed);
                 }
@@ -115,13 +140,15 @@ impl ITransaction for VersionUpgradeTransaction {
 #[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
 pub struct VaultWasm {
     #[serde(with = "serde_bytes")]
-    wasm_module: Vec<u8>,
-    version: String,
-    hash: String,
+    pub wasm_module: Vec<u8>,
+    pub version: String,
+    pub hash: String,
+    pub schema_version: Option<u32>,
 }
 
 
-async fn upgrade_self(version: String) -> Result<(), String> {
+//transactions requested before the hash was pinned are checked against the hash published in the repo
+async fn upgrade_self(version: String, expected_hash: Option<String>) -> Result<(), String> {
     let sem_ver = match Version::parse(&version) {
         Ok(x) => {
             x
@@ -133,20 +160,16 @@ async fn upgrade_self(version: String) -> Result<(), String> {
             ));
         }
     };
-    let (wasm, ): (VaultWasm, ) = match call::call(
-        get_repo_canister_id(),
-        "get_by_version",
-        (sem_ver.to_string(), ),
-    ).await {
-        Ok(x) => x,
-        Err((code, msg)) => {
-            ic_cdk::eprintln!("Error while getting wasm: [{:?}] {}", code, msg);
-            return Err(format!(
-                "An error happened during the get_by_version call: {}: {}",
-                code as u8, msg
-            ));
-        }
-    };
+    let wasm = get_repo_wasm(&sem_ver).await?;
+    install_vault_wasm(wasm, expected_hash).await
+}
+
+pub async fn install_vault_wasm(wasm: VaultWasm, expected_hash: Option<String>) -> Result<(), String> {
+    let module_hash = get_wasm_hash(&wasm.wasm_module);
+    let expected_hash = expected_hash.unwrap_or(wasm.hash);
+    if module_hash != expected_hash {
+        return Err(format!("Wasm hash mismatch: expected {}, got {}", expected_hash, module_hash));
+    }
     let arg = InstallCodeArgument {
         mode: CanisterInstallMode::Upgrade,
         canister_id: id(),
@@ -160,4 +183,36 @@ async fn upgrade_self(version: String) -> Result<(), String> {
         return Err(format!("Error while upgrading canister: [{:?}] {}", code, msg));
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+pub async fn get_repo_wasm(version: &Version) -> Result<VaultWasm, String> {
+    match call::call(
+        get_repo_canister_id(),
+        "get_by_version",
+        (version.to_string(), ),
+    ).await {
+        Ok((wasm, )) => Ok(wasm),
+        Err((code, msg)) => {
+            ic_cdk::eprintln!("Error while getting wasm: [{:?}] {}", code, msg);
+            Err(format!(
+                "An error happened during the get_by_version call: {}: {}",
+                code as u8, msg
+            ))
+        }
+    }
+}
+
+//hash of the repo module, checked against the hash the repo publishes for it
+pub async fn get_verified_hash(version: &Version) -> Result<String, String> {
+    let wasm = get_repo_wasm(version).await?;
+    let module_hash = get_wasm_hash(&wasm.wasm_module);
+    if module_hash != wasm.hash {
+        return Err(format!("Hashes do not match {} {}", module_hash, wasm.hash));
+    }
+    Ok(module_hash)
+}
+
+//the same format the repo uses for the published hash
+pub fn get_wasm_hash(wasm: &[u8]) -> String {
+    format!("0x{:x}", Sha256::digest(wasm))
+}
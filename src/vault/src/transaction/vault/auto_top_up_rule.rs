@@ -0,0 +1,12 @@
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+//tops up the vault from the wallet when the cycles balance drops below min_cycles, amounts are in e8s
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct AutoTopUpRule {
+    pub min_cycles: u64,
+    pub wallet: String,
+    pub amount: u64,
+    pub daily_cap: u64,
+    pub modified_date: u64,
+}
@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use candid::CandidType;
+use ic_cdk::api::time;
+use serde::{Deserialize, Serialize};
+
+use crate::enums::{Network, TransactionState};
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError::{InvalidNetwork, ThresholdDefineError, WalletNotExists};
+use crate::impl_basic_for_transaction;
+use crate::state::VaultState;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+use crate::transaction::vault::auto_top_up_rule::AutoTopUpRule;
+
+impl_basic_for_transaction!(AutoTopUpRuleUpdateTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct AutoTopUpRuleUpdateTransaction {
+    common: BasicTransactionFields,
+    //None disables the automatic top-up
+    rule: Option<AutoTopUpRuleRequest>,
+}
+
+impl AutoTopUpRuleUpdateTransaction {
+    fn new(state: TransactionState, batch_uid: Option<String>, rule: Option<AutoTopUpRuleRequest>) -> Self {
+        AutoTopUpRuleUpdateTransaction {
+            common: BasicTransactionFields::new(state, batch_uid, true),
+            rule,
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for AutoTopUpRuleUpdateTransaction {
+    async fn execute(&mut self, mut state: VaultState) -> VaultState {
+        let rule = match self.rule.clone() {
+            None => {
+                state.auto_top_up = None;
+                self.set_state(Executed);
+                return state;
+            }
+            Some(x) => { x }
+        };
+        match state.wallets.iter().find(|w| w.uid.eq(&rule.wallet)) {
+            None => {
+                self.set_state(Failed);
+                self.common.error = Some(WalletNotExists);
+                return state;
+            }
+            Some(wallet) => {
+                if wallet.network != Network::IC {
+                    self.set_state(Failed);
+                    self.common.error = Some(InvalidNetwork);
+                    return state;
+                }
+            }
+        }
+        if rule.amount == 0 || rule.amount > rule.daily_cap {
+            self.set_state(Failed);
+            self.common.error = Some(ThresholdDefineError { message: "Amount should be positive and within the daily cap".to_string() });
+            return state;
+        }
+        state.auto_top_up = Some(AutoTopUpRule {
+            min_cycles: rule.min_cycles,
+            wallet: rule.wallet,
+            amount: rule.amount,
+            daily_cap: rule.daily_cap,
+            modified_date: time(),
+        });
+        self.set_state(Executed);
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::AutoTopUpRuleUpdateTransactionV(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct AutoTopUpRuleRequest {
+    min_cycles: u64,
+    wallet: String,
+    amount: u64,
+    daily_cap: u64,
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct AutoTopUpRuleUpdateTransactionRequest {
+    rule: Option<AutoTopUpRuleRequest>,
+    batch_uid: Option<String>,
+}
+
+pub struct AutoTopUpRuleUpdateTransactionBuilder {
+    request: AutoTopUpRuleUpdateTransactionRequest,
+}
+
+impl AutoTopUpRuleUpdateTransactionBuilder {
+    pub fn init(request: AutoTopUpRuleUpdateTransactionRequest) -> Self {
+        return AutoTopUpRuleUpdateTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for AutoTopUpRuleUpdateTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = AutoTopUpRuleUpdateTransaction::new(
+            state,
+            self.request.batch_uid.clone(),
+            self.request.rule.clone(),
+        );
+        Box::new(trs)
+    }
+}
@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+use candid::{CandidType, Nat};
+use ic_cdk::id;
+use ic_cdk::api::management_canister::main::{CanisterSettings, update_settings, UpdateSettingsArgument};
+use serde::{Deserialize, Serialize};
+
+use crate::canister_status_service::refresh_canister_status;
+use crate::enums::{TransactionState, VaultRole};
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::enums::VaultRole::Admin;
+use crate::errors::VaultError;
+use crate::errors::VaultError::CanisterReject;
+use crate::impl_basic_for_transaction;
+use crate::state::{get_current_state, VaultState};
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+
+//controllers are changed with ControllersUpdateTransaction, unset settings are left as they are
+impl_basic_for_transaction!(CanisterSettingsUpdateTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct CanisterSettingsUpdateTransaction {
+    common: BasicTransactionFields,
+    compute_allocation: Option<Nat>,
+    memory_allocation: Option<Nat>,
+    freezing_threshold: Option<Nat>,
+    reserved_cycles_limit: Option<Nat>,
+}
+
+impl CanisterSettingsUpdateTransaction {
+    fn new(state: TransactionState, request: CanisterSettingsUpdateTransactionRequest) -> Self {
+        let mut common = BasicTransactionFields::new(state, None, false);
+        common.memo = request.memo;
+        CanisterSettingsUpdateTransaction {
+            common,
+            compute_allocation: request.compute_allocation,
+            memory_allocation: request.memory_allocation,
+            freezing_threshold: request.freezing_threshold,
+            reserved_cycles_limit: request.reserved_cycles_limit,
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for CanisterSettingsUpdateTransaction {
+    fn get_accepted_roles(&self) -> Vec<VaultRole> {
+        vec![Admin]
+    }
+
+    fn get_block_predicate(&mut self, _: &Box<dyn ITransaction>) -> bool {
+        false
+    }
+
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        let st = get_current_state();
+        let mut t = st.quorum.quorum;
+        let admins = st.members.iter()
+            .filter(|m| m.role.eq(&Admin))
+            .count() as u8;
+        if admins < t {
+            t = admins;
+        }
+        self.set_threshold(t.clone());
+        Ok(t)
+    }
+
+    async fn execute(&mut self, state: VaultState) -> VaultState {
+        let result = update_settings(UpdateSettingsArgument {
+            canister_id: id(),
+            settings: CanisterSettings {
+                controllers: None,
+                compute_allocation: self.compute_allocation.clone(),
+                memory_allocation: self.memory_allocation.clone(),
+                freezing_threshold: self.freezing_threshold.clone(),
+                reserved_cycles_limit: self.reserved_cycles_limit.clone(),
+            },
+        }).await;
+        match result {
+            Ok(_) => {
+                self.set_state(Executed);
+                let _ = refresh_canister_status().await;
+            }
+            Err((code, msg)) => {
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message: format!("{:?}: {}", code, msg) });
+            }
+        }
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        TransactionCandid::CanisterSettingsUpdateTransactionV(self.clone())
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct CanisterSettingsUpdateTransactionRequest {
+    compute_allocation: Option<Nat>,
+    memory_allocation: Option<Nat>,
+    freezing_threshold: Option<Nat>,
+    reserved_cycles_limit: Option<Nat>,
+    memo: Option<String>,
+}
+
+pub struct CanisterSettingsUpdateTransactionBuilder {
+    request: CanisterSettingsUpdateTransactionRequest,
+}
+
+impl CanisterSettingsUpdateTransactionBuilder {
+    pub fn init(request: CanisterSettingsUpdateTransactionRequest) -> Self {
+        return CanisterSettingsUpdateTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for CanisterSettingsUpdateTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = CanisterSettingsUpdateTransaction::new(state, self.request.clone());
+        Box::new(trs)
+    }
+}
@@ -3,4 +3,6 @@ pub mod quorum;
 pub mod vault_naming_transaction;
 pub mod controllers_transaction;
 pub mod add_icrc1_canisters_transaction;
-pub mod remove_icrc1_canisters_transaction;
\ No newline at end of file
+pub mod remove_icrc1_canisters_transaction;pub mod auto_top_up_rule;
+pub mod auto_top_up_rule_transaction;
+pub mod canister_settings_transaction;
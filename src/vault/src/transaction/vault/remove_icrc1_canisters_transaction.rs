@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
 
-use crate::enums::TransactionState;
+use crate::enums::{Currency, TransactionState};
 use crate::enums::TransactionState::Executed;
 use crate::errors::VaultError;
 use crate::impl_basic_for_transaction;
@@ -70,6 +70,7 @@ impl ITransaction for ICRC1CanistersRemoveTransaction {
 
     async fn execute(&mut self, mut state: VaultState) -> VaultState {
         state.icrc1_canisters.retain(|icrc1| icrc1.ledger != self.ledger_canister);
+        state.policies.retain(|p| p.currency != Currency::ICRC1(self.ledger_canister));
         self.set_state(Executed);
         state
     }
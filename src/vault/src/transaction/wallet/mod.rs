@@ -1,3 +1,4 @@
 pub mod wallet;
 pub mod wallet_create_transaction;
-pub mod wallet_update_name_transaction;
\ No newline at end of file
+pub mod wallet_update_name_transaction;
+pub mod wallet_archive_transaction;
\ No newline at end of file
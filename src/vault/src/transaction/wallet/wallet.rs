@@ -1,9 +1,17 @@
+use std::convert::TryInto;
+
 use candid::{CandidType};
 use ic_cdk::api::time;
+use ic_cdk::id;
+use ic_ledger_types::{AccountIdentifier, Subaccount as SubLegacy};
+use icrc_ledger_types::icrc1::account::Account;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::enums::{Network};
-use crate::state::VaultState;
+use crate::errors::VaultError;
+use crate::errors::VaultError::WalletArchived;
+use crate::state::{get_current_state, VaultState};
 
 #[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
 pub struct Wallet {
@@ -12,11 +20,20 @@ pub struct Wallet {
     pub network: Network,
     pub modified_date: u64,
     pub created_date: u64,
+    //receive addresses of the wallet subaccount, none for wallets created before the uid validation
+    pub account_identifier: Option<String>,
+    pub icrc1_account: Option<String>,
 }
 
 impl Wallet {
     pub fn new(uid: String, name: String, network: Network) -> Self {
+        //chain wallets get their addresses from the threshold ECDSA key instead
+        let subaccount = if network == Network::IC { to_subaccount(&uid) } else { None };
         Wallet {
+            account_identifier: subaccount
+                .map(|s| AccountIdentifier::new(&id(), &SubLegacy(s)).to_string()),
+            icrc1_account: subaccount
+                .map(|s| Account { owner: id(), subaccount: Some(s) }.to_string()),
             uid,
             name,
             network,
@@ -26,9 +43,30 @@ impl Wallet {
     }
 }
 
+//the wallet uid is the hex of the 32 bytes subaccount
+pub fn to_subaccount(uid: &String) -> Option<[u8; 32]> {
+    hex::decode(uid).ok()
+        .and_then(|bytes| bytes.try_into().ok())
+}
+
+//derived from the id of the creating transaction, so the replay of the state gives the same uid
+pub fn derive_wallet_uid(transaction_id: u64) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(id().as_slice());
+    hasher.update(transaction_id.to_be_bytes());
+    hex::encode(hasher.finalize())
+}
+
 pub fn restore_wallet(wallet: Wallet, mut state: VaultState) -> VaultState {
     state.wallets.retain(|existing| existing.uid != wallet.uid);
     state.wallets.push(wallet);
     state
 }
 
+//funds of archived wallets can not be moved or approved for spending
+pub fn check_wallet_not_archived(wallet_uid: &String) -> Result<(), VaultError> {
+    if get_current_state().archived_wallets.iter().any(|w| w.uid.eq(wallet_uid)) {
+        return Err(WalletArchived);
+    }
+    Ok(())
+}
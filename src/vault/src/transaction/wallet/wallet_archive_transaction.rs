@@ -0,0 +1,129 @@
+use async_trait::async_trait;
+use candid::{CandidType, Nat};
+use ic_cdk::api::time;
+use serde::{Deserialize, Serialize};
+
+use crate::bitcoin_service::get_btc_balance;
+use crate::enums::{Currency, Network, TransactionState};
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError::{CanisterReject, WalletNotEmpty, WalletNotExists};
+use crate::eth_service::get_eth_balance;
+use crate::impl_basic_for_transaction;
+use crate::state::VaultState;
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+use crate::transaction::wallet::wallet::Wallet;
+use crate::transfer_service::{get_icp_balance, get_icrc1_balance};
+
+impl_basic_for_transaction!(WalletArchiveTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct WalletArchiveTransaction {
+    common: BasicTransactionFields,
+    uid: String,
+    require_zero_balance: bool,
+}
+
+impl WalletArchiveTransaction {
+    fn new(state: TransactionState, batch_uid: Option<String>, uid: String, require_zero_balance: bool) -> Self {
+        WalletArchiveTransaction {
+            common: BasicTransactionFields::new(state, batch_uid, true),
+            uid,
+            require_zero_balance,
+        }
+    }
+
+    //chain wallets hold the native coin of their network only
+    async fn is_empty(&self, wallet: &Wallet, state: &VaultState) -> Result<bool, String> {
+        let balances = match wallet.network {
+            Network::IC => {
+                let mut balances = vec![get_icp_balance(self.uid.clone()).await?];
+                for icrc1 in state.icrc1_canisters.iter() {
+                    balances.push(get_icrc1_balance(icrc1.ledger, self.uid.clone()).await?);
+                }
+                balances
+            }
+            Network::BTC => { vec![Nat::from(get_btc_balance(&self.uid).await?)] }
+            Network::ETH => { vec![get_eth_balance(&self.uid).await?] }
+        };
+        Ok(balances.iter().all(|b| b.eq(&Nat::from(0u64))))
+    }
+}
+
+#[async_trait]
+impl ITransaction for WalletArchiveTransaction {
+    async fn execute(&mut self, mut state: VaultState) -> VaultState {
+        let mut wallet = match state.wallets.iter().find(|w| w.uid.eq(&self.uid)) {
+            None => {
+                self.set_state(Failed);
+                self.common.error = Some(WalletNotExists);
+                return state;
+            }
+            Some(w) => w.clone()
+        };
+        //the balance is checked once, the state replay keeps the archived wallet
+        if self.require_zero_balance && !self.get_state().eq(&Executed) {
+            match self.is_empty(&wallet, &state).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.set_state(Failed);
+                    self.common.error = Some(WalletNotEmpty);
+                    return state;
+                }
+                Err(message) => {
+                    self.set_state(Failed);
+                    self.common.error = Some(CanisterReject { message });
+                    return state;
+                }
+            }
+        }
+        //policies left without wallets are removed with the wallet
+        for policy in state.policies.iter_mut() {
+            policy.wallets.retain(|w| !w.eq(&self.uid));
+        }
+        state.policies.retain(|p| !p.wallets.is_empty() || p.currency.eq(&Currency::Cycles));
+        if state.auto_top_up.as_ref().map_or(false, |r| r.wallet.eq(&self.uid)) {
+            state.auto_top_up = None;
+        }
+        state.wallets.retain(|w| !w.uid.eq(&self.uid));
+        wallet.modified_date = time();
+        state.archived_wallets.push(wallet);
+        self.set_state(Executed);
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        let trs: WalletArchiveTransaction = self.clone();
+        TransactionCandid::WalletArchiveTransactionV(trs)
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Deserialize, Serialize)]
+pub struct WalletArchiveTransactionRequest {
+    pub uid: String,
+    pub require_zero_balance: bool,
+    pub batch_uid: Option<String>,
+}
+
+pub struct WalletArchiveTransactionBuilder {
+    request: WalletArchiveTransactionRequest,
+}
+
+impl WalletArchiveTransactionBuilder {
+    pub fn init(request: WalletArchiveTransactionRequest) -> Self {
+        return WalletArchiveTransactionBuilder {
+            request,
+        };
+    }
+}
+
+impl TransactionBuilder for WalletArchiveTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = WalletArchiveTransaction::new(state,
+                                                self.request.batch_uid.clone(),
+                                                self.request.uid.clone(),
+                                                self.request.require_zero_balance);
+        Box::new(trs)
+    }
+}
@@ -4,14 +4,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::enums::{Network, TransactionState};
 use crate::enums::TransactionState::{Executed, Failed};
-use crate::errors::VaultError::UIDAlreadyExists;
+use crate::errors::VaultError::{InvalidUID, UIDAlreadyExists};
 use crate::impl_basic_for_transaction;
 use crate::state::VaultState;
 use crate::transaction::basic_transaction::BasicTransaction;
 use crate::transaction::basic_transaction::BasicTransactionFields;
 use crate::transaction::transaction::{ITransaction, TransactionCandid};
 use crate::transaction::transaction_builder::TransactionBuilder;
-use crate::transaction::wallet::wallet::Wallet;
+use crate::transaction::wallet::wallet::{derive_wallet_uid, to_subaccount, Wallet};
 
 impl_basic_for_transaction!(WalletCreateTransaction);
 #[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
@@ -24,8 +24,11 @@ pub struct WalletCreateTransaction {
 
 impl WalletCreateTransaction {
     fn new(state: TransactionState, batch_uid: Option<String>, uid: String, name: String, network: Network) -> Self {
+        let common = BasicTransactionFields::new(state, batch_uid, true);
+        //an empty uid is replaced with a derived one
+        let uid = if uid.is_empty() { derive_wallet_uid(common.id) } else { uid };
         WalletCreateTransaction {
-            common: BasicTransactionFields::new(state, batch_uid,  true),
+            common,
             uid,
             name,
             network,
@@ -36,7 +39,13 @@ impl WalletCreateTransaction {
 #[async_trait]
 impl ITransaction for WalletCreateTransaction {
     async fn execute(&mut self, mut state: VaultState) -> VaultState {
-        if state.wallets.iter().find(|p| p.uid.eq(&self.uid)).is_some() {
+        //wallets created before the validation are kept on the state replay
+        if !self.get_state().eq(&Executed) && to_subaccount(&self.uid).is_none() {
+            self.set_state(Failed);
+            self.common.error = Some(InvalidUID);
+            return state;
+        }
+        if state.wallets.iter().chain(state.archived_wallets.iter()).find(|p| p.uid.eq(&self.uid)).is_some() {
             self.set_state(Failed);
             self.common.error = Some(UIDAlreadyExists);
             return state;
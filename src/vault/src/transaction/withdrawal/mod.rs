@@ -0,0 +1 @@
+pub mod withdrawal_transaction;
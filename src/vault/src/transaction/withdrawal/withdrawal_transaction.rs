@@ -0,0 +1,158 @@
+use async_trait::async_trait;
+use candid::{CandidType, Nat, Principal};
+use serde::{Deserialize, Serialize};
+
+use crate::enums::{TransactionState, VaultRole};
+use crate::enums::TransactionState::{Executed, Failed};
+use crate::errors::VaultError;
+use crate::errors::VaultError::{CanisterReject, ICRC1CanisterNotExists, WalletNotExists};
+use crate::impl_basic_for_transaction;
+use crate::minter_service::{approve_minter, get_withdrawal_status, revoke_minter, withdraw, WithdrawalKind, WithdrawalStatus};
+use crate::state::{get_current_state, VaultState};
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::basic_transaction::BasicTransactionFields;
+use crate::transaction::transaction::{ITransaction, TransactionCandid};
+use crate::transaction::transaction_builder::TransactionBuilder;
+use crate::transaction::wallet::wallet::check_wallet_not_archived;
+
+impl_basic_for_transaction!(WithdrawalTransaction);
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct WithdrawalTransaction {
+    common: BasicTransactionFields,
+    kind: WithdrawalKind,
+    pub wallet: String,
+    //ledger and minter are resolved from the config by the kind when the transaction is requested
+    pub ledger: Principal,
+    pub minter: Principal,
+    pub amount: Nat,
+    //native BTC or ETH address
+    pub address: String,
+    pub approve_block_index: Option<Nat>,
+    pub withdrawal_id: Option<u64>,
+    status: Option<WithdrawalStatus>,
+}
+
+impl WithdrawalTransaction {
+    fn new(state: TransactionState, request: WithdrawalTransactionRequest) -> Self {
+        let mut common = BasicTransactionFields::new(state, None, false);
+        common.memo = request.memo;
+        WithdrawalTransaction {
+            common,
+            minter: request.kind.get_minter(),
+            ledger: request.kind.get_ledger(),
+            kind: request.kind,
+            wallet: request.wallet,
+            amount: request.amount,
+            address: request.address,
+            approve_block_index: None,
+            withdrawal_id: None,
+            status: None,
+        }
+    }
+
+    pub fn is_finalized(&self) -> bool {
+        self.status.as_ref().map_or(false, |s| s.is_final())
+    }
+
+    //returns true when the minter reported a new status
+    pub async fn refresh_status(&mut self) -> bool {
+        let withdrawal_id = match self.withdrawal_id {
+            None => { return false; }
+            Some(x) => { x }
+        };
+        match get_withdrawal_status(&self.kind, self.minter, withdrawal_id).await {
+            Ok(status) => {
+                if self.status.as_ref().map_or(true, |s| !s.eq(&status)) {
+                    self.status = Some(status);
+                    return true;
+                }
+                false
+            }
+            Err(_) => { false }
+        }
+    }
+}
+
+#[async_trait]
+impl ITransaction for WithdrawalTransaction {
+    fn get_accepted_roles(&self) -> Vec<VaultRole> {
+        vec![VaultRole::Admin]
+    }
+
+    fn define_threshold(&mut self) -> Result<u8, VaultError> {
+        check_wallet_not_archived(&self.wallet)?;
+        let state = get_current_state();
+        if !state.wallets.iter().any(|w| w.uid.eq(&self.wallet)) {
+            return Err(WalletNotExists);
+        }
+        if !state.icrc1_canisters.iter().any(|c| c.ledger.eq(&self.ledger)) {
+            return Err(ICRC1CanisterNotExists);
+        }
+        let t = state.quorum.quorum;
+        self.set_threshold(t.clone());
+        Ok(t)
+    }
+
+    async fn execute(&mut self, state: VaultState) -> VaultState {
+        match approve_minter(self.ledger, self.minter, self.wallet.clone(), self.amount.clone()).await {
+            Ok(block_index) => {
+                self.approve_block_index = Some(block_index);
+            }
+            Err(message) => {
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message });
+                return state;
+            }
+        }
+        match withdraw(&self.kind, self.minter, self.wallet.clone(), self.amount.clone(), self.address.clone()).await {
+            Ok(withdrawal_id) => {
+                self.withdrawal_id = Some(withdrawal_id);
+                self.status = Some(WithdrawalStatus::Pending);
+                self.set_state(Executed);
+            }
+            Err(message) => {
+                //the minter did not burn anything, the allowance must not outlive the withdrawal
+                let message = match revoke_minter(self.ledger, self.minter, self.wallet.clone()).await {
+                    Ok(_) => { message }
+                    Err(revoke_error) => { format!("{}; failed to reset the minter allowance: {}", message, revoke_error) }
+                };
+                self.set_state(Failed);
+                self.common.error = Some(CanisterReject { message });
+            }
+        }
+        state
+    }
+
+    fn to_candid(&self) -> TransactionCandid {
+        let trs: WithdrawalTransaction = self.clone();
+        TransactionCandid::WithdrawalTransactionV(trs)
+    }
+}
+
+#[derive(Clone, Debug, CandidType, Serialize, Deserialize)]
+pub struct WithdrawalTransactionRequest {
+    kind: WithdrawalKind,
+    wallet: String,
+    amount: Nat,
+    address: String,
+    memo: Option<String>,
+}
+
+pub struct WithdrawalTransactionBuilder {
+    request: WithdrawalTransactionRequest,
+}
+
+impl WithdrawalTransactionBuilder {
+    pub fn init(request: WithdrawalTransactionRequest) -> Self {
+        return WithdrawalTransactionBuilder {
+            request
+        };
+    }
+}
+
+impl TransactionBuilder for WithdrawalTransactionBuilder {
+    async fn build_dyn_transaction(&mut self, state: TransactionState) -> Box<dyn ITransaction> {
+        let trs = WithdrawalTransaction::new(state, self.request.clone());
+        Box::new(trs)
+    }
+}
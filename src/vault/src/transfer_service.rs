@@ -1,17 +1,22 @@
-use candid::CandidType;
+use candid::{CandidType, Nat};
 use candid::Principal;
 use ic_cdk::api::call::{CallResult, RejectionCode};
-use ic_cdk::call;
-use ic_ledger_types::{AccountIdentifier, BlockIndex as BlockIndexLegacy, DEFAULT_FEE, MAINNET_LEDGER_CANISTER_ID, Memo as MemoLegacy, Subaccount as SubLegacy, Tokens};
+use ic_cdk::{call, id};
+use ic_ledger_types::{account_balance, AccountBalanceArgs, AccountIdentifier, Block, BlockIndex as BlockIndexLegacy, DEFAULT_FEE, GetBlocksArgs, MAINNET_LEDGER_CANISTER_ID, Memo as MemoLegacy, query_archived_blocks, query_blocks, Subaccount as SubLegacy, Timestamp, Tokens, TransferError as TransferErrorLegacy};
 use icrc_ledger_types::icrc1;
 use icrc_ledger_types::icrc1::account::Account;
 use icrc_ledger_types::icrc1::account::Subaccount;
-use icrc_ledger_types::icrc1::transfer::{BlockIndex, NumTokens, TransferError};
+use icrc_ledger_types::icrc1::transfer::{BlockIndex, Memo, NumTokens, TransferError};
+use icrc_ledger_types::icrc2::allowance::{Allowance, AllowanceArgs};
+use icrc_ledger_types::icrc2::approve::{ApproveArgs, ApproveError};
+use icrc_ledger_types::icrc3::transactions::{GetTransactionsRequest, GetTransactionsResponse, Transaction, TransactionRange};
 use serde::{Deserialize, Serialize};
 
 use crate::to_array;
+use crate::transaction::wallet::wallet::to_subaccount;
 
-pub async fn transfer(amount: u64, to: AccountIdentifier, from_hex: String, memo: Option<u64>) -> Result<BlockIndexLegacy, String> {
+//a duplicate of an already executed transfer resolves to the block of the original one
+pub async fn transfer(amount: u64, to: AccountIdentifier, from_hex: String, memo: Option<u64>, created_at_time: Option<u64>) -> Result<BlockIndexLegacy, String> {
     let tokens = Tokens::from_e8s(amount);
     let from_decoded = match hex::decode(from_hex) {
         Ok(x) => { x }
@@ -41,17 +46,20 @@ pub async fn transfer(amount: u64, to: AccountIdentifier, from_hex: String, memo
         fee: DEFAULT_FEE,
         from_subaccount: Some(from_sub),
         to,
-        created_at_time: None,
+        created_at_time: created_at_time.map(|t| Timestamp { timestamp_nanos: t }),
     };
-    ic_ledger_types::transfer(ledger_canister_id, transfer_args).await
-        .map_err(|e| format!("failed to call ledger: {:?}", e))?
-        .map_err(|e| format!("ledger transfer error: {:?}", e))
+    match ic_ledger_types::transfer(ledger_canister_id, transfer_args).await
+        .map_err(|e| format!("failed to call ledger: {:?}", e))? {
+        Ok(block_index) => { Ok(block_index) }
+        Err(TransferErrorLegacy::TxDuplicate { duplicate_of }) => { Ok(duplicate_of) }
+        Err(e) => { Err(format!("ledger transfer error: {:?}", e)) }
+    }
 }
 
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
 pub enum TransferResult { Ok(BlockIndex), Err(TransferError) }
 
-pub async fn transfer_icrc1(icrc1_canister: Principal, amount: u64, to_owner: Principal, subaccount: Option<Subaccount>, from_wallet_hex: String) -> CallResult<(TransferResult, )> {
+pub async fn transfer_icrc1(icrc1_canister: Principal, amount: u64, to_owner: Principal, subaccount: Option<Subaccount>, from_wallet_hex: String, memo: Option<Memo>, created_at_time: Option<u64>) -> CallResult<(TransferResult, )> {
     let amount_nat = NumTokens::from(amount);
     let from_decoded = match hex::decode(from_wallet_hex) {
         Ok(x) => { x }
@@ -64,11 +72,223 @@ pub async fn transfer_icrc1(icrc1_canister: Principal, amount: u64, to_owner: Pr
         from_subaccount: Some(from_sub),
         to: Account { owner: to_owner, subaccount },
         fee: None,
-        created_at_time: None,
-        memo: None,
+        created_at_time,
+        memo,
         amount: amount_nat,
     };
 
     let result: CallResult<(TransferResult, )> = call(icrc1_canister, "icrc1_transfer", (args, )).await;
     result
 }
+
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum ApproveResult { Ok(BlockIndex), Err(ApproveError) }
+
+pub async fn approve_icrc2(icrc2_canister: Principal, amount: Nat, spender: Account, expected_allowance: Option<u64>,
+                           expires_at: Option<u64>, from_wallet_hex: String) -> CallResult<(ApproveResult, )> {
+    let from_decoded = match hex::decode(from_wallet_hex) {
+        Ok(x) => { x }
+        Err(err) => {
+            return Err((RejectionCode::DestinationInvalid, format!("Failed to decode hex: {:?}", err)));
+        }
+    };
+    let from_sub = to_array(from_decoded);
+    let args = ApproveArgs {
+        from_subaccount: Some(from_sub),
+        spender,
+        amount,
+        expected_allowance: expected_allowance.map(Nat::from),
+        expires_at,
+        fee: None,
+        memo: None,
+        created_at_time: None,
+    };
+
+    let result: CallResult<(ApproveResult, )> = call(icrc2_canister, "icrc2_approve", (args, )).await;
+    result
+}
+
+pub async fn get_icrc2_allowance(icrc2_canister: Principal, spender: Account, from_wallet_hex: String) -> CallResult<(Allowance, )> {
+    let from_decoded = match hex::decode(from_wallet_hex) {
+        Ok(x) => { x }
+        Err(err) => {
+            return Err((RejectionCode::DestinationInvalid, format!("Failed to decode hex: {:?}", err)));
+        }
+    };
+    let args = AllowanceArgs {
+        account: Account { owner: id(), subaccount: Some(to_array(from_decoded)) },
+        spender,
+    };
+    call(icrc2_canister, "icrc2_allowance", (args, )).await
+}
+
+//blocks already moved to the archive are fetched through the callbacks returned by the ledger
+pub async fn get_icp_blocks(start: BlockIndexLegacy, length: u64) -> Result<(u64, Vec<(BlockIndexLegacy, Block)>), String> {
+    let response = query_blocks(MAINNET_LEDGER_CANISTER_ID, GetBlocksArgs { start, length }).await
+        .map_err(|e| format!("failed to call ledger: {:?}", e))?;
+    let mut blocks = Vec::new();
+    for range in response.archived_blocks {
+        let archived = query_archived_blocks(&range.callback, GetBlocksArgs { start: range.start, length: range.length }).await
+            .map_err(|e| format!("failed to call archive: {:?}", e))?
+            .map_err(|e| format!("archive error: {:?}", e))?;
+        for (i, block) in archived.blocks.into_iter().enumerate() {
+            blocks.push((range.start + i as u64, block));
+        }
+    }
+    for (i, block) in response.blocks.into_iter().enumerate() {
+        blocks.push((response.first_block_index + i as u64, block));
+    }
+    Ok((response.chain_length, blocks))
+}
+
+pub async fn get_icrc1_transactions(icrc1_canister: Principal, start: u64, length: u64) -> Result<(u64, Vec<(u64, Transaction)>), String> {
+    let request = GetTransactionsRequest { start: Nat::from(start), length: Nat::from(length) };
+    let (response, ): (GetTransactionsResponse, ) = call(icrc1_canister, "get_transactions", (request, )).await
+        .map_err(|e| format!("failed to call ledger: {:?}", e))?;
+    let mut transactions = Vec::new();
+    for range in response.archived_transactions {
+        let request = GetTransactionsRequest { start: range.start.clone(), length: range.length };
+        let (archived, ): (TransactionRange, ) = call(range.callback.canister_id, &range.callback.method, (request, )).await
+            .map_err(|e| format!("failed to call archive: {:?}", e))?;
+        let first = nat_to_u64(&range.start);
+        for (i, transaction) in archived.transactions.into_iter().enumerate() {
+            transactions.push((first + i as u64, transaction));
+        }
+    }
+    let first = nat_to_u64(&response.first_index);
+    for (i, transaction) in response.transactions.into_iter().enumerate() {
+        transactions.push((first + i as u64, transaction));
+    }
+    Ok((nat_to_u64(&response.log_length), transactions))
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct GetAccountTransactionsArgs {
+    pub account: Account,
+    pub start: Option<Nat>,
+    pub max_results: Nat,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct TransactionWithId {
+    pub id: Nat,
+    pub transaction: Transaction,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct GetAccountTransactions {
+    pub balance: Nat,
+    pub transactions: Vec<TransactionWithId>,
+    pub oldest_tx_id: Option<Nat>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct GetAccountTransactionsErr {
+    pub message: String,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum GetAccountTransactionsResult { Ok(GetAccountTransactions), Err(GetAccountTransactionsErr) }
+
+//newest first, as returned by the index canister
+pub async fn get_index_account_transactions(index_canister: Principal, account: Account, start: Option<u64>, max_results: u64) -> Result<GetAccountTransactions, String> {
+    let args = GetAccountTransactionsArgs {
+        account,
+        start: start.map(Nat::from),
+        max_results: Nat::from(max_results),
+    };
+    let (result, ): (GetAccountTransactionsResult, ) = call(index_canister, "get_account_transactions", (args, )).await
+        .map_err(|e| format!("failed to call index: {:?}", e))?;
+    match result {
+        GetAccountTransactionsResult::Ok(transactions) => { Ok(transactions) }
+        GetAccountTransactionsResult::Err(e) => { Err(e.message) }
+    }
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct GetAccountIdentifierTransactionsArgs {
+    pub account_identifier: String,
+    pub start: Option<u64>,
+    pub max_results: u64,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum IcpIndexOperation {
+    Transfer { to: String, fee: Tokens, from: String, amount: Tokens, spender: Option<String> },
+    Approve { fee: Tokens, from: String, allowance: Tokens, expected_allowance: Option<Tokens>, expires_at: Option<Timestamp>, spender: String },
+    Burn { from: String, amount: Tokens, spender: Option<String> },
+    Mint { to: String, amount: Tokens },
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct IcpIndexTransaction {
+    pub memo: u64,
+    pub icrc1_memo: Option<serde_bytes::ByteBuf>,
+    pub operation: IcpIndexOperation,
+    pub created_at_time: Option<Timestamp>,
+    pub timestamp: Option<Timestamp>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct IcpTransactionWithId {
+    pub id: u64,
+    pub transaction: IcpIndexTransaction,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct GetAccountIdentifierTransactions {
+    pub balance: u64,
+    pub transactions: Vec<IcpTransactionWithId>,
+    pub oldest_tx_id: Option<u64>,
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum GetAccountIdentifierTransactionsResult { Ok(GetAccountIdentifierTransactions), Err(GetAccountTransactionsErr) }
+
+//newest first, as returned by the ICP index canister
+pub async fn get_icp_index_account_transactions(index_canister: Principal, account: AccountIdentifier, start: Option<u64>, max_results: u64) -> Result<GetAccountIdentifierTransactions, String> {
+    let args = GetAccountIdentifierTransactionsArgs {
+        account_identifier: account.to_hex(),
+        start,
+        max_results,
+    };
+    let (result, ): (GetAccountIdentifierTransactionsResult, ) = call(index_canister, "get_account_identifier_transactions", (args, )).await
+        .map_err(|e| format!("failed to call index: {:?}", e))?;
+    match result {
+        GetAccountIdentifierTransactionsResult::Ok(transactions) => { Ok(transactions) }
+        GetAccountIdentifierTransactionsResult::Err(e) => { Err(e.message) }
+    }
+}
+
+pub fn nat_to_u64(n: &Nat) -> u64 {
+    n.0.to_u64_digits().first().cloned().unwrap_or(0)
+}
+
+//wallets created before the uid validation may have a uid that is not a subaccount, their balance is reported as an error
+pub fn to_wallet_subaccount(wallet_hex: &String) -> Result<[u8; 32], String> {
+    to_subaccount(wallet_hex)
+        .ok_or(format!("Wallet {} is not a 32 byte hex subaccount", wallet_hex))
+}
+
+pub async fn get_icp_balance(from_wallet_hex: String) -> Result<Nat, String> {
+    let args = AccountBalanceArgs {
+        account: AccountIdentifier::new(&id(), &SubLegacy(to_wallet_subaccount(&from_wallet_hex)?)),
+    };
+    account_balance(MAINNET_LEDGER_CANISTER_ID, args).await
+        .map(|tokens| Nat::from(tokens.e8s()))
+        .map_err(|e| format!("failed to call ledger: {:?}", e))
+}
+
+pub async fn get_icrc1_balance(icrc1_canister: Principal, from_wallet_hex: String) -> Result<Nat, String> {
+    let account = Account { owner: id(), subaccount: Some(to_wallet_subaccount(&from_wallet_hex)?) };
+    let (balance, ): (Nat, ) = call(icrc1_canister, "icrc1_balance_of", (account, )).await
+        .map_err(|e| format!("failed to call ledger: {:?}", e))?;
+    Ok(balance)
+}
+
+pub async fn get_icrc1_fee(icrc1_canister: Principal) -> Result<Nat, String> {
+    let (fee, ): (Nat, ) = call(icrc1_canister, "icrc1_fee", ()).await
+        .map_err(|e| format!("failed to call ledger: {:?}", e))?;
+    Ok(fee)
+}
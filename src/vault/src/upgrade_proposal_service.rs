@@ -0,0 +1,89 @@
+use std::cell::RefCell;
+
+use candid::{CandidType, Deserialize};
+use ic_cdk::api::time;
+use ic_cdk::call;
+use semver::Version;
+
+use crate::config::{get_release_channel, get_repo_canister_id};
+use crate::transaction::basic_transaction::BasicTransaction;
+use crate::transaction::transaction::TransactionCandid;
+use crate::transaction::transaction_service::{get_all_transactions, get_unfinished_transactions, store_transaction};
+use crate::transaction::upgrade::upgrade_transaction::{get_verified_hash, VersionUpgradeTransaction};
+use crate::VERSION;
+
+const CHECK_INTERVAL_NANOS: u64 = 6 * 60 * 60 * 1_000_000_000;
+const STABLE_CHANNEL: &str = "stable";
+
+thread_local! {
+    static LAST_CHECK: RefCell<u64> = RefCell::new(0);
+}
+
+#[derive(CandidType, Deserialize)]
+struct VersionWrapper {
+    version: String,
+    description: Option<String>,
+}
+
+//called from the global timer, the repo is polled at most once per interval
+pub async fn check_new_versions() {
+    let now = time();
+    if LAST_CHECK.with(|l| *l.borrow() + CHECK_INTERVAL_NANOS > now) {
+        return;
+    }
+    LAST_CHECK.with(|l| l.replace(now));
+    //one proposal at a time, admins decide on it before the next one is created
+    let in_progress = get_unfinished_transactions().iter()
+        .any(|tr| matches!(tr.to_candid(), TransactionCandid::UpgradeTransactionV(_)));
+    if in_progress {
+        return;
+    }
+    let upgrades: Vec<VersionUpgradeTransaction> = get_all_transactions().into_iter()
+        .filter_map(|tr| match tr.to_candid() {
+            TransactionCandid::UpgradeTransactionV(t) => Some(t),
+            _ => None
+        })
+        .collect();
+    let versions: Vec<VersionWrapper> = match call(get_repo_canister_id(), "get_available_versions", ()).await {
+        Ok((versions, )) => { versions }
+        Err((code, msg)) => {
+            ic_cdk::eprintln!("Error while getting versions: [{:?}] {}", code, msg);
+            return;
+        }
+    };
+    let current_version = Version::parse(VERSION).unwrap();
+    let channel = get_release_channel();
+    //versions proposed once are not proposed again, even when rejected
+    let latest = versions.into_iter()
+        .filter_map(|v| Version::parse(&v.version).ok().map(|version| (version, v.description)))
+        .filter(|(version, _)| version > &current_version)
+        .filter(|(version, _)| is_in_channel(version, &channel))
+        .filter(|(version, _)| !upgrades.iter().any(|t| t.version.eq(&version.to_string())))
+        .max_by(|a, b| a.0.cmp(&b.0));
+    let (version, description) = match latest {
+        None => { return; }
+        Some(x) => { x }
+    };
+    let hash = match get_verified_hash(&version).await {
+        Ok(x) => { x }
+        Err(msg) => {
+            ic_cdk::eprintln!("Version {} is not proposed: {}", version, msg);
+            return;
+        }
+    };
+    let trs = VersionUpgradeTransaction::new_system(version.to_string(), hash, description);
+    store_transaction(trs.clone_self());
+}
+
+fn is_in_channel(version: &Version, channel: &Option<String>) -> bool {
+    match channel {
+        None => { true }
+        Some(channel) => {
+            if version.pre.is_empty() {
+                channel.eq(STABLE_CHANNEL)
+            } else {
+                version.pre.as_str().split('.').next().map_or(false, |c| c.eq(channel))
+            }
+        }
+    }
+}
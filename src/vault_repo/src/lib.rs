@@ -19,6 +19,8 @@ pub struct VaultWasm {
     version: String,
     hash: String,
     description: Option<String>,
+    //stable memory schema of the vault build, vaults refuse rollbacks across schemas
+    schema_version: Option<u32>,
 }
 
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug, Hash, PartialEq)]
@@ -67,6 +69,7 @@ struct WalletStoreWASMArgs {
     version: String,
     hash: String,
     description: Option<String>,
+    schema_version: Option<u32>,
 }
 
 #[derive(CandidType, Deserialize)]
@@ -130,6 +133,7 @@ async fn add_version(args: WalletStoreWASMArgs) {
             version: args.version,
             hash: args.hash,
             description: args.description,
+            schema_version: args.schema_version,
         })
     });
 }
@@ -8,6 +8,10 @@ export const DFX = {
     SYNC_CONTROLLERS: () => execute(`dfx canister call vault sync_controllers`),
     GET_PRINCIPAL: () => call(`dfx identity get-principal`),
     INIT: () => execute(`dfx start --clean --background`),
+    //bitcoin is disabled in dfx.json, the regtest node is only started for the bitcoin tests
+    INIT_WITH_BITCOIN: () => execute(`mkdir -p .bitcoin && bitcoind -regtest -daemon -datadir=.bitcoin -fallbackfee=0.0002 -port=18444 && sleep 3 && dfx start --clean --background --enable-bitcoin --bitcoin-node 127.0.0.1:18444`),
+    STOP_BITCOIN: () => execute(`bitcoin-cli -regtest -datadir=.bitcoin stop; rm -rf .bitcoin`),
+    BITCOIN_CLI: (x: string) => call(`bitcoin-cli -regtest -datadir=.bitcoin ${x}`),
     UPGRADE_FORCE: (x: string) => execute(`dfx canister install --mode upgrade --upgrade-unchanged ${x} `),
     GET_CANISTER_ID: (x: string) => call(`dfx canister id ${x}`),
     ADD_CONTROLLER: (x: string, y: string) => execute(`dfx canister update-settings --add-controller "${x}" ${y}`),
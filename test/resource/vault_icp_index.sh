@@ -0,0 +1,18 @@
+#!/usr/bin/env bash
+echo "===========SETUP========="
+test -f icp_index.wasm.gz || curl -o icp_index.wasm.gz "https://download.dfinity.systems/ic/d87954601e4b22972899e9957e800406a0a6b929/canisters/ic-icp-index-canister.wasm.gz"
+test -f icp_index.wasm || gunzip icp_index.wasm.gz
+test -f icp_index.did || curl -o icp_index.did "https://raw.githubusercontent.com/dfinity/ic/d87954601e4b22972899e9957e800406a0a6b929/rs/rosetta-api/icp_ledger/index/index.did"
+
+dfx identity use test_admin
+
+echo "===========DEPLOY ICP INDEX========="
+
+dfx deploy icp_index --argument '(record { ledger_id = principal "ryjl3-tyaaa-aaaaa-aaaba-cai" })'
+export ICP_INDEX=$(dfx canister id icp_index)
+
+echo "===========DEPLOY VAULT (LOCAL ICP INDEX)========="
+
+dfx deploy vault --argument "(principal \"3ekng-5nqql-esu4u-64sla-pcm5o-hjatn-hwjo7-vk7ya-ianug-zqqyy-iae\", record { origins = vec {}; repo_canister = \"7jlkn-paaaa-aaaap-abvpa-cai\"; icp_index = opt \"$ICP_INDEX\" })"
+
+echo "DONE"
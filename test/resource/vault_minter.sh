@@ -0,0 +1,13 @@
+#!/usr/bin/env bash
+echo "===========DEPLOY MINTER STUB========="
+
+dfx identity use test_admin
+
+dfx deploy minter_stub
+export MINTER=$(dfx canister id minter_stub)
+
+echo "===========DEPLOY VAULT (STUB MINTER)========="
+
+dfx deploy vault --argument "(principal \"3ekng-5nqql-esu4u-64sla-pcm5o-hjatn-hwjo7-vk7ya-ianug-zqqyy-iae\", record { origins = vec {}; repo_canister = \"7jlkn-paaaa-aaaap-abvpa-cai\"; ckbtc_minter = opt \"$MINTER\"; ckbtc_ledger = opt \"$MINTER\" })"
+
+echo "DONE"
@@ -0,0 +1,71 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {Principal} from "@dfinity/principal";
+import {getRawTransactionById, getVaultActor, requestCreateWalletTransaction, requestRawTransaction} from "./helper";
+import {Network, VaultManager, WalletCreateTransaction} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+//minter_stub keeps the ICRC-2 allowances of its callers, so it stands in for the ledger here
+describe("ICRC-2 Allowances", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let spender = {owner: Principal.fromText("sculj-2sjuf-dxqlm-dcv5y-hin5x-zfyvr-tzngf-bt5b5-dwhcc-zbsqf-rae"), subaccount: []}
+    let manager: VaultManager;
+    let actor;
+    let ledger: Principal;
+    let walletUid;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/vault.sh`))
+        execute(`dfx deploy minter_stub`)
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        ledger = Principal.fromText(DFX.GET_CANISTER_ID("minter_stub"));
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        let wallet = await requestCreateWalletTransaction(manager, "allowanceWallet", Network.IC) as Array<WalletCreateTransaction>
+        walletUid = wallet[0].uid
+        await manager.execute()
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    it("Approve grants an allowance to the spender", async function () {
+        let tr = await requestRawTransaction(actor, {
+            ICRC2ApproveTransactionRequestV: {
+                wallet: walletUid,
+                ledger_id: ledger,
+                spender,
+                amount: 5000n,
+                expires_at: [],
+                expected_allowance: [],
+                memo: []
+            }
+        })
+        await manager.execute()
+        tr = await getRawTransactionById(actor, tr.common.id)
+        expect(tr.common.state).deep.eq({Executed: null})
+        expect(tr.block_index.length).eq(1)
+        let allowances = await actor.get_allowances(walletUid)
+        expect(allowances.length).eq(1)
+        expect(allowances[0].ledger_id.toText()).eq(ledger.toText())
+        expect(allowances[0].spender.owner.toText()).eq(spender.owner.toText())
+        expect(allowances[0].allowance).eq(5000n)
+    });
+
+    it("Revoke sets the allowance to zero", async function () {
+        let tr = await requestRawTransaction(actor, {
+            ICRC2RevokeTransactionRequestV: {wallet: walletUid, ledger_id: ledger, spender, memo: []}
+        })
+        await manager.execute()
+        tr = await getRawTransactionById(actor, tr.common.id)
+        expect(tr.common.state).deep.eq({Executed: null})
+        expect(await actor.get_allowances(walletUid)).deep.eq([])
+    });
+});
@@ -0,0 +1,64 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute, sleep} from "../util/call.util";
+import {expect} from "chai";
+import {getVaultActor, requestCreateWalletTransaction, requestRawTransaction} from "./helper";
+import {Network, VaultManager, WalletCreateTransaction} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+//the rule is checked from the canister global timer, which ticks once a minute
+describe("Auto Top-Up", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let manager: VaultManager;
+    let actor;
+    let walletUid;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/ledger.sh`))
+        await console.log(execute(`./test/resource/vault.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        let wallet = await requestCreateWalletTransaction(manager, "topUpWallet", Network.IC) as Array<WalletCreateTransaction>
+        walletUid = wallet[0].uid
+        await manager.execute()
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    function getAutoTopUps(transactions: Array<any>) {
+        return transactions.filter((t) => t.AutoTopUpTransactionV !== undefined)
+            .map((t) => t.AutoTopUpTransactionV)
+    }
+
+    it("Timer requests a top-up once the balance is below the rule", async function () {
+        await requestRawTransaction(actor, {
+            AutoTopUpRuleUpdateTransactionRequestV: {
+                rule: [{min_cycles: 1_000_000_000_000_000n, wallet: walletUid, amount: 100_000n, daily_cap: 1_000_000n}],
+                batch_uid: []
+            }
+        })
+        await manager.execute()
+        expect(getAutoTopUps(await actor.get_transactions_all())).length(0)
+        await sleep(70)
+        let topUps = getAutoTopUps(await actor.get_transactions_all())
+        expect(topUps).length(1)
+        expect(topUps[0].wallet).eq(walletUid)
+        expect(topUps[0].amount).eq(100_000n)
+        expect(topUps[0].cycles_balance > 0n).eq(true)
+    });
+
+    it("Timer keeps ticking after an upgrade", async function () {
+        execute(`dfx canister install --mode upgrade --upgrade-unchanged vault --argument '(principal "3ekng-5nqql-esu4u-64sla-pcm5o-hjatn-hwjo7-vk7ya-ianug-zqqyy-iae", record { origins = vec {}; repo_canister = "7jlkn-paaaa-aaaap-abvpa-cai" })'`)
+        await sleep(70)
+        //the check interval is an hour and the interval state is on the heap, so the upgrade lets the next tick check again
+        let topUps = getAutoTopUps(await actor.get_transactions_all())
+        expect(topUps).length(2)
+    });
+});
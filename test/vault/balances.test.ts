@@ -0,0 +1,79 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {Principal} from "@dfinity/principal";
+import {fromHexString, principalToAddressBytes} from "ictool";
+import {getVaultActor, requestCreateWalletTransaction, requestRawTransaction} from "./helper";
+import {Network, VaultManager, WalletCreateTransaction} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+describe("Wallet Balances", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let ledger = "ryjl3-tyaaa-aaaaa-aaaba-cai"
+    let manager: VaultManager;
+    let actor;
+    let fundedUid;
+    let emptyUid;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/ledger.sh`))
+        await console.log(execute(`./test/resource/vault.sh`))
+        execute(`dfx deploy minter_stub`)
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        let funded = await requestCreateWalletTransaction(manager, "fundedWallet", Network.IC) as Array<WalletCreateTransaction>
+        let empty = await requestCreateWalletTransaction(manager, "emptyWallet", Network.IC) as Array<WalletCreateTransaction>
+        fundedUid = funded[0].uid
+        emptyUid = empty[0].uid
+        await manager.execute()
+        let walBytes = principalToAddressBytes(Principal.fromText(canister_id) as any, fromHexString(fundedUid))
+        DFX.LEDGER_FILL_BALANCE(walBytes.toString().replaceAll(',', ';'))
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    it("Cache is empty until the balances are refreshed", async function () {
+        expect(await actor.get_balances_cached([])).deep.eq([])
+    });
+
+    it("Balances are read from the ICP ledger", async function () {
+        let balances = await actor.get_balances([fundedUid])
+        expect(balances.length).eq(1)
+        expect(balances[0].wallet).eq(fundedUid)
+        expect(balances[0].balances.length).eq(1)
+        expect(balances[0].balances[0].ledger.toText()).eq(ledger)
+        expect(balances[0].balances[0].balance).deep.eq([100_000_000n])
+        expect(balances[0].refreshed_date > 0n).eq(true)
+    });
+
+    it("Registered ICRC-1 ledgers are queried and errors are reported per ledger", async function () {
+        //minter_stub has no icrc1_balance_of, so its balance comes back as an error
+        let stub = Principal.fromText(DFX.GET_CANISTER_ID("minter_stub"))
+        await requestRawTransaction(actor, {
+            ICRC1CanistersAddTransactionRequestV: {index_canister: [], ledger_canister: stub, batch_uid: []}
+        })
+        await manager.execute()
+        let balances = await actor.get_balances([])
+        expect(balances.map((b) => b.wallet).sort()).deep.eq([fundedUid, emptyUid].sort())
+        let empty = balances.find((b) => b.wallet === emptyUid)
+        expect(empty.balances[0].balance).deep.eq([0n])
+        expect(empty.balances[1].ledger.toText()).eq(stub.toText())
+        expect(empty.balances[1].balance).deep.eq([])
+        expect(empty.balances[1].error.length).eq(1)
+    });
+
+    it("Cached query returns the last refresh", async function () {
+        let cached = await actor.get_balances_cached([fundedUid])
+        expect(cached.length).eq(1)
+        expect(cached[0].balances.length).eq(2)
+        expect(cached[0].balances[0].balance).deep.eq([100_000_000n])
+    });
+});
@@ -0,0 +1,109 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute, sleep} from "../util/call.util";
+import {expect} from "chai";
+import {
+    getRawTransactionById,
+    getVaultActor,
+    requestCreateWalletTransaction,
+    requestRawCreatePolicyTransaction,
+    requestRawTransaction
+} from "./helper";
+import {Network, VaultManager, WalletCreateTransaction} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+//needs bitcoind on the PATH, the replica syncs the regtest chain from it
+describe("BTC Transactions", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let manager: VaultManager;
+    let actor;
+    let btcWalletUid;
+    let icWalletUid;
+    let walletAddress;
+    before(async () => {
+        DFX.INIT_WITH_BITCOIN();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/vault_btc.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        let btcWallet = await requestRawTransaction(actor, {
+            WalletCreateTransactionRequestV: {uid: "", name: "btcWallet", network: {BTC: null}, batch_uid: []}
+        })
+        let icWallet = await requestCreateWalletTransaction(manager, "icWallet", Network.IC) as Array<WalletCreateTransaction>
+        icWalletUid = icWallet[0].uid
+        await manager.execute()
+        btcWalletUid = (await getRawTransactionById(actor, btcWallet.common.id)).uid
+        await requestRawCreatePolicyTransaction(actor, {BTC: null}, 1, 0n, [btcWalletUid], undefined)
+        await manager.execute()
+        walletAddress = await actor.get_btc_address(btcWalletUid)
+        //coinbase outputs mature after 100 blocks, the wallet gets two plain utxos instead
+        DFX.BITCOIN_CLI(`createwallet test`)
+        let minerAddress = DFX.BITCOIN_CLI(`getnewaddress`)
+        DFX.BITCOIN_CLI(`generatetoaddress 101 ${minerAddress}`)
+        DFX.BITCOIN_CLI(`sendtoaddress ${walletAddress} 10`)
+        DFX.BITCOIN_CLI(`sendtoaddress ${walletAddress} 10`)
+        DFX.BITCOIN_CLI(`generatetoaddress 1 ${minerAddress}`)
+        await sleep(10)
+    });
+
+    after(() => {
+        DFX.STOP();
+        DFX.STOP_BITCOIN();
+    });
+
+    it("Wallet address is a regtest P2PKH address with a balance", async function () {
+        expect(walletAddress.startsWith("m") || walletAddress.startsWith("n")).eq(true)
+        let balance = await actor.get_btc_balance(btcWalletUid) as bigint
+        expect(balance).eq(2_000_000_000n)
+    });
+
+    it("Two transfers in a row spend different utxos", async function () {
+        let destination = DFX.BITCOIN_CLI(`getnewaddress`)
+        let first = await requestBtcTransfer(btcWalletUid, destination, 100_000_000n)
+        let second = await requestBtcTransfer(btcWalletUid, destination, 100_000_000n)
+        await manager.execute()
+        first = await getRawTransactionById(actor, first.common.id)
+        second = await getRawTransactionById(actor, second.common.id)
+        expect(first.common.state).deep.eq({Executed: null})
+        expect(second.common.state).deep.eq({Executed: null})
+        expect(first.txid[0]).not.eq(second.txid[0])
+        let mempool = JSON.parse(DFX.BITCOIN_CLI(`getrawmempool`)) as Array<string>
+        expect(mempool).to.include(first.txid[0])
+        expect(mempool).to.include(second.txid[0])
+    });
+
+    it("Transfer with no unspent utxo left fails until the spends are mined", async function () {
+        let destination = DFX.BITCOIN_CLI(`getnewaddress`)
+        let third = await requestBtcTransfer(btcWalletUid, destination, 100_000_000n)
+        await manager.execute()
+        third = await getRawTransactionById(actor, third.common.id)
+        expect(third.common.state).deep.eq({Failed: null})
+        expect(third.common.error[0].CanisterReject.message).contains("Insufficient funds")
+    });
+
+    it("Transfer from a non BTC wallet fails", async function () {
+        let destination = DFX.BITCOIN_CLI(`getnewaddress`)
+        let transfer = await requestBtcTransfer(icWalletUid, destination, 1000n)
+        await manager.execute()
+        transfer = await getRawTransactionById(actor, transfer.common.id)
+        expect(transfer.common.error).deep.eq([{InvalidNetwork: null}])
+    });
+
+    it("Transfer to a segwit v0 address with a wrong program length fails", async function () {
+        //valid bech32 checksum, 16 bytes witness v0 program
+        let transfer = await requestBtcTransfer(btcWalletUid, "bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqm0h9ue", 1000n)
+        await manager.execute()
+        transfer = await getRawTransactionById(actor, transfer.common.id)
+        expect(transfer.common.error).deep.eq([{InvalidAddress: null}])
+    });
+
+    async function requestBtcTransfer(wallet: string, address: string, amount: bigint) {
+        return await requestRawTransaction(actor, {
+            TransferBTCTransactionRequestV: {wallet, address, amount, memo: []}
+        })
+    }
+});
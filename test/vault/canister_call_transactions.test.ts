@@ -0,0 +1,69 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {Principal} from "@dfinity/principal";
+import {getRawTransactionById, getVaultActor, requestRawCreatePolicyTransaction, requestRawTransaction} from "./helper";
+import {VaultManager} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+//candid encoding of an empty argument list
+const EMPTY_ARG = [0x44, 0x49, 0x44, 0x4c, 0x00, 0x00]
+
+describe("Canister Call Transactions", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let manager: VaultManager;
+    let actor;
+    let target: Principal;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/vault.sh`))
+        execute(`dfx deploy minter_stub`)
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        target = Principal.fromText(DFX.GET_CANISTER_ID("minter_stub"));
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    async function requestCall(canister: Principal, cycles: Array<bigint>) {
+        let call = await requestRawTransaction(actor, {
+            CanisterCallTransactionRequestV: {canister, method: "fail_next", arg: EMPTY_ARG, cycles, memo: []}
+        })
+        await manager.execute()
+        return await getRawTransactionById(actor, call.common.id)
+    }
+
+    it("Calls to the management canister, the ledger and the vault are rejected", async function () {
+        for (let canister of ["aaaaa-aa", "ryjl3-tyaaa-aaaaa-aaaba-cai", "rkp4c-7iaaa-aaaaa-aaaca-cai", canister_id]) {
+            let call = await requestCall(Principal.fromText(canister), [])
+            expect(Object.keys(call.common.state)[0]).eq("Failed")
+            expect(Object.keys(call.common.error[0])[0]).eq("CallTargetNotAllowed")
+        }
+    });
+
+    it("Call without cycles is approved by the quorum", async function () {
+        let call = await requestCall(target, [])
+        expect(Object.keys(call.common.state)[0]).eq("Executed")
+        expect(call.policy.length).eq(0)
+    });
+
+    it("Call with cycles needs a Cycles policy", async function () {
+        let call = await requestCall(target, [1_000_000n])
+        expect(Object.keys(call.common.state)[0]).eq("Failed")
+        expect(Object.keys(call.common.error[0])[0]).eq("CouldNotDefinePolicy")
+
+        let policy = await requestRawCreatePolicyTransaction(actor, {Cycles: null}, 1, 0n, [], undefined)
+        await manager.execute()
+        call = await requestCall(target, [1_000_000n])
+        expect(Object.keys(call.common.state)[0]).eq("Executed")
+        expect(call.policy[0]).eq(policy.uid)
+    });
+});
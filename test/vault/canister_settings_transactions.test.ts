@@ -0,0 +1,57 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {getRawTransactionById, getVaultActor, requestRawTransaction} from "./helper";
+import {VaultManager} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+describe("Canister Settings Transactions", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let manager: VaultManager;
+    let actor;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/vault.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        //the vault updates its own settings, so it has to be its own controller
+        DFX.ADD_CONTROLLER(canister_id, "vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    it("Status cache is empty until the status is refreshed", async function () {
+        expect(await actor.get_canister_status_cached()).deep.eq([])
+        let status = await actor.get_canister_status()
+        let cached = await actor.get_canister_status_cached()
+        expect(cached.length).eq(1)
+        expect(cached[0].settings.freezing_threshold).eq(status.settings.freezing_threshold)
+    });
+
+    it("Settings update changes the freezing threshold and refreshes the cache", async function () {
+        let tr = await requestRawTransaction(actor, {
+            CanisterSettingsUpdateTransactionRequestV: {
+                compute_allocation: [],
+                memory_allocation: [],
+                freezing_threshold: [5_184_000n],
+                reserved_cycles_limit: [],
+                memo: []
+            }
+        })
+        await manager.execute()
+        tr = await getRawTransactionById(actor, tr.common.id)
+        expect(tr.common.state).deep.eq({Executed: null})
+        expect(tr.freezing_threshold).deep.eq([5_184_000n])
+        let cached = await actor.get_canister_status_cached()
+        expect(cached[0].settings.freezing_threshold).eq(5_184_000n)
+        expect(cached[0].settings.controllers.map((c) => c.toText())).contains(canister_id)
+    });
+});
@@ -0,0 +1,81 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {principalToAddress} from "ictool";
+import {
+    getVaultActor,
+    requestCreateMemberTransaction,
+    requestCreateWalletTransaction,
+    requestRawCreatePolicyTransaction,
+    requestRawTransaction
+} from "./helper";
+import {Network, VaultManager, VaultRole, WalletCreateTransaction} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+describe("Transaction Comments", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let member_identity = getIdentity("87654321876543218765432187654322")
+    let manager: VaultManager;
+    let actor;
+    let wallet: WalletCreateTransaction;
+    let pending;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/vault.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        await requestCreateMemberTransaction(manager, principalToAddress(member_identity.getPrincipal() as any), "member", VaultRole.ADMIN)
+        wallet = (await requestCreateWalletTransaction(manager, "commentWallet", Network.IC) as Array<WalletCreateTransaction>)[0]
+        await manager.execute()
+        await requestRawCreatePolicyTransaction(actor, {ICP: null}, 2, 0n, [wallet.uid], undefined)
+        await manager.execute()
+        pending = await requestRawTransaction(actor, {
+            TransferTransactionRequestV: {
+                memo: [],
+                currency: {ICP: null},
+                address: principalToAddress(admin_identity.getPrincipal() as any),
+                wallet: wallet.uid,
+                amount: 100n
+            }
+        })
+        await manager.execute()
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    it("Pending transaction can be commented", async function () {
+        let commented = await actor.comment({transaction_id: pending.common.id, text: "  waiting for the invoice  "})
+        let comments = (Object.values(commented)[0] as any).common.comments[0]
+        expect(comments.length).eq(1)
+        expect(comments[0].text).eq("waiting for the invoice")
+        expect(comments[0].author).eq(principalToAddress(admin_identity.getPrincipal() as any))
+    });
+
+    it("Finished transaction can not be commented", async function () {
+        let error
+        try {
+            await actor.comment({transaction_id: wallet.id, text: "too late"})
+        } catch (e) {
+            error = e
+        }
+        expect(error.message).contains("Transaction is immutable")
+    });
+
+    it("Empty comment is rejected", async function () {
+        let error
+        try {
+            await actor.comment({transaction_id: pending.common.id, text: "   "})
+        } catch (e) {
+            error = e
+        }
+        expect(error.message).contains("Comment should be 1 to 1024 bytes long")
+    });
+});
@@ -0,0 +1,59 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute, sleep} from "../util/call.util";
+import {expect} from "chai";
+import {fromHexString, principalToAddressBytes} from "ictool";
+import {Principal} from "@dfinity/principal";
+import {getVaultActor, requestCreateWalletTransaction} from "./helper";
+import {Network, VaultManager, WalletCreateTransaction} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+describe("Deposits", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let manager: VaultManager;
+    let actor;
+    let walletUid;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/ledger.sh`))
+        await console.log(execute(`./test/resource/vault_icp_index.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        let createWallet = await requestCreateWalletTransaction(manager, "depositWallet", Network.IC) as Array<WalletCreateTransaction>
+        walletUid = createWallet[0].uid
+        await manager.execute()
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    //the index pulls new blocks from the ledger every few seconds
+    async function fillWallet() {
+        let walBytes = principalToAddressBytes(Principal.fromText(canister_id) as any, fromHexString(walletUid))
+        DFX.LEDGER_FILL_BALANCE(walBytes.toString().replaceAll(',', ';'))
+        await sleep(5)
+    }
+
+    it("First sync picks up deposits made after the wallet was created", async function () {
+        await fillWallet()
+        let added = await actor.sync_deposits() as bigint
+        expect(added).eq(1n)
+        let page = await actor.get_deposits([walletUid], 0n, 10n) as any
+        expect(page.total).eq(1n)
+        expect(page.deposits[0].amount).eq(100_000_000n)
+    });
+
+    it("Next sync continues from the cursor", async function () {
+        expect(await actor.sync_deposits()).eq(0n)
+        await fillWallet()
+        expect(await actor.sync_deposits()).eq(1n)
+        let page = await actor.get_deposits([walletUid], 0n, 10n) as any
+        expect(page.total).eq(2n)
+    });
+});
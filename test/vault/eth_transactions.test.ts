@@ -0,0 +1,126 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {getAddress, keccak256, parseTransaction} from "ethers/lib/utils";
+import {getRawTransactionById, getVaultActor, requestRawCreatePolicyTransaction, requestRawTransaction} from "./helper";
+import {VaultManager} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+//the raw transactions are only signed, nothing is broadcast
+describe("ETH Transactions", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let manager: VaultManager;
+    let actor;
+    let ethWalletUid;
+    let denyWalletUid;
+    let walletAddress;
+    let to = "0x3535353535353535353535353535353535353535"
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/vault_eth.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        ethWalletUid = await createEthWallet("ethWallet")
+        denyWalletUid = await createEthWallet("denyWallet")
+        await requestRawCreatePolicyTransaction(actor, {ETH: null}, 1, 0n, [ethWalletUid], undefined)
+        await requestRawCreatePolicyTransaction(actor, {ETH: null}, 1, 0n, [denyWalletUid], {DenyNotWhitelisted: null})
+        await manager.execute()
+        walletAddress = await actor.get_eth_address(ethWalletUid)
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    it("Wallet address is an EIP-55 checksummed address", async function () {
+        expect(walletAddress).eq(getAddress(walletAddress.toLowerCase()))
+        expect(await actor.get_eth_address(denyWalletUid)).not.eq(walletAddress)
+    });
+
+    it("Transfer is signed as an EIP-1559 transaction of the wallet address", async function () {
+        let transfer = await requestEthTransfer(ethWalletUid, 1_000_000_000_000_000n, [0xde, 0xad])
+        await manager.execute()
+        transfer = await getRawTransactionById(actor, transfer.common.id)
+        expect(transfer.common.state).deep.eq({Executed: null})
+        expect(transfer.policy.length).eq(1)
+        let raw = transfer.signed_transaction[0]
+        expect(transfer.transaction_hash[0]).eq(keccak256(raw))
+        let parsed = parseTransaction(raw)
+        expect(parsed.type).eq(2)
+        expect(parsed.chainId).eq(11155111)
+        expect(parsed.nonce).eq(7)
+        expect(parsed.gasLimit.toString()).eq("21000")
+        expect(parsed.maxPriorityFeePerGas.toString()).eq("1000000000")
+        expect(parsed.maxFeePerGas.toString()).eq("30000000000")
+        expect(parsed.to).eq(getAddress(to))
+        expect(parsed.value.toString()).eq("1000000000000000")
+        expect(parsed.data).eq("0xdead")
+        expect(parsed.from).eq(walletAddress)
+    });
+
+    it("Transfer above the u64 range is still matched by the wallet policy", async function () {
+        let transfer = await requestEthTransfer(ethWalletUid, 100_000_000_000_000_000_000n, [])
+        await manager.execute()
+        transfer = await getRawTransactionById(actor, transfer.common.id)
+        expect(transfer.common.state).deep.eq({Executed: null})
+        expect(parseTransaction(transfer.signed_transaction[0]).value.toString()).eq("100000000000000000000")
+    });
+
+    it("Deny policy fails transfers to unknown destinations", async function () {
+        let transfer = await requestEthTransfer(denyWalletUid, 1n, [])
+        await manager.execute()
+        transfer = await getRawTransactionById(actor, transfer.common.id)
+        expect(transfer.common.state).deep.eq({Failed: null})
+        expect(transfer.common.error).deep.eq([{DestinationNotAllowed: null}])
+    });
+
+    it("Deny policy lets transfers to the address book through", async function () {
+        await requestRawTransaction(actor, {
+            AddressBookAddTransactionRequestV: {uid: "eth", label: "eth", address: {ETH: to}, batch_uid: []}
+        })
+        await manager.execute()
+        let transfer = await requestEthTransfer(denyWalletUid, 1n, [])
+        await manager.execute()
+        transfer = await getRawTransactionById(actor, transfer.common.id)
+        expect(transfer.common.state).deep.eq({Executed: null})
+    });
+
+    it("Transfer from a wallet without an ETH policy fails", async function () {
+        let walletUid = await createEthWallet("noPolicyWallet")
+        let transfer = await requestEthTransfer(walletUid, 1n, [])
+        await manager.execute()
+        transfer = await getRawTransactionById(actor, transfer.common.id)
+        expect(transfer.common.error).deep.eq([{CouldNotDefinePolicy: null}])
+    });
+
+    async function createEthWallet(name: string) {
+        let wallet = await requestRawTransaction(actor, {
+            WalletCreateTransactionRequestV: {uid: "", name, network: {ETH: null}, batch_uid: []}
+        })
+        await manager.execute()
+        return (await getRawTransactionById(actor, wallet.common.id)).uid
+    }
+
+    async function requestEthTransfer(wallet: string, value: bigint, data: Array<number>) {
+        return await requestRawTransaction(actor, {
+            EthTransferTransactionRequestV: {
+                wallet,
+                chain_id: 11155111n,
+                nonce: 7n,
+                max_priority_fee_per_gas: 1_000_000_000n,
+                max_fee_per_gas: 30_000_000_000n,
+                gas_limit: 21000n,
+                to,
+                value,
+                data: data.length === 0 ? [] : [data],
+                memo: []
+            }
+        })
+    }
+});
@@ -0,0 +1,126 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {Principal} from "@dfinity/principal";
+import {fromHexString, principalToAddressBytes} from "ictool";
+import {getRawTransactionById, getVaultActor, requestCreateWalletTransaction, requestRawTransaction} from "./helper";
+import {Network, VaultManager, WalletCreateTransaction} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+//http_request is called directly, through a gateway the export is fetched from https://<vault id>.raw.icp0.io
+describe("Export", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let manager: VaultManager;
+    let actor;
+    let token: string;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/vault.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        await requestCreateWalletTransaction(manager, "exportWallet", Network.IC)
+        await manager.execute()
+        token = (await actor.issue_export_token()).token
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    function get(url: string, headers: Array<[string, string]> = []) {
+        return actor.http_request({method: "GET", url, headers, body: []})
+    }
+
+    function getHeader(response, name: string) {
+        return response.headers.find(([key, _]) => key.toLowerCase() === name.toLowerCase())?.[1]
+    }
+
+    it("Bearer token gives the transactions page", async function () {
+        let response = await get("/transactions.json", [["Authorization", `Bearer ${token}`]])
+        expect(response.status_code).eq(200)
+        let page = JSON.parse(new TextDecoder().decode(new Uint8Array(response.body)))
+        expect(page.total).eq(2)
+        expect(page.transactions.map((t) => t.type)).deep.eq(["MemberCreateTransactionV2", "WalletCreateTransaction"])
+        expect(getHeader(response, "X-Total-Count")).eq("2")
+        expect(getHeader(response, "IC-CertificateExpression")).contains("no_certification")
+    });
+
+    it("Query parameter token gives the csv", async function () {
+        let response = await get(`/transactions.csv?token=${token}&limit=1`)
+        expect(response.status_code).eq(200)
+        let lines = new TextDecoder().decode(new Uint8Array(response.body)).trim().split("\n")
+        expect(lines[0]).eq("id,type,state,currency,amount,wallet,destination,block_index,approvals,memo,created_date")
+        expect(lines.length).eq(2)
+    });
+
+    it("Missing and tampered tokens are rejected", async function () {
+        let missing = await get("/transactions.json")
+        expect(missing.status_code).eq(403)
+        let tampered = token.slice(0, -1) + (token.endsWith("0") ? "1" : "0")
+        let rejected = await get("/transactions.json", [["Authorization", `Bearer ${tampered}`]])
+        expect(rejected.status_code).eq(403)
+        expect(new TextDecoder().decode(new Uint8Array(rejected.body))).eq("Export token is not valid")
+        expect(getHeader(rejected, "IC-CertificateExpression")).contains("no_certification")
+    });
+});
+
+describe("Export Block Index", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let ledger = Principal.fromText("ryjl3-tyaaa-aaaaa-aaaba-cai")
+    let manager: VaultManager;
+    let actor;
+    let transfer;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/ledger.sh`))
+        await console.log(execute(`./test/resource/vault.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        let wallet = await requestCreateWalletTransaction(manager, "exportWallet", Network.IC) as Array<WalletCreateTransaction>
+        let walletUid = wallet[0].uid
+        await requestRawTransaction(actor, {
+            ICRC1CanistersAddTransactionRequestV: {index_canister: [], ledger_canister: ledger, batch_uid: []}
+        })
+        await manager.execute()
+        let walBytes = principalToAddressBytes(Principal.fromText(canister_id) as any, fromHexString(walletUid))
+        DFX.LEDGER_FILL_BALANCE(walBytes.toString().replaceAll(',', ';'))
+        transfer = await requestRawTransaction(actor, {
+            TransferICRC1QuorumTransactionRequestV: {
+                to_principal: admin_identity.getPrincipal(),
+                to_subaccount: [],
+                memo: [],
+                ledger_id: ledger,
+                wallet: walletUid,
+                amount: 1000n,
+                icrc1_memo: []
+            }
+        })
+        await manager.execute()
+        transfer = await getRawTransactionById(actor, transfer.common.id)
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    it("ICRC-1 transfer is exported with its ledger block index", async function () {
+        expect(transfer.common.state).deep.eq({Executed: null})
+        let token = (await actor.issue_export_token()).token
+        let response = await actor.http_request({method: "GET", url: `/transactions.json?token=${token}`, headers: [], body: []})
+        let page = JSON.parse(new TextDecoder().decode(new Uint8Array(response.body)))
+        let row = page.transactions.find((t) => BigInt(t.id) === transfer.common.id)
+        expect(row.type).eq("TransferICRC1QuorumTransaction")
+        expect(row.block_index).eq(transfer.block_index[0].toString())
+        expect(row.amount).eq("1000")
+    });
+});
@@ -29,6 +29,9 @@ import {
     WalletUpdateNameTransactionRequest,
     MemberExtendICRC1AccountRequest
 } from "@nfid/vaults";
+import {Identity} from "@dfinity/agent";
+import {getActor} from "../util/deployment.util";
+import {idlFactory} from "./sdk/vault_idl";
 
 
 export function verifyTransaction(expected: Transaction, actual: Transaction, trType) {
@@ -175,3 +178,32 @@ export function verifyApprove(expected: Approve, actual: Approve) {
     expect(expected.status).eq(actual.status)
     expect(expected.signer.toLowerCase()).eq(actual.signer.toLowerCase())
 }
+
+//requests which are not covered by @nfid/vaults yet go through the raw candid interface
+export async function getVaultActor(canisterId: string, identity: Identity) {
+    return await getActor(canisterId, identity, idlFactory)
+}
+
+export async function requestRawTransaction(actor, request): Promise<any> {
+    let response = await actor.request_transaction([request]) as Array<any>
+    return Object.values(response[0])[0]
+}
+
+export async function getRawTransactionById(actor, id: bigint): Promise<any> {
+    let all = await actor.get_transactions_all() as Array<any>
+    return all.map((t) => Object.values(t)[0] as any).find((t) => t.common.id === id)
+}
+
+export async function requestRawCreatePolicyTransaction(actor, currency, membersTr: number, amountTr: bigint, wallets: Array<string>, destination): Promise<any> {
+    return await requestRawTransaction(actor, {
+        PolicyCreateTransactionRequestV: {
+            uid: generateRandomString(),
+            member_threshold: membersTr,
+            amount_threshold: amountTr,
+            wallets: wallets,
+            destination: destination === undefined ? [] : [destination],
+            currency: currency,
+            batch_uid: [],
+        }
+    })
+}
@@ -0,0 +1,204 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {Principal} from "@dfinity/principal";
+import {fromHexString, principalToAddress, principalToAddressBytes} from "ictool";
+import {
+    getRawTransactionById,
+    getVaultActor,
+    requestCreateMemberTransaction,
+    requestCreateWalletTransaction,
+    requestRawCreatePolicyTransaction,
+    requestRawTransaction
+} from "./helper";
+import {Network, VaultManager, VaultRole, WalletCreateTransaction} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+//the local ICP ledger is registered as an ICRC-1 ledger
+describe("ICRC-1 Policies", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let member_identity = getIdentity("87654321876543218765432187654322")
+    let ledger = Principal.fromText("ryjl3-tyaaa-aaaaa-aaaba-cai")
+    let receiver = Principal.fromText("sculj-2sjuf-dxqlm-dcv5y-hin5x-zfyvr-tzngf-bt5b5-dwhcc-zbsqf-rae")
+    let manager: VaultManager;
+    let actor;
+    let walletUid;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/ledger.sh`))
+        await console.log(execute(`./test/resource/vault.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        await requestCreateMemberTransaction(manager, principalToAddress(member_identity.getPrincipal() as any), "member", VaultRole.ADMIN)
+        let wallet = await requestCreateWalletTransaction(manager, "icrc1Wallet", Network.IC) as Array<WalletCreateTransaction>
+        walletUid = wallet[0].uid
+        await manager.execute()
+        let walBytes = principalToAddressBytes(Principal.fromText(canister_id) as any, fromHexString(walletUid))
+        DFX.LEDGER_FILL_BALANCE(walBytes.toString().replaceAll(',', ';'))
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    function requestQuorumTransfer(amount: bigint) {
+        return requestRawTransaction(actor, {
+            TransferICRC1QuorumTransactionRequestV: {
+                to_principal: receiver,
+                to_subaccount: [],
+                memo: [],
+                ledger_id: ledger,
+                wallet: walletUid,
+                amount,
+                icrc1_memo: []
+            }
+        })
+    }
+
+    it("Policy for a ledger that is not registered fails", async function () {
+        let policy = await requestRawCreatePolicyTransaction(actor, {ICRC1: ledger}, 1, 0n, [walletUid], undefined)
+        await manager.execute()
+        policy = await getRawTransactionById(actor, policy.common.id)
+        expect(policy.common.state).deep.eq({Failed: null})
+        expect(policy.common.error).deep.eq([{ICRC1CanisterNotExists: null}])
+    });
+
+    it("Quorum transfer picks the policy tier of its amount", async function () {
+        await requestRawTransaction(actor, {
+            ICRC1CanistersAddTransactionRequestV: {index_canister: [], ledger_canister: ledger, batch_uid: []}
+        })
+        await manager.execute()
+        let small = await requestRawCreatePolicyTransaction(actor, {ICRC1: ledger}, 1, 0n, [walletUid], undefined)
+        let large = await requestRawCreatePolicyTransaction(actor, {ICRC1: ledger}, 2, 1000n, [walletUid], undefined)
+        await manager.execute()
+
+        let executed = await requestQuorumTransfer(500n)
+        await manager.execute()
+        executed = await getRawTransactionById(actor, executed.common.id)
+        expect(executed.common.state).deep.eq({Executed: null})
+        expect(executed.common.threshold).deep.eq([1])
+        expect(executed.policy).deep.eq([small.uid])
+        expect(executed.block_index.length).eq(1)
+
+        let pending = await requestQuorumTransfer(5000n)
+        await manager.execute()
+        pending = await getRawTransactionById(actor, pending.common.id)
+        expect(pending.common.state).deep.eq({Pending: null})
+        expect(pending.common.threshold).deep.eq([2])
+        expect(pending.policy).deep.eq([large.uid])
+    });
+
+    it("ICP transfer and top-up requested in another currency are rejected", async function () {
+        let transferError
+        try {
+            await requestRawTransaction(actor, {
+                TransferTransactionRequestV: {
+                    memo: [],
+                    currency: {ICRC1: ledger},
+                    address: principalToAddress(receiver as any),
+                    wallet: walletUid,
+                    amount: 500n
+                }
+            })
+        } catch (e) {
+            transferError = e
+        }
+        expect(transferError.message).contains("Only ICP can be transferred from the ICP ledger")
+        let topUpError
+        try {
+            await requestRawTransaction(actor, {
+                TopUpTransactionRequestV: {currency: {Cycles: null}, wallet: walletUid, amount: 500n, canister: []}
+            })
+        } catch (e) {
+            topUpError = e
+        }
+        expect(topUpError.message).contains("Only ICP can be transferred from the ICP ledger")
+    });
+});
+
+describe("Member ICRC-1 Transfers", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let member_identity = getIdentity("87654321876543218765432187654322")
+    let ledger = Principal.fromText("ryjl3-tyaaa-aaaaa-aaaba-cai")
+    let receiver = Principal.fromText("sculj-2sjuf-dxqlm-dcv5y-hin5x-zfyvr-tzngf-bt5b5-dwhcc-zbsqf-rae")
+    let subaccount = new Uint8Array(32).fill(7)
+    let memo = new Uint8Array([1, 2, 3])
+    let manager: VaultManager;
+    let actor;
+    let memberActor;
+    let walletUid;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/ledger.sh`))
+        await console.log(execute(`./test/resource/vault.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        memberActor = await getVaultActor(canister_id, member_identity)
+        await requestCreateMemberTransaction(manager, principalToAddress(member_identity.getPrincipal() as any), "member", VaultRole.MEMBER)
+        let wallet = await requestCreateWalletTransaction(manager, "memberIcrc1Wallet", Network.IC) as Array<WalletCreateTransaction>
+        walletUid = wallet[0].uid
+        await manager.execute()
+        await requestRawTransaction(actor, {
+            ICRC1CanistersAddTransactionRequestV: {index_canister: [], ledger_canister: ledger, batch_uid: []}
+        })
+        await manager.execute()
+        let walBytes = principalToAddressBytes(Principal.fromText(canister_id) as any, fromHexString(walletUid))
+        DFX.LEDGER_FILL_BALANCE(walBytes.toString().replaceAll(',', ';'))
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    function requestMemberTransfer(amount: bigint) {
+        return requestRawTransaction(memberActor, {
+            TransferICRC1TransactionRequestV: {
+                to_principal: receiver,
+                to_subaccount: [subaccount],
+                memo: [],
+                ledger_id: ledger,
+                wallet: walletUid,
+                amount,
+                icrc1_memo: [memo]
+            }
+        })
+    }
+
+    it("Member transfer without a policy fails", async function () {
+        let tr = await requestMemberTransfer(100n)
+        await manager.execute()
+        tr = await getRawTransactionById(actor, tr.common.id)
+        expect(tr.common.state).deep.eq({Failed: null})
+        expect(tr.common.error).deep.eq([{CouldNotDefinePolicy: null}])
+    });
+
+    it("Member transfer is executed once the policy threshold is reached", async function () {
+        let policy = await requestRawCreatePolicyTransaction(actor, {ICRC1: ledger}, 2, 0n, [walletUid], undefined)
+        await manager.execute()
+        let tr = await requestMemberTransfer(100n)
+        await manager.execute()
+        tr = await getRawTransactionById(actor, tr.common.id)
+        expect(tr.common.state).deep.eq({Pending: null})
+        expect(tr.common.threshold).deep.eq([2])
+        expect(tr.policy).deep.eq([policy.uid])
+        expect(tr.common.initiator).eq(principalToAddress(member_identity.getPrincipal() as any))
+
+        await actor.approve([{transaction_id: tr.common.id, state: {Approved: null}, reason: []}])
+        await manager.execute()
+        tr = await getRawTransactionById(actor, tr.common.id)
+        expect(tr.common.state).deep.eq({Executed: null})
+        expect(tr.block_index.length).eq(1)
+        expect(Array.from(tr.to_subaccount[0])).deep.eq(Array.from(subaccount))
+        expect(Array.from(tr.icrc1_memo[0])).deep.eq(Array.from(memo))
+    });
+});
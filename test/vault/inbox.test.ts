@@ -0,0 +1,72 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {principalToAddress} from "ictool";
+import {
+    getVaultActor,
+    requestCreateMemberTransaction,
+    requestCreateWalletTransaction,
+    requestRawCreatePolicyTransaction,
+    requestRawTransaction
+} from "./helper";
+import {Network, VaultManager, VaultRole, WalletCreateTransaction} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+describe("Inbox", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let member_identity = getIdentity("87654321876543218765432187654322")
+    let manager: VaultManager;
+    let actor;
+    let memberActor;
+    let walletUid;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/vault.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        memberActor = await getVaultActor(canister_id, member_identity)
+        await requestCreateMemberTransaction(manager, principalToAddress(member_identity.getPrincipal() as any), "member", VaultRole.ADMIN)
+        let wallet = await requestCreateWalletTransaction(manager, "inboxWallet", Network.IC) as Array<WalletCreateTransaction>
+        walletUid = wallet[0].uid
+        await manager.execute()
+        //transfers up to 100 are not covered by any policy
+        await requestRawCreatePolicyTransaction(actor, {ICP: null}, 2, 100n, [walletUid], undefined)
+        await manager.execute()
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    function requestTransfer(amount: bigint) {
+        return requestRawTransaction(actor, {
+            TransferTransactionRequestV: {
+                memo: [],
+                currency: {ICP: null},
+                address: principalToAddress(admin_identity.getPrincipal() as any),
+                wallet: walletUid,
+                amount
+            }
+        })
+    }
+
+    it("Blocked transaction shows the error it will fail with", async function () {
+        let covered = await requestTransfer(1000n)
+        let uncovered = await requestTransfer(50n)
+        await manager.execute()
+        let pending = await memberActor.get_my_pending_actions()
+        let coveredAction = pending.actions.find((a) => Object.values(a.transaction)[0].common.id === covered.common.id)
+        expect(coveredAction.missing_approvals).eq(1)
+        expect(coveredAction.threshold_error).deep.eq([])
+        let uncoveredAction = pending.actions.find((a) => Object.values(a.transaction)[0].common.id === uncovered.common.id)
+        expect(uncoveredAction.blocked_by).deep.eq([covered.common.id])
+        expect(uncoveredAction.missing_approvals).eq(0)
+        expect(uncoveredAction.threshold_error).deep.eq([{CouldNotDefinePolicy: null}])
+    });
+});
@@ -0,0 +1,145 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {Principal} from "@dfinity/principal";
+import {createHash} from "crypto";
+import {readFileSync} from "fs";
+import {principalToAddress} from "ictool";
+import {getRawTransactionById, getVaultActor, requestCreateMemberTransaction, requestRawTransaction} from "./helper";
+import {VaultManager, VaultRole} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+describe("Managed Canister Transactions", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let manager: VaultManager;
+    let actor;
+    let managed: Principal;
+    let uploadedHash: string;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/vault.sh`))
+        execute(`dfx deploy minter_stub`)
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        managed = Principal.fromText(DFX.GET_CANISTER_ID("minter_stub"));
+        DFX.ADD_CONTROLLER(canister_id, "minter_stub");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    async function requestOperation(canister: Principal, operation) {
+        let tr = await requestRawTransaction(actor, {
+            ManagedCanisterOperationTransactionRequestV: {canister_id: canister, operation, memo: []}
+        })
+        await manager.execute()
+        return await getRawTransactionById(actor, tr.common.id)
+    }
+
+    it("The vault can not manage itself", async function () {
+        let add = await requestRawTransaction(actor, {
+            ManagedCanisterAddTransactionRequestV: {canister_id: Principal.fromText(canister_id), name: "vault", batch_uid: []}
+        })
+        await manager.execute()
+        add = await getRawTransactionById(actor, add.common.id)
+        expect(add.common.state).deep.eq({Failed: null})
+        expect(add.common.error).deep.eq([{CallTargetNotAllowed: null}])
+        let stop = await requestOperation(Principal.fromText(canister_id), {Stop: null})
+        expect(stop.common.state).deep.eq({Failed: null})
+        expect(stop.common.error).deep.eq([{CallTargetNotAllowed: null}])
+    });
+
+    it("Chunks over 1 MiB are rejected", async function () {
+        let error
+        try {
+            await actor.append_wasm_chunk(new Uint8Array(1024 * 1024 + 1))
+        } catch (e) {
+            error = e
+        }
+        expect(error.message).contains("Chunk is larger than")
+    });
+
+    it("Uploaded wasm is installed in chunks with the hash pinned on request", async function () {
+        let module = readFileSync(".dfx/local/canisters/minter_stub/minter_stub.wasm")
+        let half = Math.floor(module.length / 2)
+        await actor.append_wasm_chunk(module.subarray(0, half))
+        await actor.append_wasm_chunk(module.subarray(half))
+        let uploaded = await actor.commit_wasm_upload()
+        uploadedHash = createHash("sha256").update(module).digest("hex")
+        expect(uploaded.hash).eq(uploadedHash)
+        expect(uploaded.size).eq(BigInt(module.length))
+
+        await requestRawTransaction(actor, {
+            ManagedCanisterAddTransactionRequestV: {canister_id: managed, name: "stub", batch_uid: []}
+        })
+        await manager.execute()
+        let install = await requestOperation(managed, {
+            InstallCode: {mode: {upgrade: null}, wasm: {Upload: {hash: uploadedHash}}, arg: []}
+        })
+        expect(install.common.state).deep.eq({Executed: null})
+        expect(install.wasm_hash).deep.eq([uploadedHash])
+    });
+
+    it("Install of a wasm that is not uploaded is rejected on request", async function () {
+        let error
+        try {
+            await requestRawTransaction(actor, {
+                ManagedCanisterOperationTransactionRequestV: {
+                    canister_id: managed,
+                    operation: {InstallCode: {mode: {upgrade: null}, wasm: {Upload: {hash: "00"}}, arg: []}},
+                    memo: []
+                }
+            })
+        } catch (e) {
+            error = e
+        }
+        expect(error.message).contains("Wasm 00 is not uploaded")
+    });
+
+    it("Member install request is rejected before the wasm hash is pinned", async function () {
+        let member = getIdentity("87654321876543218765432187654323")
+        await requestCreateMemberTransaction(manager, principalToAddress(member.getPrincipal() as any), "plain member", VaultRole.MEMBER)
+        await manager.execute()
+        let memberActor = await getVaultActor(canister_id, member)
+        let error
+        try {
+            await requestRawTransaction(memberActor, {
+                ManagedCanisterOperationTransactionRequestV: {
+                    canister_id: managed,
+                    operation: {InstallCode: {mode: {upgrade: null}, wasm: {Upload: {hash: "00"}}, arg: []}},
+                    memo: []
+                }
+            })
+        } catch (e) {
+            error = e
+        }
+        expect(error.message).contains("Not permitted")
+    });
+
+    it("Uploads are capped until one is deleted", async function () {
+        for (let i = 1; i < 5; i++) {
+            await actor.append_wasm_chunk(new Uint8Array([i]))
+            await actor.commit_wasm_upload()
+        }
+        expect((await actor.get_uploaded_wasms()).length).eq(5)
+        await actor.append_wasm_chunk(new Uint8Array([5]))
+        let error
+        try {
+            await actor.commit_wasm_upload()
+        } catch (e) {
+            error = e
+        }
+        expect(error.message).contains("At most 5 wasms can be uploaded")
+        await actor.delete_uploaded_wasm(uploadedHash)
+        await actor.append_wasm_chunk(new Uint8Array([5]))
+        await actor.commit_wasm_upload()
+        expect((await actor.get_uploaded_wasms()).map(w => w.hash)).not.contains(uploadedHash)
+    });
+});
@@ -0,0 +1,126 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {Principal} from "@dfinity/principal";
+import {fromHexString, principalToAddress, principalToAddressBytes} from "ictool";
+import {
+    getRawTransactionById,
+    getVaultActor,
+    requestCreateMemberTransaction,
+    requestCreateWalletTransaction,
+    requestRawCreatePolicyTransaction,
+    requestRawTransaction
+} from "./helper";
+import {Network, VaultManager, VaultRole, WalletCreateTransaction} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+describe("Transaction Metadata", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let member_identity = getIdentity("87654321876543218765432187654322")
+    let manager: VaultManager;
+    let actor;
+    let memberActor;
+    let walletUid;
+    let payroll;
+    let hosting;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/ledger.sh`))
+        await console.log(execute(`./test/resource/vault.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        memberActor = await getVaultActor(canister_id, member_identity)
+        await requestCreateMemberTransaction(manager, principalToAddress(member_identity.getPrincipal() as any), "member", VaultRole.ADMIN)
+        let wallet = await requestCreateWalletTransaction(manager, "metadataWallet", Network.IC) as Array<WalletCreateTransaction>
+        walletUid = wallet[0].uid
+        await manager.execute()
+        let walBytes = principalToAddressBytes(Principal.fromText(canister_id) as any, fromHexString(walletUid))
+        DFX.LEDGER_FILL_BALANCE(walBytes.toString().replaceAll(',', ';'))
+        await requestRawCreatePolicyTransaction(actor, {ICP: null}, 2, 0n, [walletUid], undefined)
+        await manager.execute()
+        payroll = await requestTransfer(100n)
+        hosting = await requestTransfer(200n)
+        await manager.execute()
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    function requestTransfer(amount: bigint) {
+        return requestRawTransaction(actor, {
+            TransferTransactionRequestV: {
+                memo: [],
+                currency: {ICP: null},
+                address: principalToAddress(admin_identity.getPrincipal() as any),
+                wallet: walletUid,
+                amount
+            }
+        })
+    }
+
+    function metadata(tags: Array<string>, invoice: string) {
+        return {tags, invoice_id: [invoice], reference_id: [], counterparty: ["  ACME  "], cost_center: []}
+    }
+
+    async function approveAndExecute(id: bigint) {
+        await memberActor.approve([{transaction_id: id, state: {Approved: null}, reason: []}])
+        await manager.execute()
+    }
+
+    it("Initiator tags a pending transaction", async function () {
+        let updated = await actor.update_metadata({transaction_id: payroll.common.id, metadata: metadata(["payroll", " ops ", "ops"], "INV-1")})
+        let common = (Object.values(updated)[0] as any).common
+        expect(common.metadata).deep.eq([{
+            tags: ["payroll", "ops"],
+            invoice_id: ["INV-1"],
+            reference_id: [],
+            counterparty: ["ACME"],
+            cost_center: []
+        }])
+        //the second transfer is blocked behind the first one, it can be tagged as well
+        await actor.update_metadata({transaction_id: hosting.common.id, metadata: metadata(["ops"], "INV-2")})
+    });
+
+    it("Only the initiator can update the metadata", async function () {
+        let error
+        try {
+            await memberActor.update_metadata({transaction_id: payroll.common.id, metadata: metadata(["other"], "INV-3")})
+        } catch (e) {
+            error = e
+        }
+        expect(error.message).contains("Only the initiator can update the metadata")
+    });
+
+    it("Metadata is locked once the transaction is executed", async function () {
+        await approveAndExecute(payroll.common.id)
+        await approveAndExecute(hosting.common.id)
+        let executed = await getRawTransactionById(actor, payroll.common.id)
+        expect(executed.common.state).deep.eq({Executed: null})
+        let error
+        try {
+            await actor.update_metadata({transaction_id: payroll.common.id, metadata: metadata(["late"], "INV-4")})
+        } catch (e) {
+            error = e
+        }
+        expect(error.message).contains("Transaction is immutable")
+    });
+
+    it("Outgoing amounts are aggregated by tag", async function () {
+        let report = await actor.get_outgoing_amounts({period: {Month: null}, from: [], to: []})
+        let month = new Date().toISOString().slice(0, 7)
+        expect(report.map((e) => [e.period, e.wallet, e.tag, e.amount, e.count])).deep.eq([
+            [month, walletUid, ["ops"], 300n, 2n],
+            [month, walletUid, ["payroll"], 100n, 1n],
+        ])
+        expect(report[0].currency).deep.eq({ICP: null})
+        let future = await actor.get_outgoing_amounts({period: {Month: null}, from: [BigInt(Date.now() + 60_000) * 1_000_000n], to: []})
+        expect(future).deep.eq([])
+    });
+});
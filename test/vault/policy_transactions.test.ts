@@ -4,7 +4,11 @@ import {principalToAddress} from "ictool";
 import {execute} from "../util/call.util";
 import {expect} from "chai";
 import {
+    getRawTransactionById,
     getTransactionByIdFromGetAllTrs,
+    getVaultActor,
+    requestRawCreatePolicyTransaction,
+    requestRawTransaction,
     requestCreatePolicyTransaction,
     requestCreateWalletTransaction,
     requestRemovePolicyTransaction,
@@ -209,6 +213,39 @@ describe("Policy Transactions", () => {
         expect(hasOwnProperty(transaction2.error, "UIDAlreadyExists")).eq(true)
     });
 
+    it("Deny policy fails quorum transfers to unknown destinations", async function () {
+        let actor = await getVaultActor(canister_id, admin_identity)
+        let walletResponse = await requestCreateWalletTransaction(manager, "denyWallet", Network.IC);
+        let walletUid = (walletResponse[0] as WalletCreateTransaction).uid
+        await requestRawCreatePolicyTransaction(actor, {ICP: null}, 1, 0n, [walletUid], {DenyNotWhitelisted: null})
+        await manager.execute()
+        let unknown = principalToAddress(getIdentity("87654321876543218765432187654329").getPrincipal() as any)
+        let transfer = await requestRawTransaction(actor, {
+            TransferQuorumTransactionRequestV: {memo: [], currency: {ICP: null}, address: unknown, wallet: walletUid, amount: 100n}
+        })
+        await manager.execute()
+        transfer = await getRawTransactionById(actor, transfer.common.id)
+        expect(transfer.common.state).deep.eq({Failed: null})
+        expect(transfer.common.error).deep.eq([{DestinationNotAllowed: null}])
+    });
+
+    it("Deny policy lets quorum transfers to the address book through", async function () {
+        let actor = await getVaultActor(canister_id, admin_identity)
+        let state = await actor.get_state([]) as any
+        let walletUid = state.wallets.find((w) => w.name === "denyWallet").uid
+        let known = principalToAddress(getIdentity("87654321876543218765432187654328").getPrincipal() as any)
+        await requestRawTransaction(actor, {
+            AddressBookAddTransactionRequestV: {uid: "known", label: "known", address: {AccountIdentifier: known}, batch_uid: []}
+        })
+        await manager.execute()
+        let transfer = await requestRawTransaction(actor, {
+            TransferQuorumTransactionRequestV: {memo: [], currency: {ICP: null}, address: known, wallet: walletUid, amount: 100n}
+        })
+        await manager.execute()
+        transfer = await getRawTransactionById(actor, transfer.common.id)
+        expect(transfer.common.error).not.deep.eq([{DestinationNotAllowed: null}])
+    });
+
     function buildExpectedPolicyCreateTransaction(actualTr, state) {
         let expectedApprove: Approve = {
             createdDate: actualTr.approves[0].createdDate,
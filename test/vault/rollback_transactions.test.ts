@@ -0,0 +1,99 @@
+import {DFX} from "../constanst/dfx.const";
+import {getActor, getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {principalToAddress} from "ictool";
+import {sha256} from "ethers/lib/utils";
+import {IDL} from "@dfinity/candid";
+import {readWasmFile} from "../vault_repo/vault_repo.test";
+import {getRawTransactionById, getVaultActor, requestCreateMemberTransaction, requestRawTransaction} from "./helper";
+import {VaultManager, VaultRole} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+const repoStubIdl = ({IDL}) => {
+    const VaultWasm = IDL.Record({
+        wasm_module: IDL.Vec(IDL.Nat8),
+        version: IDL.Text,
+        hash: IDL.Text,
+        description: IDL.Opt(IDL.Text),
+        schema_version: IDL.Opt(IDL.Nat32),
+    });
+    return IDL.Service({add_version: IDL.Func([VaultWasm], [], [])});
+};
+
+//repo_stub lets a version be published again with another schema
+describe("Rollback Transactions", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let member_identity = getIdentity("87654321876543218765432187654322")
+    let manager: VaultManager;
+    let actor;
+    let repo;
+    let schemaVersion: number;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        execute(`dfx deploy repo_stub --specified-id=7jlkn-paaaa-aaaap-abvpa-cai`)
+        await console.log(execute(`./test/resource/vault.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        repo = await getActor("7jlkn-paaaa-aaaap-abvpa-cai", admin_identity, repoStubIdl as IDL.InterfaceFactory)
+        schemaVersion = await actor.get_schema_version()
+        await requestCreateMemberTransaction(manager, principalToAddress(member_identity.getPrincipal() as any), "member", VaultRole.ADMIN)
+        await manager.execute()
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    async function publish(version: string, file: string, schema: Array<number>) {
+        let module = readWasmFile(file)
+        await repo.add_version({
+            wasm_module: Array.from(module),
+            version,
+            hash: sha256(module),
+            description: [],
+            schema_version: schema
+        })
+    }
+
+    async function requestRollback(version: string) {
+        let tr = await requestRawTransaction(actor, {VersionRollbackTransactionRequestV: {version, memo: ["rollback"]}})
+        await manager.execute()
+        return await getRawTransactionById(actor, tr.common.id)
+    }
+
+    it("Rollback to a version that is not older fails", async function () {
+        let tr = await requestRollback("99.0.0")
+        expect(tr.common.state).deep.eq({Failed: null})
+    });
+
+    it("Rollback to a build without a declared schema is incompatible", async function () {
+        await publish("0.0.2", "test/vault_repo/vault_002.wasm", [])
+        let tr = await requestRollback("0.0.2")
+        expect(tr.common.state).deep.eq({Failed: null})
+        expect(tr.common.error).deep.eq([{IncompatibleSchemaVersion: null}])
+        expect(tr.schema_version).deep.eq([])
+    });
+
+    it("Rollback to a build with another schema is incompatible", async function () {
+        await publish("0.0.1", "test/vault_repo/vault_001.wasm", [schemaVersion + 1])
+        let tr = await requestRollback("0.0.1")
+        expect(tr.common.state).deep.eq({Failed: null})
+        expect(tr.common.error).deep.eq([{IncompatibleSchemaVersion: null}])
+        expect(tr.schema_version).deep.eq([schemaVersion + 1])
+    });
+
+    it("Compatible rollback waits for every admin", async function () {
+        await publish("0.0.2", "test/vault_repo/vault_002.wasm", [schemaVersion])
+        let tr = await requestRollback("0.0.2")
+        expect(tr.common.state).deep.eq({Pending: null})
+        expect(tr.common.threshold).deep.eq([2])
+        expect(tr.common.error).deep.eq([])
+        expect(tr.hash).deep.eq([sha256(readWasmFile("test/vault_repo/vault_002.wasm"))])
+    });
+});
@@ -0,0 +1,1096 @@
+export const idlFactory = ({ IDL }) => {
+  const BitcoinNetwork = IDL.Variant({
+    'mainnet' : IDL.Null,
+    'regtest' : IDL.Null,
+    'testnet' : IDL.Null,
+  });
+  const Conf = IDL.Record({
+    'cketh_ledger' : IDL.Opt(IDL.Text),
+    'ecdsa_key_name' : IDL.Opt(IDL.Text),
+    'origins' : IDL.Vec(IDL.Text),
+    'ckbtc_minter' : IDL.Opt(IDL.Text),
+    'eth_rpc_url' : IDL.Opt(IDL.Text),
+    'icp_index' : IDL.Opt(IDL.Text),
+    'btc_network' : IDL.Opt(BitcoinNetwork),
+    'ckbtc_ledger' : IDL.Opt(IDL.Text),
+    'repo_canister' : IDL.Text,
+    'cketh_minter' : IDL.Opt(IDL.Text),
+    'evm_rpc_canister' : IDL.Opt(IDL.Text),
+    'release_channel' : IDL.Opt(IDL.Text),
+  });
+  const TransactionState = IDL.Variant({
+    'Blocked' : IDL.Null,
+    'Failed' : IDL.Null,
+    'Approved' : IDL.Null,
+    'Rejected' : IDL.Null,
+    'Executed' : IDL.Null,
+    'Purged' : IDL.Null,
+    'Pending' : IDL.Null,
+  });
+  const TransactionApproveRequest = IDL.Record({
+    'transaction_id' : IDL.Nat64,
+    'state' : TransactionState,
+    'reason' : IDL.Opt(IDL.Text),
+  });
+  const WithdrawalStatus = IDL.Variant({
+    'Finalized' : IDL.Record({ 'txid' : IDL.Text }),
+    'AmountTooLow' : IDL.Null,
+    'Unknown' : IDL.Null,
+    'Submitted' : IDL.Record({ 'txid' : IDL.Text }),
+    'Reimbursed' : IDL.Record({ 'txid' : IDL.Opt(IDL.Text) }),
+    'Pending' : IDL.Null,
+  });
+  const WithdrawalKind = IDL.Variant({
+    'CkBTC' : IDL.Null,
+    'CkETH' : IDL.Null,
+  });
+  const TransactionMetadata = IDL.Record({
+    'reference_id' : IDL.Opt(IDL.Text),
+    'invoice_id' : IDL.Opt(IDL.Text),
+    'tags' : IDL.Vec(IDL.Text),
+    'cost_center' : IDL.Opt(IDL.Text),
+    'counterparty' : IDL.Opt(IDL.Text),
+  });
+  const VaultError = IDL.Variant({
+    'AddressAlreadyExists' : IDL.Null,
+    'InvalidAddress' : IDL.Null,
+    'ControllersUpdateError' : IDL.Record({ 'message' : IDL.Text }),
+    'ICRC1CanisterNotExists' : IDL.Null,
+    'TransactionNotExists' : IDL.Null,
+    'RetryWindowExpired' : IDL.Null,
+    'CanisterNotManaged' : IDL.Null,
+    'WalletNotExists' : IDL.Null,
+    'TransactionNotRetryable' : IDL.Null,
+    'CanisterAlreadyManaged' : IDL.Null,
+    'CouldNotDefinePolicy' : IDL.Null,
+    'ThresholdAlreadyExists' : IDL.Null,
+    'InvalidUID' : IDL.Null,
+    'IncompatibleSchemaVersion' : IDL.Null,
+    'QuorumNotReachable' : IDL.Null,
+    'CanisterReject' : IDL.Record({ 'message' : IDL.Text }),
+    'InvalidNetwork' : IDL.Null,
+    'CallTargetNotAllowed' : IDL.Null,
+    'MemberNotExists' : IDL.Null,
+    'DestinationNotAllowed' : IDL.Null,
+    'MemberAlreadyExists' : IDL.Null,
+    'ThresholdDefineError' : IDL.Record({ 'message' : IDL.Text }),
+    'UIDAlreadyExists' : IDL.Null,
+    'WalletArchived' : IDL.Null,
+    'PolicyNotExists' : IDL.Null,
+    'AddressNotExists' : IDL.Null,
+    'WalletNotEmpty' : IDL.Null,
+  });
+  const Approve = IDL.Record({
+    'status' : TransactionState,
+    'signer' : IDL.Text,
+    'created_date' : IDL.Nat64,
+    'reason' : IDL.Opt(IDL.Text),
+  });
+  const Comment = IDL.Record({
+    'text' : IDL.Text,
+    'author' : IDL.Text,
+    'created_date' : IDL.Nat64,
+  });
+  const BasicTransactionFields = IDL.Record({
+    'id' : IDL.Nat64,
+    'threshold' : IDL.Opt(IDL.Nat8),
+    'initiator' : IDL.Text,
+    'metadata' : IDL.Opt(TransactionMetadata),
+    'modified_date' : IDL.Nat64,
+    'memo' : IDL.Opt(IDL.Text),
+    'error' : IDL.Opt(VaultError),
+    'state' : TransactionState,
+    'approves' : IDL.Vec(Approve),
+    'is_vault_state' : IDL.Bool,
+    'comments' : IDL.Opt(IDL.Vec(Comment)),
+    'created_date' : IDL.Nat64,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const WithdrawalTransaction = IDL.Record({
+    'status' : IDL.Opt(WithdrawalStatus),
+    'withdrawal_id' : IDL.Opt(IDL.Nat64),
+    'kind' : WithdrawalKind,
+    'minter' : IDL.Principal,
+    'approve_block_index' : IDL.Opt(IDL.Nat),
+    'ledger' : IDL.Principal,
+    'address' : IDL.Text,
+    'wallet' : IDL.Text,
+    'common' : BasicTransactionFields,
+    'amount' : IDL.Nat,
+  });
+  const ControllersUpdateTransaction = IDL.Record({
+    'principals' : IDL.Vec(IDL.Principal),
+    'common' : BasicTransactionFields,
+    'current_controllers' : IDL.Vec(IDL.Principal),
+  });
+  const Network = IDL.Variant({
+    'IC' : IDL.Null,
+    'BTC' : IDL.Null,
+    'ETH' : IDL.Null,
+  });
+  const WalletCreateTransaction = IDL.Record({
+    'uid' : IDL.Text,
+    'name' : IDL.Text,
+    'network' : Network,
+    'common' : BasicTransactionFields,
+  });
+  const EthTransferTransaction = IDL.Record({
+    'to' : IDL.Text,
+    'transaction_hash' : IDL.Opt(IDL.Text),
+    'value' : IDL.Nat,
+    'max_priority_fee_per_gas' : IDL.Nat,
+    'data' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'max_fee_per_gas' : IDL.Nat,
+    'chain_id' : IDL.Nat64,
+    'nonce' : IDL.Nat64,
+    'gas_limit' : IDL.Nat64,
+    'wallet' : IDL.Text,
+    'common' : BasicTransactionFields,
+    'signed_transaction' : IDL.Opt(IDL.Text),
+    'policy' : IDL.Opt(IDL.Text),
+  });
+  const DestinationScope = IDL.Variant({
+    'NotWhitelisted' : IDL.Null,
+    'DenyNotWhitelisted' : IDL.Null,
+    'Whitelisted' : IDL.Null,
+  });
+  const Currency = IDL.Variant({
+    'BTC' : IDL.Null,
+    'ETH' : IDL.Null,
+    'ICP' : IDL.Null,
+    'ICRC1' : IDL.Principal,
+    'Cycles' : IDL.Null,
+  });
+  const PolicyCreateTransaction = IDL.Record({
+    'uid' : IDL.Text,
+    'destination' : IDL.Opt(DestinationScope),
+    'member_threshold' : IDL.Nat8,
+    'amount_threshold' : IDL.Nat64,
+    'wallets' : IDL.Vec(IDL.Text),
+    'currency' : Currency,
+    'common' : BasicTransactionFields,
+  });
+  const ManagedCanisterAddTransaction = IDL.Record({
+    'name' : IDL.Text,
+    'canister_id' : IDL.Principal,
+    'common' : BasicTransactionFields,
+  });
+  const VaultRole = IDL.Variant({ 'Member' : IDL.Null, 'Admin' : IDL.Null });
+  const MemberUpdateRoleTransaction = IDL.Record({
+    'role' : VaultRole,
+    'member_id' : IDL.Text,
+    'common' : BasicTransactionFields,
+  });
+  const CanisterPermissionUpdateTransaction = IDL.Record({
+    'threshold' : IDL.Opt(IDL.Nat8),
+    'canister' : IDL.Principal,
+    'common' : BasicTransactionFields,
+    'roles' : IDL.Vec(VaultRole),
+  });
+  const ICRC1CanistersRemoveTransaction = IDL.Record({
+    'ledger_canister' : IDL.Principal,
+    'common' : BasicTransactionFields,
+  });
+  const WalletArchiveTransaction = IDL.Record({
+    'uid' : IDL.Text,
+    'common' : BasicTransactionFields,
+    'require_zero_balance' : IDL.Bool,
+  });
+  const CanisterSnapshot = IDL.Record({
+    'id' : IDL.Vec(IDL.Nat8),
+    'total_size' : IDL.Nat64,
+    'taken_at_timestamp' : IDL.Nat64,
+  });
+  const CanisterInstallMode = IDL.Variant({
+    'reinstall' : IDL.Null,
+    'upgrade' : IDL.Null,
+    'install' : IDL.Null,
+  });
+  const WasmSource = IDL.Variant({
+    'Repo' : IDL.Record({ 'version' : IDL.Text }),
+    'Upload' : IDL.Record({ 'hash' : IDL.Text }),
+  });
+  const CanisterSettings = IDL.Record({
+    'freezing_threshold' : IDL.Opt(IDL.Nat),
+    'controllers' : IDL.Opt(IDL.Vec(IDL.Principal)),
+    'reserved_cycles_limit' : IDL.Opt(IDL.Nat),
+    'memory_allocation' : IDL.Opt(IDL.Nat),
+    'compute_allocation' : IDL.Opt(IDL.Nat),
+  });
+  const CanisterOperation = IDL.Variant({
+    'Start' : IDL.Null,
+    'DeleteSnapshot' : IDL.Record({ 'snapshot_id' : IDL.Vec(IDL.Nat8) }),
+    'Stop' : IDL.Null,
+    'InstallCode' : IDL.Record({
+      'arg' : IDL.Vec(IDL.Nat8),
+      'mode' : CanisterInstallMode,
+      'wasm' : WasmSource,
+    }),
+    'UpdateSettings' : IDL.Record({ 'settings' : CanisterSettings }),
+    'Delete' : IDL.Null,
+    'LoadSnapshot' : IDL.Record({ 'snapshot_id' : IDL.Vec(IDL.Nat8) }),
+    'TakeSnapshot' : IDL.Record({
+      'replace_snapshot' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    }),
+  });
+  const ManagedCanisterOperationTransaction = IDL.Record({
+    'snapshot' : IDL.Opt(CanisterSnapshot),
+    'canister_id' : IDL.Principal,
+    'operation' : CanisterOperation,
+    'common' : BasicTransactionFields,
+    'wasm_hash' : IDL.Opt(IDL.Text),
+  });
+  const TopUpTransaction = IDL.Record({
+    'block_index' : IDL.Opt(IDL.Nat64),
+    'currency' : Currency,
+    'canister' : IDL.Opt(IDL.Principal),
+    'wallet' : IDL.Text,
+    'created_at_time' : IDL.Opt(IDL.Nat64),
+    'common' : BasicTransactionFields,
+    'amount' : IDL.Nat64,
+    'policy' : IDL.Opt(IDL.Text),
+  });
+  const TopUpQuorumTransaction = IDL.Record({
+    'block_index' : IDL.Opt(IDL.Nat64),
+    'currency' : Currency,
+    'canister' : IDL.Opt(IDL.Principal),
+    'wallet' : IDL.Text,
+    'created_at_time' : IDL.Opt(IDL.Nat64),
+    'common' : BasicTransactionFields,
+    'amount' : IDL.Nat64,
+  });
+  const RetryTransaction = IDL.Record({
+    'transaction_id' : IDL.Nat64,
+    'common' : BasicTransactionFields,
+  });
+  const Account = IDL.Record({
+    'owner' : IDL.Principal,
+    'subaccount' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+  });
+  const ICRC2ApproveTransaction = IDL.Record({
+    'block_index' : IDL.Opt(IDL.Nat),
+    'ledger_id' : IDL.Principal,
+    'wallet' : IDL.Text,
+    'common' : BasicTransactionFields,
+    'amount' : IDL.Nat64,
+    'expected_allowance' : IDL.Opt(IDL.Nat64),
+    'expires_at' : IDL.Opt(IDL.Nat64),
+    'spender' : Account,
+  });
+  const VersionRollbackTransaction = IDL.Record({
+    'hash' : IDL.Opt(IDL.Text),
+    'version' : IDL.Text,
+    'schema_version' : IDL.Opt(IDL.Nat32),
+    'initial_version' : IDL.Text,
+    'common' : BasicTransactionFields,
+  });
+  const ICRC1CanistersAddTransaction = IDL.Record({
+    'index_canister' : IDL.Opt(IDL.Principal),
+    'ledger_canister' : IDL.Principal,
+    'common' : BasicTransactionFields,
+  });
+  const Address = IDL.Variant({
+    'BTC' : IDL.Text,
+    'ETH' : IDL.Text,
+    'ICRC1' : Account,
+    'AccountIdentifier' : IDL.Text,
+  });
+  const AddressBookAddTransaction = IDL.Record({
+    'uid' : IDL.Text,
+    'label' : IDL.Text,
+    'address' : Address,
+    'common' : BasicTransactionFields,
+  });
+  const VaultNamingUpdateTransaction = IDL.Record({
+    'name' : IDL.Opt(IDL.Text),
+    'description' : IDL.Opt(IDL.Text),
+    'common' : BasicTransactionFields,
+  });
+  const TransferTransaction = IDL.Record({
+    'block_index' : IDL.Opt(IDL.Nat64),
+    'currency' : Currency,
+    'address' : IDL.Text,
+    'wallet' : IDL.Text,
+    'created_at_time' : IDL.Opt(IDL.Nat64),
+    'common' : BasicTransactionFields,
+    'amount' : IDL.Nat64,
+    'policy' : IDL.Opt(IDL.Text),
+  });
+  const PolicyRemoveTransaction = IDL.Record({
+    'uid' : IDL.Text,
+    'common' : BasicTransactionFields,
+  });
+  const TransferBTCTransaction = IDL.Record({
+    'txid' : IDL.Opt(IDL.Text),
+    'address' : IDL.Text,
+    'wallet' : IDL.Text,
+    'common' : BasicTransactionFields,
+    'amount' : IDL.Nat64,
+    'policy' : IDL.Opt(IDL.Text),
+  });
+  const MemberExtendICRC1AccountTransaction = IDL.Record({
+    'account' : Account,
+    'common' : BasicTransactionFields,
+  });
+  const PolicyUpdateTransaction = IDL.Record({
+    'uid' : IDL.Text,
+    'member_threshold' : IDL.Nat8,
+    'amount_threshold' : IDL.Nat64,
+    'common' : BasicTransactionFields,
+  });
+  const ICRC2RevokeTransaction = IDL.Record({
+    'block_index' : IDL.Opt(IDL.Nat),
+    'ledger_id' : IDL.Principal,
+    'wallet' : IDL.Text,
+    'common' : BasicTransactionFields,
+    'spender' : Account,
+  });
+  const TransferICRC1QuorumTransaction = IDL.Record({
+    'to_principal' : IDL.Principal,
+    'block_index' : IDL.Opt(IDL.Nat),
+    'to_subaccount' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'ledger_id' : IDL.Principal,
+    'icrc1_memo' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'wallet' : IDL.Text,
+    'created_at_time' : IDL.Opt(IDL.Nat64),
+    'common' : BasicTransactionFields,
+    'amount' : IDL.Nat64,
+    'policy' : IDL.Opt(IDL.Text),
+  });
+  const MemberCreateTransaction = IDL.Record({
+    'name' : IDL.Text,
+    'role' : VaultRole,
+    'member_id' : IDL.Text,
+    'common' : BasicTransactionFields,
+  });
+  const MemberUpdateNameTransaction = IDL.Record({
+    'name' : IDL.Text,
+    'member_id' : IDL.Text,
+    'common' : BasicTransactionFields,
+  });
+  const VersionUpgradeTransaction = IDL.Record({
+    'hash' : IDL.Opt(IDL.Text),
+    'version' : IDL.Text,
+    'initial_version' : IDL.Text,
+    'common' : BasicTransactionFields,
+  });
+  const AutoTopUpRuleRequest = IDL.Record({
+    'daily_cap' : IDL.Nat64,
+    'wallet' : IDL.Text,
+    'amount' : IDL.Nat64,
+    'min_cycles' : IDL.Nat64,
+  });
+  const AutoTopUpRuleUpdateTransaction = IDL.Record({
+    'rule' : IDL.Opt(AutoTopUpRuleRequest),
+    'common' : BasicTransactionFields,
+  });
+  const PurgeTransaction = IDL.Record({ 'common' : BasicTransactionFields });
+  const CanisterSettingsUpdateTransaction = IDL.Record({
+    'freezing_threshold' : IDL.Opt(IDL.Nat),
+    'reserved_cycles_limit' : IDL.Opt(IDL.Nat),
+    'common' : BasicTransactionFields,
+    'memory_allocation' : IDL.Opt(IDL.Nat),
+    'compute_allocation' : IDL.Opt(IDL.Nat),
+  });
+  const TransferQuorumTransaction = IDL.Record({
+    'block_index' : IDL.Opt(IDL.Nat64),
+    'currency' : Currency,
+    'address' : IDL.Text,
+    'wallet' : IDL.Text,
+    'created_at_time' : IDL.Opt(IDL.Nat64),
+    'common' : BasicTransactionFields,
+    'amount' : IDL.Nat64,
+  });
+  const QuorumUpdateTransaction = IDL.Record({
+    'common' : BasicTransactionFields,
+    'quorum' : IDL.Nat8,
+  });
+  const MemberCreateTransactionV2 = IDL.Record({
+    'name' : IDL.Text,
+    'role' : VaultRole,
+    'account' : Account,
+    'common' : BasicTransactionFields,
+  });
+  const WalletUpdateNameTransaction = IDL.Record({
+    'uid' : IDL.Text,
+    'name' : IDL.Text,
+    'common' : BasicTransactionFields,
+  });
+  const ManagedCanisterRemoveTransaction = IDL.Record({
+    'canister_id' : IDL.Principal,
+    'common' : BasicTransactionFields,
+  });
+  const AutoTopUpTransaction = IDL.Record({
+    'cycles_balance' : IDL.Nat64,
+    'block_index' : IDL.Opt(IDL.Nat64),
+    'wallet' : IDL.Text,
+    'created_at_time' : IDL.Opt(IDL.Nat64),
+    'common' : BasicTransactionFields,
+    'amount' : IDL.Nat64,
+  });
+  const MemberRemoveTransaction = IDL.Record({
+    'member_id' : IDL.Text,
+    'common' : BasicTransactionFields,
+  });
+  const CanisterCallTransaction = IDL.Record({
+    'arg' : IDL.Vec(IDL.Nat8),
+    'method' : IDL.Text,
+    'cycles' : IDL.Opt(IDL.Nat64),
+    'canister' : IDL.Principal,
+    'common' : BasicTransactionFields,
+    'reply' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'policy' : IDL.Opt(IDL.Text),
+  });
+  const TransferICRC1Transaction = IDL.Record({
+    'to_principal' : IDL.Principal,
+    'block_index' : IDL.Opt(IDL.Nat),
+    'to_subaccount' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'ledger_id' : IDL.Principal,
+    'icrc1_memo' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'wallet' : IDL.Text,
+    'created_at_time' : IDL.Opt(IDL.Nat64),
+    'common' : BasicTransactionFields,
+    'amount' : IDL.Nat64,
+    'policy' : IDL.Opt(IDL.Text),
+  });
+  const DepositCyclesTransaction = IDL.Record({
+    'canister' : IDL.Principal,
+    'common' : BasicTransactionFields,
+    'amount' : IDL.Nat64,
+    'policy' : IDL.Opt(IDL.Text),
+  });
+  const AddressBookRemoveTransaction = IDL.Record({
+    'uid' : IDL.Text,
+    'common' : BasicTransactionFields,
+  });
+  const TransactionCandid = IDL.Variant({
+    'WithdrawalTransactionV' : WithdrawalTransaction,
+    'ControllersUpdateTransactionV' : ControllersUpdateTransaction,
+    'WalletCreateTransactionV' : WalletCreateTransaction,
+    'EthTransferTransactionV' : EthTransferTransaction,
+    'PolicyCreateTransactionV' : PolicyCreateTransaction,
+    'ManagedCanisterAddTransactionV' : ManagedCanisterAddTransaction,
+    'MemberUpdateRoleTransactionV' : MemberUpdateRoleTransaction,
+    'CanisterPermissionUpdateTransactionV' : CanisterPermissionUpdateTransaction,
+    'ICRC1CanistersRemoveTransactionV' : ICRC1CanistersRemoveTransaction,
+    'WalletArchiveTransactionV' : WalletArchiveTransaction,
+    'ManagedCanisterOperationTransactionV' : ManagedCanisterOperationTransaction,
+    'TopUpTransactionV' : TopUpTransaction,
+    'TopUpQuorumTransactionV' : TopUpQuorumTransaction,
+    'RetryTransactionV' : RetryTransaction,
+    'ICRC2ApproveTransactionV' : ICRC2ApproveTransaction,
+    'VersionRollbackTransactionV' : VersionRollbackTransaction,
+    'ICRC1CanistersAddTransactionV' : ICRC1CanistersAddTransaction,
+    'AddressBookAddTransactionV' : AddressBookAddTransaction,
+    'VaultNamingUpdateTransactionV' : VaultNamingUpdateTransaction,
+    'TransferTransactionV' : TransferTransaction,
+    'PolicyRemoveTransactionV' : PolicyRemoveTransaction,
+    'TransferBTCTransactionV' : TransferBTCTransaction,
+    'MemberExtendICRC1AccountTransactionV' : MemberExtendICRC1AccountTransaction,
+    'PolicyUpdateTransactionV' : PolicyUpdateTransaction,
+    'ICRC2RevokeTransactionV' : ICRC2RevokeTransaction,
+    'TransferICRC1QuorumTransactionV' : TransferICRC1QuorumTransaction,
+    'MemberCreateTransactionV' : MemberCreateTransaction,
+    'MemberUpdateNameTransactionV' : MemberUpdateNameTransaction,
+    'UpgradeTransactionV' : VersionUpgradeTransaction,
+    'AutoTopUpRuleUpdateTransactionV' : AutoTopUpRuleUpdateTransaction,
+    'PurgeTransactionV' : PurgeTransaction,
+    'CanisterSettingsUpdateTransactionV' : CanisterSettingsUpdateTransaction,
+    'TransferQuorumTransactionV' : TransferQuorumTransaction,
+    'QuorumUpdateTransactionV' : QuorumUpdateTransaction,
+    'MemberCreateTransactionV2' : MemberCreateTransactionV2,
+    'WalletUpdateNameTransactionV' : WalletUpdateNameTransaction,
+    'ManagedCanisterRemoveTransactionV' : ManagedCanisterRemoveTransaction,
+    'AutoTopUpTransactionV' : AutoTopUpTransaction,
+    'MemberRemoveTransactionV' : MemberRemoveTransaction,
+    'CanisterCallTransactionV' : CanisterCallTransaction,
+    'TransferICRC1TransactionV' : TransferICRC1Transaction,
+    'DepositCyclesTransactionV' : DepositCyclesTransaction,
+    'AddressBookRemoveTransactionV' : AddressBookRemoveTransaction,
+  });
+  const TransactionCommentRequest = IDL.Record({
+    'transaction_id' : IDL.Nat64,
+    'text' : IDL.Text,
+  });
+  const UploadedWasm = IDL.Record({ 'hash' : IDL.Text, 'size' : IDL.Nat64 });
+  const WalletAllowance = IDL.Record({
+    'ledger_id' : IDL.Principal,
+    'wallet' : IDL.Text,
+    'allowance' : IDL.Nat,
+    'expires_at' : IDL.Opt(IDL.Nat64),
+    'spender' : Account,
+  });
+  const LedgerBalance = IDL.Record({
+    'balance' : IDL.Opt(IDL.Nat),
+    'error' : IDL.Opt(IDL.Text),
+    'ledger' : IDL.Principal,
+  });
+  const WalletBalance = IDL.Record({
+    'refreshed_date' : IDL.Nat64,
+    'wallet' : IDL.Text,
+    'balances' : IDL.Vec(LedgerBalance),
+  });
+  const Outpoint = IDL.Record({
+    'txid' : IDL.Vec(IDL.Nat8),
+    'vout' : IDL.Nat32,
+  });
+  const Utxo = IDL.Record({
+    'height' : IDL.Nat32,
+    'value' : IDL.Nat64,
+    'outpoint' : Outpoint,
+  });
+  const CanisterStatusType = IDL.Variant({
+    'stopped' : IDL.Null,
+    'stopping' : IDL.Null,
+    'running' : IDL.Null,
+  });
+  const DefiniteCanisterSettings = IDL.Record({
+    'freezing_threshold' : IDL.Nat,
+    'controllers' : IDL.Vec(IDL.Principal),
+    'reserved_cycles_limit' : IDL.Nat,
+    'memory_allocation' : IDL.Nat,
+    'compute_allocation' : IDL.Nat,
+  });
+  const VaultCanisterStatus = IDL.Record({
+    'refreshed_date' : IDL.Nat64,
+    'status' : CanisterStatusType,
+    'memory_size' : IDL.Nat,
+    'cycles' : IDL.Nat,
+    'settings' : DefiniteCanisterSettings,
+    'idle_cycles_burned_per_day' : IDL.Nat,
+    'module_hash' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'reserved_cycles' : IDL.Nat,
+  });
+  const Deposit = IDL.Record({
+    'block_index' : IDL.Nat64,
+    'from' : Address,
+    'memo' : IDL.Opt(IDL.Nat64),
+    'label' : IDL.Opt(IDL.Text),
+    'icrc1_memo' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'ledger' : IDL.Principal,
+    'timestamp' : IDL.Nat64,
+    'wallet' : IDL.Text,
+    'amount' : IDL.Nat,
+  });
+  const DepositPage = IDL.Record({
+    'total' : IDL.Nat64,
+    'deposits' : IDL.Vec(Deposit),
+  });
+  const PendingAction = IDL.Record({
+    'transaction' : TransactionCandid,
+    'blocked_by' : IDL.Vec(IDL.Nat64),
+    'missing_approvals' : IDL.Nat8,
+    'threshold_error' : IDL.Opt(VaultError),
+  });
+  const PendingActions = IDL.Record({
+    'last_acknowledged' : IDL.Opt(IDL.Nat64),
+    'actions' : IDL.Vec(PendingAction),
+    'unseen_activity' : IDL.Nat64,
+  });
+  const ReportPeriod = IDL.Variant({
+    'Day' : IDL.Null,
+    'Year' : IDL.Null,
+    'Month' : IDL.Null,
+  });
+  const OutgoingReportRequest = IDL.Record({
+    'to' : IDL.Opt(IDL.Nat64),
+    'period' : ReportPeriod,
+    'from' : IDL.Opt(IDL.Nat64),
+  });
+  const OutgoingReportEntry = IDL.Record({
+    'tag' : IDL.Opt(IDL.Text),
+    'period' : IDL.Text,
+    'count' : IDL.Nat64,
+    'currency' : Currency,
+    'wallet' : IDL.Text,
+    'amount' : IDL.Nat,
+  });
+  const Member = IDL.Record({
+    'modified_date' : IDL.Nat64,
+    'name' : IDL.Text,
+    'role' : VaultRole,
+    'account' : IDL.Opt(Account),
+    'member_id' : IDL.Text,
+    'created_date' : IDL.Nat64,
+  });
+  const AutoTopUpRule = IDL.Record({
+    'daily_cap' : IDL.Nat64,
+    'modified_date' : IDL.Nat64,
+    'wallet' : IDL.Text,
+    'amount' : IDL.Nat64,
+    'min_cycles' : IDL.Nat64,
+  });
+  const ManagedCanister = IDL.Record({
+    'modified_date' : IDL.Nat64,
+    'name' : IDL.Text,
+    'canister_id' : IDL.Principal,
+    'created_date' : IDL.Nat64,
+  });
+  const CanisterPermission = IDL.Record({
+    'threshold' : IDL.Opt(IDL.Nat8),
+    'modified_date' : IDL.Nat64,
+    'canister' : IDL.Principal,
+    'roles' : IDL.Vec(VaultRole),
+  });
+  const AddressBookEntry = IDL.Record({
+    'uid' : IDL.Text,
+    'modified_date' : IDL.Nat64,
+    'label' : IDL.Text,
+    'address' : Address,
+    'created_date' : IDL.Nat64,
+  });
+  const ICRC1 = IDL.Record({
+    'ledger' : IDL.Principal,
+    'index' : IDL.Opt(IDL.Principal),
+  });
+  const Wallet = IDL.Record({
+    'uid' : IDL.Text,
+    'icrc1_account' : IDL.Opt(IDL.Text),
+    'modified_date' : IDL.Nat64,
+    'name' : IDL.Text,
+    'network' : Network,
+    'account_identifier' : IDL.Opt(IDL.Text),
+    'created_date' : IDL.Nat64,
+  });
+  const Quorum = IDL.Record({
+    'modified_date' : IDL.Nat64,
+    'quorum' : IDL.Nat8,
+  });
+  const Policy = IDL.Record({
+    'uid' : IDL.Text,
+    'destination' : IDL.Opt(DestinationScope),
+    'member_threshold' : IDL.Nat8,
+    'modified_date' : IDL.Nat64,
+    'amount_threshold' : IDL.Nat64,
+    'wallets' : IDL.Vec(IDL.Text),
+    'currency' : Currency,
+    'created_date' : IDL.Nat64,
+  });
+  const VaultState = IDL.Record({
+    'members' : IDL.Vec(Member),
+    'auto_top_up' : IDL.Opt(AutoTopUpRule),
+    'name' : IDL.Opt(IDL.Text),
+    'managed_canisters' : IDL.Vec(ManagedCanister),
+    'description' : IDL.Opt(IDL.Text),
+    'canister_permissions' : IDL.Vec(CanisterPermission),
+    'address_book' : IDL.Vec(AddressBookEntry),
+    'icrc1_canisters' : IDL.Vec(ICRC1),
+    'wallets' : IDL.Vec(Wallet),
+    'quorum' : Quorum,
+    'archived_wallets' : IDL.Vec(Wallet),
+    'policies' : IDL.Vec(Policy),
+  });
+  const DiscrepancyKind = IDL.Variant({
+    'Unverifiable' : IDL.Null,
+    'BlockMismatch' : IDL.Null,
+    'StateCorrected' : IDL.Null,
+    'BlockNotFound' : IDL.Null,
+  });
+  const TransferDiscrepancy = IDL.Record({
+    'transaction_id' : IDL.Nat64,
+    'kind' : DiscrepancyKind,
+    'detected_date' : IDL.Nat64,
+    'message' : IDL.Text,
+  });
+  const HttpRequest = IDL.Record({
+    'url' : IDL.Text,
+    'method' : IDL.Text,
+    'body' : IDL.Vec(IDL.Nat8),
+    'headers' : IDL.Vec(IDL.Tuple(IDL.Text, IDL.Text)),
+  });
+  const HttpResponse = IDL.Record({
+    'body' : IDL.Vec(IDL.Nat8),
+    'headers' : IDL.Vec(IDL.Tuple(IDL.Text, IDL.Text)),
+    'status_code' : IDL.Nat16,
+  });
+  const ExportToken = IDL.Record({
+    'token' : IDL.Text,
+    'expires_at' : IDL.Nat64,
+  });
+  const AddressBookRemoveTransactionRequest = IDL.Record({
+    'uid' : IDL.Text,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const ICRC1CanistersRemoveTransactionRequest = IDL.Record({
+    'ledger_canister' : IDL.Principal,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const QuorumUpdateTransactionRequest = IDL.Record({
+    'quorum' : IDL.Nat8,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const VaultNamingUpdateTransactionRequest = IDL.Record({
+    'name' : IDL.Opt(IDL.Text),
+    'description' : IDL.Opt(IDL.Text),
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const ManagedCanisterRemoveTransactionRequest = IDL.Record({
+    'canister_id' : IDL.Principal,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const WalletArchiveTransactionRequest = IDL.Record({
+    'uid' : IDL.Text,
+    'require_zero_balance' : IDL.Bool,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const RetryTransactionRequest = IDL.Record({
+    'transaction_id' : IDL.Nat64,
+    'memo' : IDL.Opt(IDL.Text),
+  });
+  const AddressBookAddTransactionRequest = IDL.Record({
+    'uid' : IDL.Text,
+    'label' : IDL.Text,
+    'address' : Address,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const ICRC1CanistersAddTransactionRequest = IDL.Record({
+    'index_canister' : IDL.Opt(IDL.Principal),
+    'ledger_canister' : IDL.Principal,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const ControllersUpdateTransactionRequest = IDL.Record({
+    'principals' : IDL.Vec(IDL.Principal),
+  });
+  const MemberUpdateNameTransactionRequest = IDL.Record({
+    'name' : IDL.Text,
+    'member_id' : IDL.Text,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const TopUpTransactionRequest = IDL.Record({
+    'currency' : Currency,
+    'canister' : IDL.Opt(IDL.Principal),
+    'wallet' : IDL.Text,
+    'amount' : IDL.Nat64,
+  });
+  const CanisterCallTransactionRequest = IDL.Record({
+    'arg' : IDL.Vec(IDL.Nat8),
+    'method' : IDL.Text,
+    'memo' : IDL.Opt(IDL.Text),
+    'cycles' : IDL.Opt(IDL.Nat64),
+    'canister' : IDL.Principal,
+  });
+  const VersionRollbackTransactionRequest = IDL.Record({
+    'memo' : IDL.Opt(IDL.Text),
+    'version' : IDL.Text,
+  });
+  const AutoTopUpRuleUpdateTransactionRequest = IDL.Record({
+    'rule' : IDL.Opt(AutoTopUpRuleRequest),
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const ICRC2RevokeTransactionRequest = IDL.Record({
+    'memo' : IDL.Opt(IDL.Text),
+    'ledger_id' : IDL.Principal,
+    'wallet' : IDL.Text,
+    'spender' : Account,
+  });
+  const TransferBTCTransactionRequest = IDL.Record({
+    'memo' : IDL.Opt(IDL.Text),
+    'address' : IDL.Text,
+    'wallet' : IDL.Text,
+    'amount' : IDL.Nat64,
+  });
+  const TransferICRC1QuorumTransactionRequest = IDL.Record({
+    'to_principal' : IDL.Principal,
+    'to_subaccount' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'memo' : IDL.Opt(IDL.Text),
+    'ledger_id' : IDL.Principal,
+    'icrc1_memo' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'wallet' : IDL.Text,
+    'amount' : IDL.Nat64,
+  });
+  const WalletCreateTransactionRequest = IDL.Record({
+    'uid' : IDL.Text,
+    'name' : IDL.Text,
+    'network' : Network,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const MemberRemoveTransactionRequest = IDL.Record({
+    'member_id' : IDL.Text,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const WithdrawalTransactionRequest = IDL.Record({
+    'kind' : WithdrawalKind,
+    'memo' : IDL.Opt(IDL.Text),
+    'address' : IDL.Text,
+    'wallet' : IDL.Text,
+    'amount' : IDL.Nat,
+  });
+  const MemberCreateTransactionRequest = IDL.Record({
+    'name' : IDL.Text,
+    'role' : VaultRole,
+    'member_id' : IDL.Text,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const TransferICRC1TransactionRequest = IDL.Record({
+    'to_principal' : IDL.Principal,
+    'to_subaccount' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'memo' : IDL.Opt(IDL.Text),
+    'ledger_id' : IDL.Principal,
+    'icrc1_memo' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'wallet' : IDL.Text,
+    'amount' : IDL.Nat64,
+  });
+  const ICRC2ApproveTransactionRequest = IDL.Record({
+    'memo' : IDL.Opt(IDL.Text),
+    'ledger_id' : IDL.Principal,
+    'wallet' : IDL.Text,
+    'amount' : IDL.Nat64,
+    'expected_allowance' : IDL.Opt(IDL.Nat64),
+    'expires_at' : IDL.Opt(IDL.Nat64),
+    'spender' : Account,
+  });
+  const TransferTransactionRequest = IDL.Record({
+    'memo' : IDL.Opt(IDL.Text),
+    'currency' : Currency,
+    'address' : IDL.Text,
+    'wallet' : IDL.Text,
+    'amount' : IDL.Nat64,
+  });
+  const MemberCreateTransactionRequestV2 = IDL.Record({
+    'name' : IDL.Text,
+    'role' : VaultRole,
+    'account' : Account,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const ManagedCanisterOperationTransactionRequest = IDL.Record({
+    'memo' : IDL.Opt(IDL.Text),
+    'canister_id' : IDL.Principal,
+    'operation' : CanisterOperation,
+  });
+  const MemberUpdateRoleTransactionRequest = IDL.Record({
+    'role' : VaultRole,
+    'member_id' : IDL.Text,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const WalletUpdateNameTransactionRequest = IDL.Record({
+    'uid' : IDL.Text,
+    'name' : IDL.Text,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const ManagedCanisterAddTransactionRequest = IDL.Record({
+    'name' : IDL.Text,
+    'canister_id' : IDL.Principal,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const PolicyUpdateTransactionRequest = IDL.Record({
+    'uid' : IDL.Text,
+    'member_threshold' : IDL.Nat8,
+    'amount_threshold' : IDL.Nat64,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const DepositCyclesTransactionRequest = IDL.Record({
+    'memo' : IDL.Opt(IDL.Text),
+    'canister' : IDL.Principal,
+    'amount' : IDL.Nat64,
+  });
+  const VersionUpgradeTransactionRequest = IDL.Record({ 'version' : IDL.Text });
+  const CanisterPermissionUpdateTransactionRequest = IDL.Record({
+    'threshold' : IDL.Opt(IDL.Nat8),
+    'canister' : IDL.Principal,
+    'roles' : IDL.Vec(VaultRole),
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const MemberExtendICRC1AccountRequest = IDL.Record({
+    'account' : Account,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const PolicyRemoveTransactionRequest = IDL.Record({
+    'uid' : IDL.Text,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const EthTransferTransactionRequest = IDL.Record({
+    'to' : IDL.Text,
+    'value' : IDL.Nat,
+    'max_priority_fee_per_gas' : IDL.Nat,
+    'data' : IDL.Opt(IDL.Vec(IDL.Nat8)),
+    'memo' : IDL.Opt(IDL.Text),
+    'max_fee_per_gas' : IDL.Nat,
+    'chain_id' : IDL.Nat64,
+    'nonce' : IDL.Nat64,
+    'gas_limit' : IDL.Nat64,
+    'wallet' : IDL.Text,
+  });
+  const PolicyCreateTransactionRequest = IDL.Record({
+    'uid' : IDL.Text,
+    'destination' : IDL.Opt(DestinationScope),
+    'member_threshold' : IDL.Nat8,
+    'amount_threshold' : IDL.Nat64,
+    'wallets' : IDL.Vec(IDL.Text),
+    'currency' : Currency,
+    'batch_uid' : IDL.Opt(IDL.Text),
+  });
+  const CanisterSettingsUpdateTransactionRequest = IDL.Record({
+    'freezing_threshold' : IDL.Opt(IDL.Nat),
+    'reserved_cycles_limit' : IDL.Opt(IDL.Nat),
+    'memo' : IDL.Opt(IDL.Text),
+    'memory_allocation' : IDL.Opt(IDL.Nat),
+    'compute_allocation' : IDL.Opt(IDL.Nat),
+  });
+  const TransactionRequest = IDL.Variant({
+    'AddressBookRemoveTransactionRequestV' : AddressBookRemoveTransactionRequest,
+    'ICRC1CanistersRemoveTransactionRequestV' : ICRC1CanistersRemoveTransactionRequest,
+    'QuorumUpdateTransactionRequestV' : QuorumUpdateTransactionRequest,
+    'VaultNamingUpdateTransactionRequestV' : VaultNamingUpdateTransactionRequest,
+    'ManagedCanisterRemoveTransactionRequestV' : ManagedCanisterRemoveTransactionRequest,
+    'WalletArchiveTransactionRequestV' : WalletArchiveTransactionRequest,
+    'PurgeTransactionRequestV' : IDL.Record({}),
+    'RetryTransactionRequestV' : RetryTransactionRequest,
+    'AddressBookAddTransactionRequestV' : AddressBookAddTransactionRequest,
+    'ICRC1CanistersAddTransactionRequestV' : ICRC1CanistersAddTransactionRequest,
+    'ControllersUpdateTransactionRequestV' : ControllersUpdateTransactionRequest,
+    'MemberUpdateNameTransactionRequestV' : MemberUpdateNameTransactionRequest,
+    'TopUpTransactionRequestV' : TopUpTransactionRequest,
+    'CanisterCallTransactionRequestV' : CanisterCallTransactionRequest,
+    'VersionRollbackTransactionRequestV' : VersionRollbackTransactionRequest,
+    'AutoTopUpRuleUpdateTransactionRequestV' : AutoTopUpRuleUpdateTransactionRequest,
+    'ICRC2RevokeTransactionRequestV' : ICRC2RevokeTransactionRequest,
+    'TransferBTCTransactionRequestV' : TransferBTCTransactionRequest,
+    'TransferICRC1QuorumTransactionRequestV' : TransferICRC1QuorumTransactionRequest,
+    'WalletCreateTransactionRequestV' : WalletCreateTransactionRequest,
+    'MemberRemoveTransactionRequestV' : MemberRemoveTransactionRequest,
+    'WithdrawalTransactionRequestV' : WithdrawalTransactionRequest,
+    'MemberCreateTransactionRequestV' : MemberCreateTransactionRequest,
+    'TransferICRC1TransactionRequestV' : TransferICRC1TransactionRequest,
+    'ICRC2ApproveTransactionRequestV' : ICRC2ApproveTransactionRequest,
+    'TransferQuorumTransactionRequestV' : TransferTransactionRequest,
+    'MemberCreateTransactionRequestV2' : MemberCreateTransactionRequestV2,
+    'TransferTransactionRequestV' : TransferTransactionRequest,
+    'ManagedCanisterOperationTransactionRequestV' : ManagedCanisterOperationTransactionRequest,
+    'MemberUpdateRoleTransactionRequestV' : MemberUpdateRoleTransactionRequest,
+    'WalletUpdateNameTransactionRequestV' : WalletUpdateNameTransactionRequest,
+    'ManagedCanisterAddTransactionRequestV' : ManagedCanisterAddTransactionRequest,
+    'PolicyUpdateTransactionRequestV' : PolicyUpdateTransactionRequest,
+    'DepositCyclesTransactionRequestV' : DepositCyclesTransactionRequest,
+    'VersionUpgradeTransactionRequestV' : VersionUpgradeTransactionRequest,
+    'TopUpQuorumTransactionRequestV' : TopUpTransactionRequest,
+    'CanisterPermissionUpdateTransactionRequestV' : CanisterPermissionUpdateTransactionRequest,
+    'MemberExtendICRC1AccountRequestV' : MemberExtendICRC1AccountRequest,
+    'PolicyRemoveTransactionRequestV' : PolicyRemoveTransactionRequest,
+    'EthTransferTransactionRequestV' : EthTransferTransactionRequest,
+    'PolicyCreateTransactionRequestV' : PolicyCreateTransactionRequest,
+    'CanisterSettingsUpdateTransactionRequestV' : CanisterSettingsUpdateTransactionRequest,
+  });
+  const TransactionMetadataRequest = IDL.Record({
+    'transaction_id' : IDL.Nat64,
+    'metadata' : TransactionMetadata,
+  });
+  return IDL.Service({
+    'acknowledge_activity' : IDL.Func([], [IDL.Nat64], []),
+    'append_wasm_chunk' : IDL.Func([IDL.Vec(IDL.Nat8)], [IDL.Nat64], []),
+    'approve' : IDL.Func(
+        [IDL.Vec(TransactionApproveRequest)],
+        [IDL.Vec(TransactionCandid)],
+        [],
+      ),
+    'canister_balance' : IDL.Func([], [IDL.Nat64], ['query']),
+    'comment' : IDL.Func([TransactionCommentRequest], [TransactionCandid], []),
+    'commit_wasm_upload' : IDL.Func([], [UploadedWasm], []),
+    'delete_uploaded_wasm' : IDL.Func([IDL.Text], [], []),
+    'execute' : IDL.Func([], [], []),
+    'get_allowances' : IDL.Func([IDL.Text], [IDL.Vec(WalletAllowance)], []),
+    'get_balances' : IDL.Func(
+        [IDL.Vec(IDL.Text)],
+        [IDL.Vec(WalletBalance)],
+        [],
+      ),
+    'get_balances_cached' : IDL.Func(
+        [IDL.Vec(IDL.Text)],
+        [IDL.Vec(WalletBalance)],
+        ['query'],
+      ),
+    'get_btc_address' : IDL.Func([IDL.Text], [IDL.Text], []),
+    'get_btc_balance' : IDL.Func([IDL.Text], [IDL.Nat64], []),
+    'get_btc_utxos' : IDL.Func([IDL.Text], [IDL.Vec(Utxo)], []),
+    'get_canister_status' : IDL.Func([], [VaultCanisterStatus], []),
+    'get_canister_status_cached' : IDL.Func(
+        [],
+        [IDL.Opt(VaultCanisterStatus)],
+        ['query'],
+      ),
+    'get_controllers' : IDL.Func([], [IDL.Vec(IDL.Principal)], []),
+    'get_deposits' : IDL.Func(
+        [IDL.Opt(IDL.Text), IDL.Nat64, IDL.Nat64],
+        [DepositPage],
+        ['query'],
+      ),
+    'get_eth_address' : IDL.Func([IDL.Text], [IDL.Text], []),
+    'get_my_pending_actions' : IDL.Func([], [PendingActions], ['query']),
+    'get_outgoing_amounts' : IDL.Func(
+        [OutgoingReportRequest],
+        [IDL.Vec(OutgoingReportEntry)],
+        ['query'],
+      ),
+    'get_schema_version' : IDL.Func([], [IDL.Nat32], ['query']),
+    'get_state' : IDL.Func([IDL.Opt(IDL.Nat64)], [VaultState], ['query']),
+    'get_transactions_all' : IDL.Func(
+        [],
+        [IDL.Vec(TransactionCandid)],
+        ['query'],
+      ),
+    'get_transfer_discrepancies' : IDL.Func(
+        [],
+        [IDL.Vec(TransferDiscrepancy)],
+        ['query'],
+      ),
+    'get_trusted_origins_certified' : IDL.Func(
+        [],
+        [
+          IDL.Record({
+            'certificate' : IDL.Vec(IDL.Nat8),
+            'witness' : IDL.Vec(IDL.Nat8),
+            'response' : IDL.Vec(IDL.Text),
+          }),
+        ],
+        ['query'],
+      ),
+    'get_uploaded_wasms' : IDL.Func([], [IDL.Vec(UploadedWasm)], ['query']),
+    'get_version' : IDL.Func([], [IDL.Text], ['query']),
+    'http_request' : IDL.Func([HttpRequest], [HttpResponse], ['query']),
+    'issue_export_token' : IDL.Func([], [ExportToken], []),
+    'list_canister_snapshots' : IDL.Func(
+        [IDL.Principal],
+        [IDL.Vec(CanisterSnapshot)],
+        [],
+      ),
+    'poll_withdrawals' : IDL.Func([], [IDL.Vec(TransactionCandid)], []),
+    'reconcile' : IDL.Func([], [IDL.Vec(TransferDiscrepancy)], []),
+    'request_transaction' : IDL.Func(
+        [IDL.Vec(TransactionRequest)],
+        [IDL.Vec(TransactionCandid)],
+        [],
+      ),
+    'sync_deposits' : IDL.Func([], [IDL.Nat64], []),
+    'tag_deposit' : IDL.Func(
+        [IDL.Principal, IDL.Nat64, IDL.Opt(IDL.Text)],
+        [Deposit],
+        [],
+      ),
+    'update_metadata' : IDL.Func(
+        [TransactionMetadataRequest],
+        [TransactionCandid],
+        [],
+      ),
+  });
+};
+export const init = ({ IDL }) => {
+  const BitcoinNetwork = IDL.Variant({
+    'mainnet' : IDL.Null,
+    'regtest' : IDL.Null,
+    'testnet' : IDL.Null,
+  });
+  const Conf = IDL.Record({
+    'cketh_ledger' : IDL.Opt(IDL.Text),
+    'ecdsa_key_name' : IDL.Opt(IDL.Text),
+    'origins' : IDL.Vec(IDL.Text),
+    'ckbtc_minter' : IDL.Opt(IDL.Text),
+    'eth_rpc_url' : IDL.Opt(IDL.Text),
+    'icp_index' : IDL.Opt(IDL.Text),
+    'btc_network' : IDL.Opt(BitcoinNetwork),
+    'ckbtc_ledger' : IDL.Opt(IDL.Text),
+    'repo_canister' : IDL.Text,
+    'cketh_minter' : IDL.Opt(IDL.Text),
+    'evm_rpc_canister' : IDL.Opt(IDL.Text),
+    'release_channel' : IDL.Opt(IDL.Text),
+  });
+  return [IDL.Principal, Conf];
+};
\ No newline at end of file
@@ -2,17 +2,96 @@ import {getIdentity} from "../util/deployment.util";
 import {expect} from "chai";
 import {principalToAddress} from "ictool";
 import {
-    getTransactionByIdFromGetAllTrs, requestCreateMemberTransaction, requestCreatePolicyTransaction,
-    requestCreateWalletTransaction, requestTopUpQuorumTransaction,
+    getRawTransactionById, getTransactionByIdFromGetAllTrs, getVaultActor, requestCreateMemberTransaction, requestCreatePolicyTransaction,
+    requestCreateWalletTransaction, requestRawCreatePolicyTransaction, requestRawTransaction, requestTopUpQuorumTransaction,
     requestTopUpTransaction,
     verifyTransaction
 } from "./helper";
-import {sleep} from "../util/call.util";
+import {execute, sleep} from "../util/call.util";
+import {DFX} from "../constanst/dfx.const";
+import {Principal} from "@dfinity/principal";
 import {Approve,
     ApproveRequest, Currency, WalletCreateTransaction, Network, TopUpTransaction, TransactionState, TransactionType, VaultManager, VaultRole} from "@nfid/vaults";
 
 require('./bigintextension.js');
 
+//the policy checks run before the ledger and the cycles minter are called, so they work against the local replica
+describe("TopUp Destination Policies", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let manager: VaultManager;
+    let actor;
+    let walletUid;
+    let whitelistedPolicy;
+    const otherCanister = Principal.fromText("qoctq-giaaa-aaaaa-aaaea-cai")
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/ledger.sh`))
+        await console.log(execute(`./test/resource/vault.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        let wallet = await requestCreateWalletTransaction(manager, "topUpWallet", Network.IC) as Array<WalletCreateTransaction>
+        walletUid = wallet[0].uid
+        await manager.execute()
+        await requestRawCreatePolicyTransaction(actor, {ICP: null}, 1, 0n, [walletUid], {DenyNotWhitelisted: null})
+        whitelistedPolicy = await requestRawCreatePolicyTransaction(actor, {ICP: null}, 1, 0n, [walletUid], {Whitelisted: null})
+        await manager.execute()
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    async function requestTopUp(canister: Array<Principal>) {
+        let topUp = await requestRawTransaction(actor, {
+            TopUpTransactionRequestV: {currency: {ICP: null}, wallet: walletUid, amount: 100n, canister}
+        })
+        await manager.execute()
+        return await getRawTransactionById(actor, topUp.common.id)
+    }
+
+    it("Top-up of a canister outside the address book is denied", async function () {
+        let topUp = await requestTopUp([otherCanister])
+        expect(topUp.common.state).deep.eq({Failed: null})
+        expect(topUp.common.error).deep.eq([{DestinationNotAllowed: null}])
+    });
+
+    it("Top-up of the vault itself is treated as whitelisted", async function () {
+        let topUp = await requestTopUp([])
+        expect(topUp.common.error).not.deep.eq([{DestinationNotAllowed: null}])
+        expect(topUp.policy).deep.eq([whitelistedPolicy.uid])
+    });
+
+    it("Top-up of a canister in the address book uses the whitelisted policy", async function () {
+        await requestRawTransaction(actor, {
+            AddressBookAddTransactionRequestV: {uid: "canister", label: "canister", address: {ICRC1: {owner: otherCanister, subaccount: []}}, batch_uid: []}
+        })
+        await manager.execute()
+        let topUp = await requestTopUp([otherCanister])
+        expect(topUp.common.error).not.deep.eq([{DestinationNotAllowed: null}])
+        expect(topUp.policy).deep.eq([whitelistedPolicy.uid])
+    });
+
+    it("Cycles deposits are checked against the address book", async function () {
+        let cyclesPolicy = await requestRawCreatePolicyTransaction(actor, {Cycles: null}, 1, 0n, [], {Whitelisted: null})
+        await manager.execute()
+        let unknown = await requestRawTransaction(actor, {
+            DepositCyclesTransactionRequestV: {canister: Principal.fromText("renrk-eyaaa-aaaaa-aaada-cai"), amount: 1000n, memo: []}
+        })
+        let known = await requestRawTransaction(actor, {
+            DepositCyclesTransactionRequestV: {canister: otherCanister, amount: 1000n, memo: []}
+        })
+        await manager.execute()
+        unknown = await getRawTransactionById(actor, unknown.common.id)
+        expect(unknown.common.error).deep.eq([{CouldNotDefinePolicy: null}])
+        known = await getRawTransactionById(actor, known.common.id)
+        expect(known.policy).deep.eq([cyclesPolicy.uid])
+    });
+});
+
 describe.skip("TopUp Transactions", () => {
     //predefined wallet address to fill with ICP manually
     const walletAddress = "706ab8c2d9585942dc4bdc5ed73188d7f56f97374a36b63b08ca45456ae699e3";
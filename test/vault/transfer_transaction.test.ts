@@ -5,12 +5,16 @@ import {expect} from "chai";
 import {fromHexString, principalToAddress, principalToAddressBytes} from "ictool";
 import {Principal} from "@dfinity/principal";
 import {
+    getRawTransactionById,
     getTransactionByIdFromGetAllTrs,
+    getVaultActor,
+    requestRawTransaction,
     requestCreateMemberTransaction,
     requestCreatePolicyTransaction,
     requestCreateWalletTransaction,
     requestICRC1TransferTransaction,
     requestPurgeTransaction,
+    requestRawCreatePolicyTransaction,
     requestQuorumTransferTransaction,
     requestTransferTransaction,
     requestUpdateQuorumTransaction,
@@ -104,6 +108,71 @@ describe("Transfer Transactions", () => {
         expect(tr.error.CanisterReject.message).eq("ledger transfer error: InsufficientFunds { balance: Tokens { e8s: 99989900 } }")
     });
 
+    it("Retry of a failed transfer inside the dedup window is executed again", async function () {
+        let actor = await getVaultActor(canister_id, admin_identity)
+        let all = (await actor.get_transactions_all() as Array<any>).map((t) => Object.values(t)[0] as any)
+        let failed = all.find((t) => t.common.error.length > 0 && t.common.error[0].CanisterReject !== undefined)
+        let retry = await requestRawTransaction(actor, {RetryTransactionRequestV: {transaction_id: failed.common.id, memo: []}})
+        await manager.execute()
+        retry = await getRawTransactionById(actor, retry.common.id)
+        expect(retry.common.state).deep.eq({Failed: null})
+        expect(retry.common.error[0].RetryWindowExpired).eq(undefined)
+        expect(retry.common.error[0].CanisterReject).not.eq(undefined)
+        let executed = all.find((t) => t.common.state.Executed !== undefined)
+        retry = await requestRawTransaction(actor, {RetryTransactionRequestV: {transaction_id: executed.common.id, memo: []}})
+        await manager.execute()
+        retry = await getRawTransactionById(actor, retry.common.id)
+        expect(retry.common.error).deep.eq([{TransactionNotRetryable: null}])
+    });
+
+    it("Retry of a failed transfer to a destination denied since then fails", async function () {
+        let actor = await getVaultActor(canister_id, admin_identity)
+        let createWallet = await requestCreateWalletTransaction(manager, "retryWallet", Network.IC) as Array<WalletCreateTransaction>
+        let retryWalletUid = createWallet[0].uid
+        await requestCreatePolicyTransaction(manager, 1, 10, [retryWalletUid])
+        await manager.execute()
+        let trRequestResponse = await requestTransferTransaction(manager, address, retryWalletUid, 100)
+        await manager.execute()
+        let failed = await getTransactionByIdFromGetAllTrs(manager, trRequestResponse[0].id) as TransferTransaction
+        expect(failed.state).eq(TransactionState.Failed)
+        await requestRawCreatePolicyTransaction(actor, {ICP: null}, 1, 0n, [retryWalletUid], {DenyNotWhitelisted: null})
+        await manager.execute()
+        let retry = await requestRawTransaction(actor, {RetryTransactionRequestV: {transaction_id: failed.id, memo: []}})
+        await manager.execute()
+        retry = await getRawTransactionById(actor, retry.common.id)
+        expect(retry.common.error).deep.eq([{DestinationNotAllowed: null}])
+    });
+
+    it("Retry of a failed transfer from an archived wallet fails", async function () {
+        let actor = await getVaultActor(canister_id, admin_identity)
+        let createWallet = await requestCreateWalletTransaction(manager, "retryWallet", Network.IC) as Array<WalletCreateTransaction>
+        let retryWalletUid = createWallet[0].uid
+        await requestCreatePolicyTransaction(manager, 1, 10, [retryWalletUid])
+        await manager.execute()
+        let trRequestResponse = await requestTransferTransaction(manager, address, retryWalletUid, 100)
+        await manager.execute()
+        let failed = await getTransactionByIdFromGetAllTrs(manager, trRequestResponse[0].id) as TransferTransaction
+        expect(failed.state).eq(TransactionState.Failed)
+        await requestRawTransaction(actor, {
+            WalletArchiveTransactionRequestV: {uid: retryWalletUid, require_zero_balance: false, batch_uid: []}
+        })
+        await manager.execute()
+        let retry = await requestRawTransaction(actor, {RetryTransactionRequestV: {transaction_id: failed.id, memo: []}})
+        await manager.execute()
+        retry = await getRawTransactionById(actor, retry.common.id)
+        expect(retry.common.error).deep.eq([{WalletArchived: null}])
+    });
+
+    it("Reconcile leaves transfers which never reached the ledger as they are", async function () {
+        let actor = await getVaultActor(canister_id, admin_identity)
+        let discrepancies = await actor.reconcile() as Array<any>
+        expect(discrepancies.filter((d) => d.kind.StateCorrected !== undefined).length).eq(0)
+        expect(discrepancies.filter((d) => d.kind.BlockMismatch !== undefined).length).eq(0)
+        let all = (await actor.get_transactions_all() as Array<any>).map((t) => Object.values(t)[0] as any)
+        let failed = all.find((t) => t.common.error.length > 0 && t.common.error[0].CanisterReject !== undefined)
+        expect(failed.common.state).deep.eq({Failed: null})
+    });
+
     it("Trs approved and executed from member", async function () {
         await requestCreateMemberTransaction(manager, member_address, "memberName", VaultRole.MEMBER)
         await manager.execute()
@@ -0,0 +1,116 @@
+import {DFX} from "../constanst/dfx.const";
+import {getActor, getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {principalToAddress} from "ictool";
+import {sha256} from "ethers/lib/utils";
+import {IDL} from "@dfinity/candid";
+import {readWasmFile} from "../vault_repo/vault_repo.test";
+import {
+    getRawTransactionById,
+    getVaultActor,
+    requestCreateMemberTransaction,
+    requestRawTransaction,
+    requestUpdateQuorumTransaction
+} from "./helper";
+import {VaultManager, VaultRole} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+const repoStubIdl = ({IDL}) => {
+    const VaultWasm = IDL.Record({
+        wasm_module: IDL.Vec(IDL.Nat8),
+        version: IDL.Text,
+        hash: IDL.Text,
+        description: IDL.Opt(IDL.Text),
+        schema_version: IDL.Opt(IDL.Nat32),
+    });
+    return IDL.Service({add_version: IDL.Func([VaultWasm], [], [])});
+};
+
+//repo_stub takes the place of the repo, it publishes whatever hash it is given
+describe("Upgrade Hash Verification", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let member_identity = getIdentity("87654321876543218765432187654322")
+    let manager: VaultManager;
+    let actor;
+    let repo;
+    let vaultWasm: Uint8Array;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        execute(`dfx deploy repo_stub --specified-id=7jlkn-paaaa-aaaap-abvpa-cai`)
+        await console.log(execute(`./test/resource/vault.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        repo = await getActor("7jlkn-paaaa-aaaap-abvpa-cai", admin_identity, repoStubIdl as IDL.InterfaceFactory)
+        vaultWasm = readWasmFile(".dfx/local/canisters/vault/vault.wasm")
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    function publish(version: string, module: Uint8Array, hash: string) {
+        return repo.add_version({
+            wasm_module: Array.from(module),
+            version,
+            hash,
+            description: [],
+            schema_version: [1]
+        })
+    }
+
+    it("Member request is rejected before the repo is called", async function () {
+        let member = getIdentity("87654321876543218765432187654323")
+        await requestCreateMemberTransaction(manager, principalToAddress(member.getPrincipal() as any), "plain member", VaultRole.MEMBER)
+        await manager.execute()
+        await publish("99.0.0", vaultWasm, "0x" + "00".repeat(32))
+        let memberActor = await getVaultActor(canister_id, member)
+        let error
+        try {
+            await requestRawTransaction(memberActor, {VersionUpgradeTransactionRequestV: {version: "99.0.0"}})
+        } catch (e) {
+            error = e
+        }
+        expect(error.message).contains("Not permitted")
+    });
+
+    it("Upgrade to a version published with a wrong hash is rejected on request", async function () {
+        await publish("99.0.0", vaultWasm, "0x" + "00".repeat(32))
+        let error
+        try {
+            await requestRawTransaction(actor, {VersionUpgradeTransactionRequestV: {version: "99.0.0"}})
+        } catch (e) {
+            error = e
+        }
+        expect(error.message).contains("Hashes do not match")
+    });
+
+    it("Module swapped after the request is not installed", async function () {
+        await requestCreateMemberTransaction(manager, principalToAddress(member_identity.getPrincipal() as any), "member", VaultRole.ADMIN)
+        await manager.execute()
+        await requestUpdateQuorumTransaction(manager, 2)
+        await manager.execute()
+        let hash = sha256(vaultWasm)
+        await publish("99.0.0", vaultWasm, hash)
+        let tr = await requestRawTransaction(actor, {VersionUpgradeTransactionRequestV: {version: "99.0.0"}})
+        await manager.execute()
+        tr = await getRawTransactionById(actor, tr.common.id)
+        expect(tr.common.state).deep.eq({Pending: null})
+        expect(tr.hash).deep.eq([hash])
+
+        let other = readWasmFile("test/vault_repo/vault_001.wasm")
+        await publish("99.0.0", other, sha256(other))
+        let memberActor = await getVaultActor(canister_id, member_identity)
+        await memberActor.approve([{transaction_id: tr.common.id, state: {Approved: null}, reason: []}])
+        await manager.execute()
+        tr = await getRawTransactionById(actor, tr.common.id)
+        expect(tr.common.state).deep.eq({Failed: null})
+        expect(tr.common.error[0].CanisterReject.message).contains("Wasm hash mismatch")
+        expect(await manager.getVersion()).not.eq("99.0.0")
+    });
+});
@@ -6,6 +6,7 @@ import {execute, sleep} from "../util/call.util";
 import {expect} from "chai";
 import {
     getTransactionByIdFromGetAllTrs,
+    getVaultActor,
     requestCreateWalletTransaction,
     requestVersionUpgradeTransaction,
     verifyTransaction
@@ -47,6 +48,7 @@ describe("Upgrade Transactions", () => {
         let wasm: VaultWasm = {
             description: [],
             wasm_module: Array.from(wasm_bytes),
+            schema_version: [],
             hash: hash,
             version: "0.0.1"
         }
@@ -95,6 +97,7 @@ describe("Upgrade Transactions", () => {
         let wasm: VaultWasm = {
             description: [],
             wasm_module: Array.from(wasm_bytes),
+            schema_version: [],
             hash: hash,
             version: latestVaultVersion
         }
@@ -117,7 +120,6 @@ describe("Upgrade Transactions", () => {
         expect(state.wallets.length).eq(2);
     });
 
-
     it("Upgrade approved and failed with smaller version", async function () {
         let trRequestResponse = await requestVersionUpgradeTransaction(manager, "0.0.1");
         let trs = trRequestResponse[0];
@@ -149,6 +151,31 @@ describe("Upgrade Transactions", () => {
     });
 
 
+    it("Newer repo version is proposed from the global timer", async function () {
+        let canister_id = DFX.GET_CANISTER_ID("vault");
+        let wasm_bytes = readWasmFile(".dfx/local/canisters/vault/vault.wasm");
+        let actor = await getActor(vault_repo_id, admin, vrIdl);
+        await actor.add_version({
+            description: ["timer proposal"],
+            wasm_module: Array.from(wasm_bytes),
+            schema_version: [],
+            hash: sha256(wasm_bytes),
+            version: "99.0.0"
+        });
+        //the upgrade re-arms the timer and resets the check interval kept on the heap
+        execute(`dfx canister install --mode upgrade --upgrade-unchanged vault --argument '(principal "3ekng-5nqql-esu4u-64sla-pcm5o-hjatn-hwjo7-vk7ya-ianug-zqqyy-iae", record { origins = vec {}; repo_canister = "7jlkn-paaaa-aaaap-abvpa-cai" })'`)
+        await sleep(70);
+        let vaultActor = await getVaultActor(canister_id, admin_identity)
+        let proposals = (await vaultActor.get_transactions_all() as Array<any>)
+            .filter((t) => t.UpgradeTransactionV !== undefined)
+            .map((t) => t.UpgradeTransactionV)
+        expect(proposals.length).eq(1)
+        expect(proposals[0].version).eq("99.0.0")
+        expect(proposals[0].common.state).deep.eq({Blocked: null})
+        expect(proposals[0].common.memo).deep.eq(["timer proposal"])
+    });
+
+
     function buildExpectedVersionUpgradeTransaction(state) {
         let expectedApprove: Approve = {
             createdDate: 0n,
@@ -1,11 +1,15 @@
 import {DFX} from "../constanst/dfx.const";
 import {getIdentity} from "../util/deployment.util";
-import {principalToAddress} from "ictool";
+import {fromHexString, principalToAddress, principalToAddressBytes} from "ictool";
+import {Principal} from "@dfinity/principal";
 import {execute, sleep} from "../util/call.util";
 import {expect} from "chai";
 import {
+    getRawTransactionById,
     getTransactionByIdFromGetAllTrs,
+    getVaultActor,
     requestCreateWalletTransaction,
+    requestRawTransaction,
     requestUpdateWalletNameTransaction,
     verifyTransaction
 } from "./helper";
@@ -83,12 +87,14 @@ describe("Wallet Transactions", () => {
 
 
     it("Request 2 Wallets With The Same Uid failed", async function () {
-        let request1 = new WalletCreateTransactionRequest("uniqueId", "11112123123", Network.IC);
+        //the uid is the hex of the wallet subaccount
+        let uniqueId = "ab".repeat(32)
+        let request1 = new WalletCreateTransactionRequest(uniqueId, "11112123123", Network.IC);
         let response1 = await manager.requestTransaction([request1])
         await sleep(2)
         let transaction1 = await getTransactionByIdFromGetAllTrs(manager, response1[0].id) as WalletCreateTransaction
         expect(transaction1.state).eq(TransactionState.Executed)
-        let request2 = new WalletCreateTransactionRequest("uniqueId", "345346456", Network.IC);
+        let request2 = new WalletCreateTransactionRequest(uniqueId, "345346456", Network.IC);
         let response2 = await manager.requestTransaction([request2])
         await sleep(2)
         let transaction2 = await getTransactionByIdFromGetAllTrs(manager, response2[0].id) as WalletCreateTransaction
@@ -97,6 +103,96 @@ describe("Wallet Transactions", () => {
     });
 
 
+    it("Wallet with a uid that is not a subaccount failed", async function () {
+        let actor = await getVaultActor(canister_id, admin_identity)
+        let create = await requestRawTransaction(actor, {
+            WalletCreateTransactionRequestV: {uid: "notASubaccount", name: "invalid", network: {IC: null}, batch_uid: []}
+        })
+        await manager.execute()
+        create = await getRawTransactionById(actor, create.common.id)
+        expect(create.common.state).deep.eq({Failed: null})
+        expect(create.common.error).deep.eq([{InvalidUID: null}])
+    });
+
+    it("Wallet without uid gets a derived subaccount and receive addresses", async function () {
+        let actor = await getVaultActor(canister_id, admin_identity)
+        let create = await requestRawTransaction(actor, {
+            WalletCreateTransactionRequestV: {uid: "", name: "derived", network: {IC: null}, batch_uid: []}
+        })
+        await manager.execute()
+        create = await getRawTransactionById(actor, create.common.id)
+        expect(create.common.state).deep.eq({Executed: null})
+        expect(create.uid).match(/^[0-9a-f]{64}$/)
+        let state = await actor.get_state([])
+        let wallet = state.wallets.find((w) => w.uid === create.uid)
+        let subaccount = fromHexString(create.uid)
+        expect(wallet.account_identifier).deep.eq([principalToAddress(Principal.fromText(canister_id) as any, subaccount)])
+        expect(wallet.icrc1_account[0].startsWith(`${canister_id}-`)).eq(true)
+        expect(wallet.icrc1_account[0].endsWith(`.${create.uid.replace(/^0+/, "")}`)).eq(true)
+    });
+
+    it("ArchiveWallet executed and ICRC-2 approve from the archived wallet failed", async function () {
+        let actor = await getVaultActor(canister_id, admin_identity)
+        let archive = await requestRawTransaction(actor, {
+            WalletArchiveTransactionRequestV: {uid: uid, require_zero_balance: true, batch_uid: []}
+        })
+        await manager.execute()
+        archive = await getRawTransactionById(actor, archive.common.id)
+        expect(archive.common.state).deep.eq({Executed: null})
+        let approve = await requestRawTransaction(actor, {
+            ICRC2ApproveTransactionRequestV: {
+                wallet: uid,
+                ledger_id: Principal.fromText("ryjl3-tyaaa-aaaaa-aaaba-cai"),
+                spender: {owner: admin_identity.getPrincipal(), subaccount: []},
+                amount: 100n,
+                expires_at: [],
+                expected_allowance: [],
+                memo: [],
+            }
+        })
+        await manager.execute()
+        approve = await getRawTransactionById(actor, approve.common.id)
+        expect(approve.common.state).deep.eq({Failed: null})
+        expect(approve.common.error).deep.eq([{WalletArchived: null}])
+    });
+
+    it("ArchiveWallet of a funded wallet failed", async function () {
+        let actor = await getVaultActor(canister_id, admin_identity)
+        let createWallet = await requestCreateWalletTransaction(manager, "fundedWallet", Network.IC)
+        let fundedUid = (createWallet[0] as WalletCreateTransaction).uid
+        await manager.execute()
+        let walBytes = principalToAddressBytes(Principal.fromText(canister_id) as any, fromHexString(fundedUid))
+        DFX.LEDGER_FILL_BALANCE(walBytes.toString().replaceAll(',', ';'))
+        let archive = await requestRawTransaction(actor, {
+            WalletArchiveTransactionRequestV: {uid: fundedUid, require_zero_balance: true, batch_uid: []}
+        })
+        await manager.execute()
+        archive = await getRawTransactionById(actor, archive.common.id)
+        expect(archive.common.error).deep.eq([{WalletNotEmpty: null}])
+    });
+
+    it("ArchiveWallet of an ETH wallet needs the zero balance check to be skipped without an RPC endpoint", async function () {
+        let actor = await getVaultActor(canister_id, admin_identity)
+        let create = await requestRawTransaction(actor, {
+            WalletCreateTransactionRequestV: {uid: "", name: "ethWallet", network: {ETH: null}, batch_uid: []}
+        })
+        await manager.execute()
+        let ethUid = (await getRawTransactionById(actor, create.common.id)).uid
+        let checked = await requestRawTransaction(actor, {
+            WalletArchiveTransactionRequestV: {uid: ethUid, require_zero_balance: true, batch_uid: []}
+        })
+        await manager.execute()
+        checked = await getRawTransactionById(actor, checked.common.id)
+        expect(checked.common.state).deep.eq({Failed: null})
+        expect(checked.common.error[0].CanisterReject.message).eq("No ETH RPC endpoint is configured")
+        let unchecked = await requestRawTransaction(actor, {
+            WalletArchiveTransactionRequestV: {uid: ethUid, require_zero_balance: false, batch_uid: []}
+        })
+        await manager.execute()
+        unchecked = await getRawTransactionById(actor, unchecked.common.id)
+        expect(unchecked.common.state).deep.eq({Executed: null})
+    });
+
     function buildExpectedWalletCreateTransaction(state) {
         let expectedApprove: Approve = {
             createdDate: 0n,
@@ -0,0 +1,94 @@
+import {DFX} from "../constanst/dfx.const";
+import {getIdentity} from "../util/deployment.util";
+import {execute} from "../util/call.util";
+import {expect} from "chai";
+import {Principal} from "@dfinity/principal";
+import {
+    getRawTransactionById,
+    getVaultActor,
+    requestCreateWalletTransaction,
+    requestRawTransaction
+} from "./helper";
+import {Network, VaultManager, WalletCreateTransaction} from "@nfid/vaults";
+
+require('./bigintextension.js');
+
+//the stub canister plays both the ckBTC ledger and the ckBTC minter, see test/resource/minter_stub
+describe("Withdrawal Transactions", () => {
+    let canister_id;
+    let admin_identity = getIdentity("87654321876543218765432187654321")
+    let manager: VaultManager;
+    let actor;
+    let minter: Principal;
+    let walletUid;
+    before(async () => {
+        DFX.INIT();
+        DFX.USE_TEST_ADMIN();
+        await console.log(execute(`./test/resource/vault_minter.sh`))
+        canister_id = DFX.GET_CANISTER_ID("vault");
+        minter = Principal.fromText(DFX.GET_CANISTER_ID("minter_stub"));
+        manager = new VaultManager(canister_id, admin_identity);
+        await manager.resetToLocalEnv();
+        actor = await getVaultActor(canister_id, admin_identity)
+        let wallet = await requestCreateWalletTransaction(manager, "ckWallet", Network.IC) as Array<WalletCreateTransaction>
+        walletUid = wallet[0].uid
+        await requestRawTransaction(actor, {
+            ICRC1CanistersAddTransactionRequestV: {index_canister: [], ledger_canister: minter, batch_uid: []}
+        })
+        await manager.execute()
+    });
+
+    after(() => {
+        DFX.STOP();
+    });
+
+    function withdrawalRequest(amount: bigint, kind: object = {CkBTC: null}) {
+        return {
+            WithdrawalTransactionRequestV: {
+                kind,
+                wallet: walletUid,
+                amount: amount,
+                address: "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+                memo: []
+            }
+        }
+    }
+
+    it("Withdrawal goes to the configured minter and is polled to pending", async function () {
+        let withdrawal = await requestRawTransaction(actor, withdrawalRequest(1000n))
+        await manager.execute()
+        let executed = await getRawTransactionById(actor, withdrawal.common.id)
+        expect(Object.keys(executed.common.state)[0]).eq("Executed")
+        expect(executed.minter.toText()).eq(minter.toText())
+        expect(executed.ledger.toText()).eq(minter.toText())
+        expect(executed.withdrawal_id.length).eq(1)
+        await actor.poll_withdrawals()
+        let polled = await getRawTransactionById(actor, withdrawal.common.id)
+        expect(Object.keys(polled.status[0])[0]).eq("Pending")
+        let allowances = await actor.get_allowances(walletUid)
+        let minterAllowance = allowances.find(a => a.spender.owner.toText() === minter.toText())
+        expect(minterAllowance.allowance).eq(0n)
+    });
+
+    it("Failed withdrawal resets the minter allowance", async function () {
+        execute(`dfx canister call minter_stub fail_next`)
+        let withdrawal = await requestRawTransaction(actor, withdrawalRequest(2000n))
+        await manager.execute()
+        let failed = await getRawTransactionById(actor, withdrawal.common.id)
+        expect(Object.keys(failed.common.state)[0]).eq("Failed")
+        expect(failed.approve_block_index.length).eq(1)
+        expect(failed.common.error[0].CanisterReject.message).contains("TemporarilyUnavailable")
+        let allowances = await actor.get_allowances(walletUid)
+        let minterAllowance = allowances.find(a => a.spender.owner.toText() === minter.toText())
+        expect(minterAllowance.allowance).eq(0n)
+    });
+
+    it("Withdrawal burns from the ledger configured for its kind", async function () {
+        //the mainnet ckETH ledger is not registered in this vault
+        let withdrawal = await requestRawTransaction(actor, withdrawalRequest(1000n, {CkETH: null}))
+        await manager.execute()
+        let failed = await getRawTransactionById(actor, withdrawal.common.id)
+        expect(failed.ledger.toText()).eq("ss2fx-dyaaa-aaaar-qacoq-cai")
+        expect(failed.common.error).deep.eq([{ICRC1CanisterNotExists: null}])
+    });
+});
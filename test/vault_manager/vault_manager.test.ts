@@ -37,6 +37,7 @@ describe("VM Test", () => {
         let wasm: VaultWasm = {
             description: [],
             wasm_module: Array.from(wasm_bytes),
+            schema_version: [],
             hash: hash,
             version: "0.0.1"
         }
@@ -136,6 +137,7 @@ describe("VM Test", () => {
         let wasm: VaultWasm = {
             description: [],
             wasm_module: Array.from(wasm_bytes),
+            schema_version: [],
             hash: hash,
             version: "0.0.2"
         }
@@ -10,6 +10,7 @@ export interface VaultWasm {
     'hash' : string,
     'description' : [] | [string],
     'version' : string,
+    'schema_version' : [] | [number],
 }
 export interface VersionWrapper {
     'description' : [] | [string],
@@ -4,6 +4,7 @@ export const idlFactory = ({ IDL }) => {
         'hash' : IDL.Text,
         'description' : IDL.Opt(IDL.Text),
         'version' : IDL.Text,
+        'schema_version' : IDL.Opt(IDL.Nat32),
     });
     const VersionWrapper = IDL.Record({
         'description' : IDL.Opt(IDL.Text),
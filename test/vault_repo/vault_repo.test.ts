@@ -32,6 +32,7 @@ describe("VR Test", () => {
         let wasm: VaultWasm = {
             description: ["lorem"],
             wasm_module: Array.from(wasm_bytes),
+            schema_version: [],
             hash: hash,
             version: "0.0.1"
         }
@@ -49,6 +50,7 @@ describe("VR Test", () => {
         let wasm: VaultWasm = {
             description: [],
             wasm_module: Array.from(wasm_bytes),
+            schema_version: [],
             hash: hash,
             version: "0.0.2"
         }
@@ -79,6 +81,7 @@ describe("VR Test", () => {
         let wasm: VaultWasm = {
             description: [],
             wasm_module: Array.from(wasm_bytes),
+            schema_version: [],
             hash: hash,
             version: "0.0.3"
         }